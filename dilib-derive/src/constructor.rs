@@ -1,47 +1,145 @@
+use crate::dependency::Dependency;
 use proc_macro2::Ident;
 use syn::parenthesized;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::token::{Comma, Paren};
+use syn::{Lit, Meta, MetaNameValue, NestedMeta, Token, Type};
 
 #[derive(Debug)]
 pub struct TargetConstructor {
     pub(crate) name: String,
-    pub(crate) args: Vec<String>,
+    pub(crate) args: Vec<ConstructorArg>,
 }
 
 impl TargetConstructor {
-    pub fn new(name: String, args: Vec<String>) -> Self {
+    pub fn new(name: String, args: Vec<ConstructorArg>) -> Self {
         TargetConstructor { name, args }
     }
+
+    // Returns `true` if the field is passed to the constructor
+    pub fn has_field(&self, field: &Ident) -> bool {
+        self.args
+            .iter()
+            .any(|arg| matches!(arg, ConstructorArg::Field(ident) if ident == field))
+    }
+
+    pub fn deps(&self) -> impl Iterator<Item = &Dependency> {
+        self.args.iter().filter_map(|arg| match arg {
+            ConstructorArg::Dependency(dep) => Some(dep.as_ref()),
+            _ => None,
+        })
+    }
+
+    pub fn deps_mut(&mut self) -> impl Iterator<Item = &mut Dependency> {
+        self.args.iter_mut().filter_map(|arg| match arg {
+            ConstructorArg::Dependency(dep) => Some(dep.as_mut()),
+            _ => None,
+        })
+    }
+}
+
+/// An argument passed to the constructor.
+#[derive(Debug)]
+pub enum ConstructorArg {
+    /// A field of the struct: `field`
+    Field(Ident),
+    /// A literal: `retries = 3`
+    Literal(Lit),
+    /// A value resolved from the container: `repo: Arc<Repo> = name("main")`
+    Dependency(Box<Dependency>),
 }
 
 // new(param1, param2, ...)
 #[derive(Debug)]
 pub struct TargetConstructorTokens {
-    method: Ident,
+    pub(crate) method: Ident,
     #[allow(dead_code)]
     paren_token: Paren,
-    args: Punctuated<Ident, Comma>,
-}
-
-impl TargetConstructorTokens {
-    pub fn into_constructor(self) -> TargetConstructor {
-        let name = self.method.to_string();
-        let args = self.args.iter().map(|s| s.to_string()).collect::<Vec<_>>();
-
-        TargetConstructor::new(name, args)
-    }
+    pub(crate) args: Punctuated<ConstructorArgTokens, Comma>,
 }
 
 impl Parse for TargetConstructorTokens {
-    #[allow(clippy::eval_order_dependence)]
+    #[allow(clippy::mixed_read_write_in_expression)]
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let content;
         Ok(TargetConstructorTokens {
             method: input.parse()?,
             paren_token: parenthesized!(content in input),
-            args: content.parse_terminated(Ident::parse)?,
+            args: content.parse_terminated(ConstructorArgTokens::parse)?,
         })
     }
 }
+
+// name, name = literal or name: Type = annotation.annotation(...)
+#[derive(Debug)]
+pub struct ConstructorArgTokens {
+    pub(crate) ident: Ident,
+    pub(crate) ty: Option<Type>,
+    pub(crate) value: Option<ConstructorArgValue>,
+}
+
+#[derive(Debug)]
+pub enum ConstructorArgValue {
+    // retries = 3
+    Literal(Lit),
+    // name("main").scope("singleton"), the same keys of `#[inject(...)]` on a field
+    Annotations(Vec<NestedMeta>),
+}
+
+impl Parse for ConstructorArgTokens {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let ident = input.parse::<Ident>()?;
+
+        let ty = if input.peek(Token![:]) {
+            input.parse::<Token![:]>()?;
+            Some(input.parse::<Type>()?)
+        } else {
+            None
+        };
+
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse::<ConstructorArgValue>()?)
+        } else {
+            None
+        };
+
+        Ok(ConstructorArgTokens { ident, ty, value })
+    }
+}
+
+impl Parse for ConstructorArgValue {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(Lit) {
+            return Ok(ConstructorArgValue::Literal(input.parse()?));
+        }
+
+        let mut annotations = Vec::new();
+        loop {
+            // name("main") is parsed as `name="main"` and `optional` as is
+            let key = input.parse::<Ident>()?;
+            let meta = if input.peek(Paren) {
+                let content;
+                parenthesized!(content in input);
+                Meta::NameValue(MetaNameValue {
+                    eq_token: Token![=](key.span()),
+                    path: key.into(),
+                    lit: content.parse()?,
+                })
+            } else {
+                Meta::Path(key.into())
+            };
+
+            annotations.push(NestedMeta::Meta(meta));
+
+            if !input.peek(Token![.]) {
+                break;
+            }
+
+            input.parse::<Token![.]>()?;
+        }
+
+        Ok(ConstructorArgValue::Annotations(annotations))
+    }
+}
@@ -1,7 +1,7 @@
 use proc_macro2::{Span, TokenStream};
 use quote::*;
 use syn::Lit;
-use syn::{Ident, Type};
+use syn::{ExprPath, Ident, Type};
 
 #[derive(Debug)]
 pub struct Dependency {
@@ -11,6 +11,13 @@ pub struct Dependency {
     container: Ident,
     name: Option<String>,
     default_value: Option<DefaultValue>,
+    fallible: bool,
+    asynchronous: bool,
+    handle: Option<Handle>,
+    optional: bool,
+    all: bool,
+    with: Option<ExprPath>,
+    map: Option<ExprPath>,
 }
 
 impl Dependency {
@@ -22,9 +29,20 @@ impl Dependency {
             container,
             name: None,
             default_value: None,
+            fallible: false,
+            asynchronous: false,
+            handle: None,
+            optional: false,
+            all: false,
+            with: None,
+            map: None,
         }
     }
 
+    pub fn set_container(&mut self, container: Ident) {
+        self.container = container;
+    }
+
     pub fn set_name(&mut self, name: String) {
         self.name = Some(name);
     }
@@ -37,6 +55,38 @@ impl Dependency {
         self.default_value = Some(default_value);
     }
 
+    pub fn set_fallible(&mut self, fallible: bool) {
+        self.fallible = fallible;
+    }
+
+    pub fn set_async(&mut self, asynchronous: bool) {
+        self.asynchronous = asynchronous;
+    }
+
+    pub fn set_handle(&mut self, handle: Handle) {
+        self.handle = Some(handle);
+    }
+
+    pub fn set_optional(&mut self, optional: bool) {
+        self.optional = optional;
+    }
+
+    pub fn set_all(&mut self, all: bool) {
+        self.all = all;
+    }
+
+    pub fn set_with(&mut self, with: ExprPath) {
+        self.with = Some(with);
+    }
+
+    pub fn set_map(&mut self, map: ExprPath) {
+        self.map = Some(map);
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     pub fn var_name(&self) -> Ident {
         match self.field.clone() {
             TargetField::Named(s) => s,
@@ -51,6 +101,9 @@ impl Dependency {
         let msg = self.get_error_message();
 
         let get_value = match &self.default_value {
+            // let name : type = with_fn(container) or map_fn(container.try_get::<T>().expect("..."))
+            _ if self.with.is_some() || self.map.is_some() => quote! {},
+
             // let name : type = "string literal".into()
             Some(DefaultValue::Literal(Lit::Str(_))) => quote! { .into() },
 
@@ -60,49 +113,171 @@ impl Dependency {
             // let name : type = Default::default()
             Some(DefaultValue::Infer) => quote! {},
 
-            // let name : type = container.get::<type>().expect("...")
+            // let name : type = container.try_get::<type>()?
+            _ if self.fallible => quote! { ? },
+
+            // let name : type = container.try_get::<type>().expect("...")
             _ => quote! { .expect(#msg) },
         };
 
-        match self.scope {
-            Scope::Scoped => {
-                quote! { let #local_var : #var_type = #expr #get_value ; }
-            }
-            Scope::Singleton => {
-                quote! { let #local_var : dilib::Singleton< #var_type > = #expr #get_value ; }
+        let var_type = match (&self.handle, &self.scope) {
+            (Some(Handle::Lazy), _) => quote! { dilib::Lazy< #var_type > },
+            (Some(Handle::Factory), _) => quote! { dilib::Factory< #var_type > },
+            (None, Scope::Scoped) => quote! { #var_type },
+            (None, Scope::ScopedSingleton | Scope::Singleton) => {
+                quote! { dilib::Singleton< #var_type > }
             }
+        };
+
+        // let name : Option<type> = dilib::ResolveResultExt::optional(container.try_get::<type>()).expect("...")
+        if self.with.is_some() || self.map.is_some() {
+            // The field type is not resolved from the container
+            let field_type = &self.field_type;
+            quote! { let #local_var : #field_type = #expr ; }
+        } else if self.all {
+            quote! { let #local_var : std::vec::Vec< #var_type > = #expr #get_value ; }
+        } else if self.optional {
+            quote! { let #local_var : std::option::Option< #var_type > = #expr #get_value ; }
+        } else {
+            quote! { let #local_var : #var_type = #expr #get_value ; }
         }
     }
 
-    fn get_error_message(&self) -> String {
-        match (&self.scope, &self.name) {
-            (Scope::Scoped, Some(name)) => {
-                format!("cannot get scoped value of name \"{}\"", name)
+    // Emits the `DependencyInfo` of this field, if is resolved from the container
+    pub fn emit_info(&self) -> Option<proc_macro2::TokenStream> {
+        let field_type = &self.field_type;
+
+        if self.with.is_some() {
+            return None;
+        }
+
+        let info = match self.scope {
+            // The type is the argument of the `map` function
+            _ if self.map.is_some() => self.emit_map_info(),
+            // Factories always create scoped values
+            _ if matches!(self.handle, Some(Handle::Factory)) => {
+                quote! { dilib::DependencyInfo::scoped::<#field_type>() }
             }
-            (Scope::Singleton, Some(name)) => {
-                format!("cannot get singleton value of name \"{}\"", name)
+            // dilib::DependencyInfo::scoped::<type>()
+            Scope::Scoped => quote! { dilib::DependencyInfo::scoped::<#field_type>() },
+            // dilib::DependencyInfo::scoped_singleton::<type>()
+            Scope::ScopedSingleton => {
+                quote! { dilib::DependencyInfo::scoped_singleton::<#field_type>() }
             }
-            (Scope::Scoped, None) => {
-                let ty = crate::helpers::token_stream_to_string_non_whitespace(
-                    &self.field_type.to_token_stream(),
+            // dilib::DependencyInfo::singleton::<type>()
+            Scope::Singleton => quote! { dilib::DependencyInfo::singleton::<#field_type>() },
+        };
+
+        let info = match self.name.as_deref() {
+            Some(name) => quote! { #info.with_name(#name) },
+            None => info,
+        };
+
+        // dilib::DependencyInfo::scoped::<type>().with_deferred()
+        // a `Lazy` resolves a provider of any kind
+        let info = match self.handle {
+            Some(Handle::Lazy) => quote! { #info.with_deferred().with_any_kind() },
+            Some(Handle::Factory) => quote! { #info.with_deferred() },
+            None => info,
+        };
+
+        // Optional dependencies are not required to be registered
+        if self.default_value.is_some() || self.optional || self.all {
+            Some(quote! { #info.with_default() })
+        } else {
+            Some(info)
+        }
+    }
+
+    // Infers the type resolved for `map` from the argument of the function
+    fn emit_map_info(&self) -> proc_macro2::TokenStream {
+        let map = &self.map;
+
+        match self.scope {
+            // dilib::DependencyInfo::scoped::<T>() where map: fn(T) -> R
+            Scope::Scoped => quote! {
+                {
+                    fn info<T: 'static, R>(_: fn(T) -> R) -> dilib::DependencyInfo {
+                        dilib::DependencyInfo::scoped::<T>()
+                    }
+                    info(#map)
+                }
+            },
+            // dilib::DependencyInfo::scoped_singleton::<T>() where map: fn(Singleton<T>) -> R
+            Scope::ScopedSingleton => quote! {
+                {
+                    fn info<T: 'static, R>(_: fn(dilib::Singleton<T>) -> R) -> dilib::DependencyInfo {
+                        dilib::DependencyInfo::scoped_singleton::<T>()
+                    }
+                    info(#map)
+                }
+            },
+            // dilib::DependencyInfo::singleton::<T>() where map: fn(Singleton<T>) -> R
+            Scope::Singleton => quote! {
+                {
+                    fn info<T: 'static, R>(_: fn(dilib::Singleton<T>) -> R) -> dilib::DependencyInfo {
+                        dilib::DependencyInfo::singleton::<T>()
+                    }
+                    info(#map)
+                }
+            },
+        }
+    }
+
+    fn get_error_message(&self) -> String {
+        let scope = match (&self.handle, &self.scope) {
+            (Some(Handle::Lazy), _) => "lazy",
+            (Some(Handle::Factory), _) => "factory",
+            (None, Scope::Scoped) => "scoped",
+            (None, Scope::ScopedSingleton) => "scoped singleton",
+            (None, Scope::Singleton) => "singleton",
+        };
+
+        // `all` cannot be used with a name
+        if self.all {
+            let ty = crate::helpers::token_stream_to_string_non_whitespace(
+                &self.field_type.to_token_stream(),
+            );
+            return format!("cannot get all the {} values of type `{}`", scope, ty);
+        }
+
+        match &self.name {
+            Some(name) => format!("cannot get {} value of name \"{}\"", scope, name),
+            None if self.map.is_some() => {
+                let map = crate::helpers::token_stream_to_string_non_whitespace(
+                    &self.map.to_token_stream(),
                 );
-                format!("cannot get scoped value of type `{}`", ty)
+                format!("cannot get {} value for `{}`", scope, map)
             }
-            (Scope::Singleton, None) => {
+            None => {
                 let ty = crate::helpers::token_stream_to_string_non_whitespace(
                     &self.field_type.to_token_stream(),
                 );
-                format!("cannot get singleton value of type `{}`", ty)
+                format!("cannot get {} value of type `{}`", scope, ty)
             }
         }
     }
 
     fn emit_assign_expr(&self) -> proc_macro2::TokenStream {
-        if let Some(default_value) = &self.default_value {
-            if self.name.is_some() {
-                panic!("#[inject(default)] cannot contain a `name` field");
-            }
+        // let var : type = with_fn(container)
+        if let Some(with) = &self.with {
+            let container = &self.container;
+            return quote! { #with ( #container ) };
+        }
 
+        // let var : type = map_fn(container.try_get::<T>()?)
+        if let Some(map) = &self.map {
+            let expr = self.emit_resolve_expr();
+            let msg = self.get_error_message();
+            return if self.fallible {
+                quote! { #map ( #expr ? ) }
+            } else {
+                quote! { #map ( #expr .expect(#msg) ) }
+            };
+        }
+
+        // `all` and `default` cannot be used together, nor with a `name`
+        if let Some(default_value) = &self.default_value {
             return match default_value {
                 // let var : type = default_value
                 DefaultValue::Literal(literal) => quote! { #literal },
@@ -112,28 +287,100 @@ impl Dependency {
             };
         }
 
+        if self.all {
+            let container = &self.container;
+            return match self.scope {
+                // let var : Vec<type> = container.try_get_all_scoped();
+                Scope::Scoped => quote! { #container.try_get_all_scoped() },
+                // let var : Vec<Singleton<type>> = container.try_get_all_singletons();
+                Scope::ScopedSingleton | Scope::Singleton => {
+                    quote! { #container.try_get_all_singletons() }
+                }
+            };
+        }
+
+        // A missing provider is resolved as `None`
+        let expr = self.emit_resolve_expr();
+        if self.optional {
+            quote! { dilib::ResolveResultExt::optional(#expr) }
+        } else {
+            expr
+        }
+    }
+
+    fn emit_resolve_expr(&self) -> proc_macro2::TokenStream {
         let container = &self.container;
+
+        // Handles are created without resolving the value, even in async injection
+        match (&self.handle, self.name.as_deref()) {
+            // let var : Lazy<type> = container.try_get_lazy_with_name(name);
+            (Some(Handle::Lazy), Some(name)) => {
+                return quote! { #container.try_get_lazy_with_name(#name) }
+            }
+            // let var : Lazy<type> = container.try_get_lazy();
+            (Some(Handle::Lazy), None) => return quote! { #container.try_get_lazy() },
+            // let var : Factory<type> = container.try_get_factory_with_name(name);
+            (Some(Handle::Factory), Some(name)) => {
+                return quote! { #container.try_get_factory_with_name(#name) }
+            }
+            // let var : Factory<type> = container.try_get_factory();
+            (Some(Handle::Factory), None) => return quote! { #container.try_get_factory() },
+            (None, _) => {}
+        }
+
+        if self.asynchronous {
+            return self.emit_async_assign_expr();
+        }
+
         // let var_type = &self.field_type;
 
-        match self.scope {
-            Scope::Scoped => {
-                if let Some(name) = self.name.as_deref() {
-                    // let var : type = container.get_scoped_with_name(name);
-                    quote! { #container.get_scoped_with_name(#name) }
-                } else {
-                    // let var : type = container.get_scoped();
-                    quote! { #container.get_scoped() }
-                }
+        // The errors are propagated with `?` if fallible, otherwise `expect` will panic
+        // with the cause, for example a missing provider or a circular dependency
+        match (&self.scope, self.name.as_deref()) {
+            // let var : type = container.try_get_scoped_with_name(name);
+            (Scope::Scoped, Some(name)) => {
+                quote! { #container.try_get_scoped_with_name(#name) }
             }
-            Scope::Singleton => {
-                if let Some(name) = self.name.as_deref() {
-                    // let var : type = container.get_singleton_with_name(name);
-                    quote! { #container.get_singleton_with_name(#name) }
-                } else {
-                    // let var : type = container.get_singleton();
-                    quote! { #container.get_singleton() }
-                }
+            // let var : type = container.try_get_scoped();
+            (Scope::Scoped, None) => quote! { #container.try_get_scoped() },
+            // let var : type = container.try_get_scoped_singleton_with_name(name);
+            (Scope::ScopedSingleton, Some(name)) => {
+                quote! { #container.try_get_scoped_singleton_with_name(#name) }
             }
+            // let var : type = container.try_get_scoped_singleton();
+            (Scope::ScopedSingleton, None) => quote! { #container.try_get_scoped_singleton() },
+            // let var : type = container.try_get_singleton_with_name(name);
+            (Scope::Singleton, Some(name)) => {
+                quote! { #container.try_get_singleton_with_name(#name) }
+            }
+            // let var : type = container.try_get_singleton();
+            (Scope::Singleton, None) => quote! { #container.try_get_singleton() },
+        }
+    }
+
+    // Async providers can be scoped or singletons, scoped singletons are resolved synchronously
+    fn emit_async_assign_expr(&self) -> proc_macro2::TokenStream {
+        let container = &self.container;
+
+        match (&self.scope, self.name.as_deref()) {
+            // let var : type = container.try_get_scoped_async_with_name(name).await;
+            (Scope::Scoped, Some(name)) => {
+                quote! { #container.try_get_scoped_async_with_name(#name).await }
+            }
+            // let var : type = container.try_get_scoped_async().await;
+            (Scope::Scoped, None) => quote! { #container.try_get_scoped_async().await },
+            // let var : type = container.try_get_scoped_singleton_with_name(name);
+            (Scope::ScopedSingleton, Some(name)) => {
+                quote! { #container.try_get_scoped_singleton_with_name(#name) }
+            }
+            // let var : type = container.try_get_scoped_singleton();
+            (Scope::ScopedSingleton, None) => quote! { #container.try_get_scoped_singleton() },
+            // let var : type = container.try_get_singleton_async_with_name(name).await;
+            (Scope::Singleton, Some(name)) => {
+                quote! { #container.try_get_singleton_async_with_name(#name).await }
+            }
+            // let var : type = container.try_get_singleton_async().await;
+            (Scope::Singleton, None) => quote! { #container.try_get_singleton_async().await },
         }
     }
 }
@@ -148,9 +395,19 @@ impl ToTokens for Dependency {
 #[derive(Debug, Clone)]
 pub enum Scope {
     Scoped,
+    ScopedSingleton,
     Singleton,
 }
 
+/// A handle that resolves the dependency after the value is constructed.
+#[derive(Debug, Clone, Copy)]
+pub enum Handle {
+    /// A `dilib::Lazy<T>` resolved the first time is dereferenced.
+    Lazy,
+    /// A `dilib::Factory<T>` that creates a new value each time is called.
+    Factory,
+}
+
 /// Default value of the dependency value.
 #[derive(Debug, Clone)]
 pub enum DefaultValue {
@@ -0,0 +1,123 @@
+use proc_macro2::Span;
+use quote::ToTokens;
+use std::fmt::Display;
+
+/// Collects the errors found while parsing the input, so all of them are reported at once.
+#[derive(Default)]
+pub struct Errors {
+    error: Option<syn::Error>,
+}
+
+impl Errors {
+    pub fn push(&mut self, error: syn::Error) {
+        match &mut self.error {
+            Some(errors) => errors.combine(error),
+            None => self.error = Some(error),
+        }
+    }
+
+    // Adds an error pointing to the given span
+    pub fn push_at<M: Display>(&mut self, span: Span, message: M) {
+        self.push(syn::Error::new(span, message));
+    }
+
+    // Adds an error pointing to the given tokens
+    pub fn push_spanned<T: ToTokens, M: Display>(&mut self, tokens: T, message: M) {
+        self.push(syn::Error::new_spanned(tokens, message));
+    }
+
+    pub fn finish(self) -> syn::Result<()> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+// Formats the message for an unknown value, suggesting the closest expected value if any
+pub fn unknown_value_message(message: &str, value: &str, expected: &[&str]) -> String {
+    match did_you_mean(value, expected) {
+        Some(suggestion) => format!("{} `{}`, did you mean `{}`?", message, value, suggestion),
+        None => format!("{} `{}`, expected {}", message, value, one_of(expected)),
+    }
+}
+
+// Formats the values as: `a`, `b` or `c`
+pub fn one_of(values: &[&str]) -> String {
+    let values = values
+        .iter()
+        .map(|s| format!("`{}`", s))
+        .collect::<Vec<_>>();
+
+    match values.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        Some((last, _)) => last.clone(),
+        None => String::new(),
+    }
+}
+
+// Returns the candidate closest to the value, if is similar enough
+fn did_you_mean<'a>(value: &str, candidates: &[&'a str]) -> Option<&'a str> {
+    let value = value.to_lowercase();
+    let max_distance = std::cmp::max(1, value.len() / 3);
+
+    candidates
+        .iter()
+        .map(|candidate| (*candidate, edit_distance(&value, candidate)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+// Number of insertions, deletions, substitutions and transpositions to turn `a` into `b`
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a = a.chars().collect::<Vec<_>>();
+    let b = b.chars().collect::<Vec<_>>();
+    let mut distances = vec![vec![0; b.len() + 1]; a.len() + 1];
+
+    for (i, row) in distances.iter_mut().enumerate() {
+        row[0] = i;
+    }
+
+    for (j, distance) in distances[0].iter_mut().enumerate() {
+        *distance = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            let mut distance = (distances[i - 1][j] + 1)
+                .min(distances[i][j - 1] + 1)
+                .min(distances[i - 1][j - 1] + cost);
+
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                distance = distance.min(distances[i - 2][j - 2] + 1);
+            }
+
+            distances[i][j] = distance;
+        }
+    }
+
+    distances[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn did_you_mean_test() {
+        let keys = ["name", "scope", "default"];
+
+        assert_eq!(did_you_mean("scpoe", &keys), Some("scope"));
+        assert_eq!(did_you_mean("nmae", &keys), Some("name"));
+        assert_eq!(did_you_mean("Default", &keys), Some("default"));
+        assert_eq!(did_you_mean("data", &keys), None);
+    }
+
+    #[test]
+    fn one_of_test() {
+        assert_eq!(one_of(&["a"]), "`a`");
+        assert_eq!(one_of(&["a", "b", "c"]), "`a`, `b` or `c`");
+    }
+}
@@ -4,6 +4,9 @@ pub const INJECT: &str = "inject";
 // #[inject(constructor="name(param1, param2, ...)")]
 pub const CONSTRUCTOR: &str = "constructor";
 
+// #[inject(post_construct="init")]
+pub const POST_CONSTRUCT: &str = "post_construct";
+
 // #[inject(scope="singleton")]
 pub const SCOPE: &str = "scope";
 
@@ -12,3 +15,21 @@ pub const NAME: &str = "name";
 
 // #[inject(default=1)]
 pub const DEFAULT: &str = "default";
+
+// #[inject(optional)]
+pub const OPTIONAL: &str = "optional";
+
+// #[inject(all)]
+pub const ALL: &str = "all";
+
+// #[inject(with="path::to::fn")]
+pub const WITH: &str = "with";
+
+// #[inject(map="path::to::fn")]
+pub const MAP: &str = "map";
+
+// #[inject(variant="Memory")]
+pub const VARIANT: &str = "variant";
+
+// #[inject(selector="cache")]
+pub const SELECTOR: &str = "selector";
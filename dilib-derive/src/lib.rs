@@ -1,5 +1,6 @@
 mod constructor;
 mod dependency;
+mod error;
 mod helpers;
 mod keys;
 mod target;
@@ -16,9 +17,33 @@ use proc_macro::TokenStream;
 /// - `#[inject(name="name")]`: To get a named dependency.
 /// - `#[inject(default)]`: To set `Default::default()` for the field.
 /// - `#[inject(default=literal)]`: To set the literal value for the field.
-/// - `#[inject(constructor=name(arg1, arg2, ...))]`: To set the constructor to use.
+/// - `#[inject(constructor=name(arg1, arg2, ...))]`: To set the constructor to use, only the fields passed to it are injected.
+///   An argument can also be a literal `arg = 3` or a value that is not a field `arg: Type`,
+///   which is configured with the keys of `#[inject(...)]` as `arg: Arc<Repo> = name("main").scope("singleton")`.
+///   To mark the constructor on the function use `#[inject(constructor)]` with `#[dilib::injectable]` on the `impl`.
+/// - `#[inject(post_construct="init")]`: To call `fn init(&mut self, &Container)` after the value is constructed,
+///   with `TryInject` the function returns `Result<(), ResolveError>`. A path is called as a function instead of a method.
 /// - `#[inject(scope="scoped")]`: To get a scoped dependency. This is the default.
 /// - `#[inject(scope="singleton")]`: To get a singleton dependency. If the field type is `Arc<T>` or `Singleton<T>` this is the default.
+/// - `#[inject(scope="scoped_singleton")]`: To get the singleton of the current scope, the field type must be `Arc<T>` or `Singleton<T>`.
+/// - `#[inject(all)]`: To get all the values of `T` in a field `Vec<T>` or `Vec<Singleton<T>>`, including the multi-bindings,
+///   the injection fails if one of the values cannot be resolved.
+/// - `#[inject(optional=false)]`: To resolve an `Option<T>` field as a value instead of an optional dependency.
+/// - `#[inject(with="path::to::fn")]`: To set the value returned by `fn(&Container) -> T` for the field.
+/// - `#[inject(map="path::to::fn")]`: To resolve the argument of `fn(A) -> T` and set the returned value for the field,
+///   the `name` and `scope` apply to the argument.
+///
+/// Fields of type `Option<T>` are injected as `None` if there is no provider for `T`, the `name` and `scope` of the field apply to `T`.
+///
+/// Fields of type `Lazy<T>` and `Factory<T>` are injected as handles of `T`, which is resolved when the handle is used,
+/// the `name` and `scope` of the field apply to `T`.
+///
+/// Enums require `#[inject(variant="Name")]` to construct the given variant,
+/// or `#[inject(selector="name")]` to construct the variant which name is the value of the named `String`
+/// in the container, use `#[inject(selector="name", scope="singleton")]` if the `String` is a singleton.
+/// The fields of the variants are configured as the fields of a struct.
+///
+/// Invalid or conflicting keys are reported as compile errors pointing to the key.
 ///
 /// # Example
 /// ```rust,no_run
@@ -48,5 +73,66 @@ use proc_macro::TokenStream;
 #[proc_macro_derive(Inject, attributes(inject))]
 pub fn derive_injectable_attribute(item: TokenStream) -> TokenStream {
     let input = syn::parse_macro_input!(item);
-    parse_derive_inject(input).expand().into()
+    match parse_derive_inject(input) {
+        Ok(derive) => derive.expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Provides an implementation of the `TryInject` trait for the given type.
+///
+/// Accepts the same `#[inject]` configuration than `#[derive(Inject)]`, but instead
+/// of panicking when a dependency is missing the error is returned from `try_inject`.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::{Arc, Mutex};
+/// use dilib_derive::TryInject;
+///
+/// #[derive(TryInject)]
+/// struct MetricsService {
+///     #[inject(name="METRICS_URL")]
+///     url: String,
+///
+///     counter: Arc<Mutex<usize>>
+/// }
+/// ```
+///
+#[proc_macro_derive(TryInject, attributes(inject))]
+pub fn derive_try_injectable_attribute(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item);
+    match parse_derive_inject(input) {
+        Ok(derive) => derive.fallible().expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+/// Provides an implementation of the `AsyncInject` trait for the given type.
+///
+/// Accepts the same `#[inject]` configuration than `#[derive(Inject)]`,
+/// the scoped and singleton dependencies are resolved awaiting the async providers.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use dilib_derive::AsyncInject;
+///
+/// struct Pool;
+///
+/// #[derive(AsyncInject)]
+/// struct UserRepository {
+///     pool: Arc<Pool>,
+///
+///     #[inject(name="TABLE")]
+///     table: String,
+/// }
+/// ```
+///
+#[proc_macro_derive(AsyncInject, attributes(inject))]
+pub fn derive_async_injectable_attribute(item: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(item);
+    match parse_derive_inject(input) {
+        Ok(derive) => derive.asynchronous().expand().into(),
+        Err(error) => error.to_compile_error().into(),
+    }
 }
@@ -1,16 +1,22 @@
-use std::str::FromStr;
-
-use mattro::{MacroAttribute, MetaItem};
 use proc_macro2::Span;
 use quote::{quote, ToTokens};
+use syn::spanned::Spanned;
 use syn::{
-    Data, DataStruct, DeriveInput, Field, Fields, GenericArgument, GenericParam, Generics, Ident,
-    PathArguments, Type,
+    Data, DataEnum, DeriveInput, ExprPath, Field, Fields, GenericArgument, GenericParam, Generics,
+    Ident, Lit, LitStr, Meta, NestedMeta, PathArguments, Type, Visibility,
 };
 
-use crate::constructor::{TargetConstructor, TargetConstructorTokens};
-use crate::dependency::{DefaultValue, Dependency, Scope, TargetField};
-use crate::utils::InjectError;
+use crate::constructor::{
+    ConstructorArg, ConstructorArgTokens, ConstructorArgValue, TargetConstructor,
+    TargetConstructorTokens,
+};
+use crate::dependency::{DefaultValue, Dependency, Handle, Scope, TargetField};
+use crate::error::{unknown_value_message, Errors};
+use crate::keys;
+use crate::utils::{
+    parse_inject_attribute, parse_inject_items, path_to_string, InjectAttribute, InjectItem,
+    FIELD_KEYS,
+};
 
 #[derive(Debug, Eq, PartialEq)]
 pub enum StructKind {
@@ -24,14 +30,35 @@ pub enum StructKind {
     Tuple,
 }
 
+// A struct or an enum variant constructed with its fields injected
+#[derive(Debug)]
+pub struct TargetValue {
+    variant: Option<Ident>,
+    deps: Vec<Dependency>,
+    kind: StructKind,
+}
+
+impl TargetValue {
+    pub fn new(variant: Option<Ident>, deps: Vec<Dependency>, kind: StructKind) -> Self {
+        TargetValue {
+            variant,
+            deps,
+            kind,
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct DeriveInject {
     target_type: Ident,
     container: Ident,
     constructor: Option<TargetConstructor>,
-    deps: Vec<Dependency>,
+    values: Vec<TargetValue>,
+    selector: Option<Dependency>,
+    post_construct: Option<ExprPath>,
     generics: Generics,
-    kind: StructKind,
+    fallible: bool,
+    asynchronous: bool,
 }
 
 impl DeriveInject {
@@ -39,63 +66,317 @@ impl DeriveInject {
         target_type: Ident,
         container: Ident,
         constructor: Option<TargetConstructor>,
-        deps: Vec<Dependency>,
+        value: TargetValue,
         generics: Generics,
-        kind: StructKind,
     ) -> Self {
-        DeriveInject {
+        let mut derive = DeriveInject {
             target_type,
             container,
             constructor,
-            deps,
+            values: vec![value],
+            selector: None,
+            post_construct: None,
             generics,
-            kind,
+            fallible: false,
+            asynchronous: false,
+        };
+
+        derive.set_container(derive.container.clone());
+        derive
+    }
+
+    // An enum which variant is selected by the value of a named `String` in the container
+    pub fn with_selector(
+        target_type: Ident,
+        container: Ident,
+        selector: Dependency,
+        values: Vec<TargetValue>,
+        generics: Generics,
+    ) -> Self {
+        let mut derive = DeriveInject {
+            target_type,
+            container,
+            constructor: None,
+            values,
+            selector: Some(selector),
+            post_construct: None,
+            generics,
+            fallible: false,
+            asynchronous: false,
+        };
+
+        derive.set_container(derive.container.clone());
+        derive
+    }
+
+    // The dependencies are resolved from the same container passed to the function
+    fn set_container(&mut self, container: Ident) {
+        for dep in self.deps_mut() {
+            dep.set_container(container.clone());
         }
+        self.container = container;
+    }
+
+    // Calls `fn(&mut Self, &Container)` after the value is constructed
+    pub fn set_post_construct(&mut self, post_construct: ExprPath) {
+        // The container is required to call the function
+        if self.container == "_" {
+            self.set_container(Ident::new("container", Span::call_site()));
+        }
+
+        self.post_construct = Some(post_construct);
+    }
+
+    // Emits `TryInject` instead of `Inject`
+    pub fn fallible(mut self) -> Self {
+        self.fallible = true;
+        for dep in self.deps_mut() {
+            dep.set_fallible(true);
+        }
+        self
+    }
+
+    // Emits `AsyncInject` instead of `Inject`
+    pub fn asynchronous(mut self) -> Self {
+        self.asynchronous = true;
+        for dep in self.deps_mut() {
+            dep.set_async(true);
+        }
+        self
+    }
+
+    fn deps_mut(&mut self) -> impl Iterator<Item = &mut Dependency> {
+        self.values
+            .iter_mut()
+            .flat_map(|value| value.deps.iter_mut())
+            .chain(self.selector.iter_mut())
+            .chain(self.constructor.iter_mut().flat_map(|c| c.deps_mut()))
+    }
+
+    // The arguments of the constructor resolved from the container
+    fn constructor_deps(&self) -> impl Iterator<Item = &Dependency> {
+        self.constructor.iter().flat_map(|c| c.deps())
     }
 
     pub fn expand(&self) -> proc_macro2::TokenStream {
         let target_type = &self.target_type;
         let container = &self.container;
-        let deps = self.deps.as_slice();
         let generic_params = self.generics_params();
         let generic_types = self.generics_types();
         let where_clause = self.where_clause();
 
-        let body = if self.kind == StructKind::Unit {
-            quote! { #target_type }
-        } else if let Some(constructor) = &self.constructor {
-            let params = constructor
-                .args
-                .iter()
-                .map(|s| Ident::new(s, Span::call_site()));
+        // The dependencies are resolved before constructing the value,
+        // or in each arm of the `match` on the selector
+        let (deps, body) = match &self.selector {
+            Some(selector) => (quote! { #selector }, self.emit_select(selector)),
+            None => {
+                let value = &self.values[0];
+                let deps = value.deps.iter().chain(self.constructor_deps());
+                (quote! { #(#deps)* }, self.emit_construct(value))
+            }
+        };
 
-            // Type :: constructor ( params )
-            let constructor_name = Ident::new(&constructor.name, Span::call_site());
-            quote! { #target_type :: #constructor_name ( #(#params),* )}
-        } else {
-            let params = deps.iter().map(|s| s.var_name());
+        let body = match &self.post_construct {
+            Some(post_construct) => self.emit_post_construct(body, post_construct),
+            None => body,
+        };
+
+        let metadata = self.emit_metadata();
 
-            match self.kind {
-                StructKind::Named => {
-                    // Type { params }
-                    quote! { #target_type { #(#params),* } }
+        if self.fallible {
+            return quote! {
+                impl #generic_params dilib::TryInject for #target_type #generic_types #where_clause {
+                    #[allow(unused)]
+                    #[allow(dead_code)]
+                    fn try_inject(#container : &dilib::Container) -> std::result::Result<Self, dilib::ResolveError> {
+                        #deps
+                        std::result::Result::Ok(#body)
+                    }
+
+                    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                        <Self as dilib::InjectMetadata>::dependencies()
+                    }
                 }
-                StructKind::Tuple => {
-                    // Type ( params )
-                    quote! { #target_type ( #(#params),* ) }
+
+                #metadata
+            };
+        }
+
+        if self.asynchronous {
+            return quote! {
+                impl #generic_params dilib::AsyncInject for #target_type #generic_types #where_clause {
+                    #[allow(unused)]
+                    #[allow(dead_code)]
+                    fn inject_async<'c>(#container : &'c dilib::Container<'c>) -> dilib::InjectFuture<'c, Self> {
+                        std::boxed::Box::pin(async move {
+                            #deps
+                            #body
+                        })
+                    }
+
+                    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                        <Self as dilib::InjectMetadata>::dependencies()
+                    }
                 }
-                StructKind::Unit => unreachable!(),
-            }
-        };
+
+                #metadata
+            };
+        }
 
         quote! {
             impl #generic_params dilib::Inject for #target_type #generic_types #where_clause {
                 #[allow(unused)]
                 #[allow(dead_code)]
                 fn inject(#container : &dilib::Container) -> Self {
-                    #(#deps)*
+                    #deps
                     #body
                 }
+
+                fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                    <Self as dilib::InjectMetadata>::dependencies()
+                }
+            }
+
+            #metadata
+        }
+    }
+
+    // { let mut value = body; Self::init(&mut value, container); value }
+    fn emit_post_construct(
+        &self,
+        body: proc_macro2::TokenStream,
+        post_construct: &ExprPath,
+    ) -> proc_macro2::TokenStream {
+        let container = &self.container;
+
+        // A method of the type or a path to a function
+        let post_construct = match post_construct.path.get_ident() {
+            Some(method) if post_construct.qself.is_none() => quote! { Self :: #method },
+            _ => quote! { #post_construct },
+        };
+
+        let result = if self.fallible {
+            quote! { ? }
+        } else {
+            quote! {}
+        };
+
+        quote! {
+            {
+                let mut value = #body;
+                #post_construct (&mut value, #container) #result;
+                value
+            }
+        }
+    }
+
+    // Type { params }, Type::Variant ( params ) or Type::constructor ( params )
+    fn emit_construct(&self, value: &TargetValue) -> proc_macro2::TokenStream {
+        let target_type = &self.target_type;
+        let path = match &value.variant {
+            Some(variant) => quote! { #target_type :: #variant },
+            None => quote! { #target_type },
+        };
+
+        if value.kind == StructKind::Unit {
+            return path;
+        }
+
+        if let Some(constructor) = &self.constructor {
+            let params = constructor.args.iter().map(|arg| match arg {
+                ConstructorArg::Field(ident) => ident.to_token_stream(),
+                ConstructorArg::Literal(lit) => lit.to_token_stream(),
+                ConstructorArg::Dependency(dep) => dep.var_name().to_token_stream(),
+            });
+
+            // Type :: constructor ( params )
+            let constructor_name = Ident::new(&constructor.name, Span::call_site());
+            return quote! { #target_type :: #constructor_name ( #(#params),* )};
+        }
+
+        let params = value.deps.iter().map(|s| s.var_name());
+
+        match value.kind {
+            StructKind::Named => {
+                // Type { params }
+                quote! { #path { #(#params),* } }
+            }
+            StructKind::Tuple => {
+                // Type ( params )
+                quote! { #path ( #(#params),* ) }
+            }
+            StructKind::Unit => unreachable!(),
+        }
+    }
+
+    // match selector.as_str() { "Variant" => { deps; Type::Variant { params } }, ... }
+    fn emit_select(&self, selector: &Dependency) -> proc_macro2::TokenStream {
+        let selector_var = selector.var_name();
+        let arms = self.values.iter().map(|value| {
+            // SAFETY: all the values of an enum are variants
+            let variant = value.variant.as_ref().unwrap().to_string();
+            let deps = value.deps.as_slice();
+            let construct = self.emit_construct(value);
+            quote! { #variant => { #(#deps)* #construct } }
+        });
+
+        let message = format!(
+            "unknown variant `{{}}` of `{}` selected by \"{}\"",
+            self.target_type,
+            selector.name().unwrap_or_default()
+        );
+
+        let unknown = if self.fallible {
+            quote! { return std::result::Result::Err(dilib::ResolveError::new(std::format!(#message, other))) }
+        } else {
+            quote! { std::panic!(#message, other) }
+        };
+
+        quote! {
+            match #selector_var.as_str() {
+                #(#arms,)*
+                other => #unknown
+            }
+        }
+    }
+
+    // impl InjectMetadata for Type { fn dependencies() -> Vec<DependencyInfo> }
+    fn emit_metadata(&self) -> proc_macro2::TokenStream {
+        let target_type = &self.target_type;
+        let generic_params = self.generics_params();
+        let generic_types = self.generics_types();
+        let where_clause = self.where_clause();
+        let infos = match &self.selector {
+            // Only the dependencies of the selected variant are resolved
+            Some(selector) => selector
+                .emit_info()
+                .into_iter()
+                .chain(self.values.iter().flat_map(|value| {
+                    value.deps.iter().filter_map(|dep| {
+                        let info = dep.emit_info()?;
+                        Some(quote! { #info.with_default() })
+                    })
+                }))
+                .collect::<Vec<_>>(),
+            None => self.values[0]
+                .deps
+                .iter()
+                .chain(self.constructor_deps())
+                .filter_map(|dep| dep.emit_info())
+                .collect::<Vec<_>>(),
+        };
+
+        let dependencies = if infos.is_empty() {
+            quote! { std::vec::Vec::new() }
+        } else {
+            quote! { std::vec::Vec::from([ #(#infos),* ]) }
+        };
+
+        quote! {
+            impl #generic_params dilib::InjectMetadata for #target_type #generic_types #where_clause {
+                fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                    #dependencies
+                }
             }
         }
     }
@@ -154,112 +435,592 @@ impl DeriveInject {
     }
 }
 
-pub fn parse_derive_inject(input: DeriveInput) -> DeriveInject {
+pub fn parse_derive_inject(input: DeriveInput) -> syn::Result<DeriveInject> {
     match &input.data {
-        Data::Enum(_) => panic!("Enum types cannot implement `Inject` with #[derive]"),
-        Data::Union(_) => panic!("Union types cannot implement `Inject` with #[derive]"),
+        Data::Enum(data_enum) => parse_derive_inject_enum(&input, data_enum),
+        Data::Union(data_union) => Err(syn::Error::new_spanned(
+            data_union.union_token,
+            "union types cannot implement `Inject` with #[derive]",
+        )),
         Data::Struct(data_struct) => {
+            // The errors of all the attributes are reported at once
+            let mut errors = Errors::default();
             let target_type = input.ident.clone();
-            let constructor = get_target_constructor(&input);
-            let container = get_container_identifier(data_struct);
-            let deps = get_deps(&data_struct.fields);
+            let attr = get_target_attribute(&input, STRUCT_KEYS, "struct", &mut errors);
+            let constructor = get_target_constructor(&attr, &mut errors);
+            let post_construct = get_post_construct(&attr, &mut errors);
+            let container = get_container_identifier(std::iter::once(&data_struct.fields));
+            let mut deps = get_deps(&data_struct.fields, &mut errors);
             let generics = input.generics.clone();
-            let kind = get_struct_kind(data_struct);
+            let kind = get_struct_kind(&data_struct.fields);
+
+            // Only the fields passed to the constructor are resolved
+            if let Some(constructor) = &constructor {
+                deps.retain(|dep| constructor.has_field(&dep.var_name()));
+            }
 
-            DeriveInject::new(target_type, container, constructor, deps, generics, kind)
+            errors.finish()?;
+            let mut derive = DeriveInject::new(
+                target_type,
+                container,
+                constructor,
+                TargetValue::new(None, deps, kind),
+                generics,
+            );
+
+            if let Some(post_construct) = post_construct {
+                derive.set_post_construct(post_construct);
+            }
+
+            Ok(derive)
         }
     }
 }
 
-// #[inject(constructor="new(param1, param2, ...)")]
-fn get_target_constructor(input: &DeriveInput) -> Option<TargetConstructor> {
-    let attributes = input
-        .attrs
-        .iter()
-        .cloned()
-        .map(|a| MacroAttribute::new(a).unwrap())
-        .filter(|a| a.path() == "inject")
-        .collect::<Vec<_>>();
-
-    // Check if the `#[inject(constructor="")]` are well formed
-    for attr in &attributes {
-        if attr.len() != 1 || attr.path() != crate::keys::INJECT {
-            panic!("invalid inject constructor `{}`", attr);
-        }
-        if let Some(MetaItem::NameValue(s)) = attr.get(0) {
-            if s.name != crate::keys::CONSTRUCTOR {
-                panic!("invalid inject constructor `{}`", attr);
-            }
+// #[inject(variant="Variant")] or #[inject(selector="name", scope="...")]
+fn parse_derive_inject_enum(
+    input: &DeriveInput,
+    data_enum: &DataEnum,
+) -> syn::Result<DeriveInject> {
+    let mut errors = Errors::default();
+    let target_type = input.ident.clone();
+    let generics = input.generics.clone();
+    let attr = get_target_attribute(input, ENUM_KEYS, "enum", &mut errors);
+    let post_construct = get_post_construct(&attr, &mut errors);
+
+    let mut values = Vec::new();
+    for variant in data_enum.variants.iter() {
+        if let Some(attr) = variant.attrs.iter().find(|a| a.path.is_ident(keys::INJECT)) {
+            errors.push_spanned(
+                attr,
+                "`#[inject]` cannot be declared on a variant, use `#[inject(variant=\"...\")]` on the enum",
+            );
         }
+
+        let deps = get_deps(&variant.fields, &mut errors);
+        let kind = get_struct_kind(&variant.fields);
+        values.push(TargetValue::new(Some(variant.ident.clone()), deps, kind));
+    }
+
+    let variant = attr.get(keys::VARIANT).and_then(|item| item.value.as_ref());
+    let selector = attr
+        .get(keys::SELECTOR)
+        .and_then(|item| item.value.as_ref());
+
+    if let (Some(_), Some(item)) = (attr.get(keys::VARIANT), attr.get(keys::SELECTOR)) {
+        errors.push_at(
+            item.span,
+            "`#[inject(variant)]` cannot contain a `selector` field",
+        );
     }
 
-    if let Some(attr) = attributes.last().cloned() {
-        let name_value_attr = attr.into_name_values().unwrap();
-        let value = name_value_attr.get("constructor").unwrap();
-        let token_string = value.to_string_literal().unwrap();
-        let tokens = proc_macro2::TokenStream::from_str(&token_string).unwrap();
-        if let Ok(result) = syn::parse2::<TargetConstructorTokens>(tokens) {
-            return Some(result.into_constructor());
+    if let (None, Some(item)) = (attr.get(keys::SELECTOR), attr.get(keys::SCOPE)) {
+        errors.push_at(
+            item.span,
+            "`scope` can only be declared with a `selector`: `#[inject(selector=\"...\", scope=\"...\")]`",
+        );
+    }
+
+    let derive = match (variant, selector) {
+        (Some(Lit::Str(variant)), None) => {
+            let name = variant.value();
+            let index = values
+                .iter()
+                .position(|value| matches!(&value.variant, Some(ident) if *ident == name));
+
+            match index {
+                Some(index) => {
+                    let value = values.swap_remove(index);
+                    let fields = &data_enum.variants[index].fields;
+                    let container = get_container_identifier(std::iter::once(fields));
+                    Some(DeriveInject::new(
+                        target_type,
+                        container,
+                        None,
+                        value,
+                        generics,
+                    ))
+                }
+                None => {
+                    let names = data_enum
+                        .variants
+                        .iter()
+                        .map(|variant| variant.ident.to_string())
+                        .collect::<Vec<_>>();
+                    let names = names.iter().map(String::as_str).collect::<Vec<_>>();
+                    let message = unknown_value_message("unknown variant", &name, &names);
+                    errors.push_spanned(variant, message);
+                    None
+                }
+            }
+        }
+        (None, Some(Lit::Str(selector))) => {
+            // The name of the container cannot collide with the fields of any variant
+            let mut container =
+                get_container_identifier(data_enum.variants.iter().map(|v| &v.fields));
+
+            // The container is always used to resolve the selector
+            if container == "_" {
+                container = Ident::new("container", Span::call_site());
+            }
+
+            let selector_var = TargetField::Named(Ident::new("selector", Span::call_site()));
+            let ty: Type = syn::parse_quote!(std::string::String);
+            let mut dependency =
+                Dependency::new(selector_var, ty, Scope::Scoped, container.clone());
+            dependency.set_name(selector.value());
+
+            if let Some(Some(Lit::Str(scope))) = attr.get(keys::SCOPE).map(|item| &item.value) {
+                if let Some(scope) = parse_scope(scope, &mut errors) {
+                    dependency.set_scope(scope);
+                }
+            }
+
+            Some(DeriveInject::with_selector(
+                target_type,
+                container,
+                dependency,
+                values,
+                generics,
+            ))
+        }
+        (None, None) => {
+            errors.push_spanned(
+                data_enum.enum_token,
+                "enum types require `#[inject(variant=\"...\")]` or `#[inject(selector=\"...\")]` to implement `Inject` with #[derive]",
+            );
+            None
         }
+        // `variant` and `selector` together, already reported
+        _ => None,
+    };
+
+    errors.finish()?;
+
+    // SAFETY: an error is reported if there is no value to construct
+    let mut derive = derive.unwrap();
+    if let Some(post_construct) = post_construct {
+        derive.set_post_construct(post_construct);
     }
 
-    None
+    Ok(derive)
 }
 
-fn get_container_identifier(struct_data: &DataStruct) -> Ident {
-    const CONTAINER_IDENT: &str = "container";
+// #[inject(constructor="new(param1, param2: Type = name(\"...\"), param3 = literal, ...)")]
+fn get_target_constructor(
+    attr: &InjectAttribute,
+    errors: &mut Errors,
+) -> Option<TargetConstructor> {
+    let item = attr.get(keys::CONSTRUCTOR)?;
 
-    match &struct_data.fields {
-        Fields::Named(fields) => {
-            let mut container_name = CONTAINER_IDENT.to_owned();
-            let mut matches = 1_usize;
+    match &item.value {
+        Some(Lit::Str(lit_str)) => match lit_str.parse::<TargetConstructorTokens>() {
+            Ok(tokens) => {
+                let name = tokens.method.to_string();
+                let mut names = Vec::new();
+                let mut args = Vec::new();
 
-            for f in &fields.named {
-                let field_name = f.ident.as_ref().unwrap().to_string();
+                for arg in tokens.args {
+                    if names.contains(&arg.ident) {
+                        errors.push_spanned(
+                            &arg.ident,
+                            format!("duplicated constructor argument `{}`", arg.ident),
+                        );
+                    }
 
-                // We prevent name collisions
-                // If there is a field named `container` we try other name
-                // following the pattern: `container(N)` where N is the number of collisions
-                if field_name == container_name {
-                    container_name = format!("{}{}", container_name, matches);
-                    matches += 1;
+                    names.push(arg.ident.clone());
+                    args.push(new_constructor_arg(arg, errors));
                 }
+
+                Some(TargetConstructor::new(name, args))
+            }
+            Err(_) => {
+                errors.push_spanned(
+                    lit_str,
+                    format!(
+                        "invalid constructor `{}`, expected `name(arg1, arg2, ...)`",
+                        lit_str.value()
+                    ),
+                );
+                None
             }
+        },
+        _ => None,
+    }
+}
+
+fn new_constructor_arg(arg: ConstructorArgTokens, errors: &mut Errors) -> ConstructorArg {
+    let ConstructorArgTokens { ident, ty, value } = arg;
+
+    let (ty, value) = match (ty, value) {
+        // field
+        (None, None) => return ConstructorArg::Field(ident),
+        // name = literal
+        (None, Some(ConstructorArgValue::Literal(lit))) => return ConstructorArg::Literal(lit),
+        // name = annotations
+        (None, Some(ConstructorArgValue::Annotations(_))) => {
+            errors.push_spanned(
+                &ident,
+                format!(
+                    "constructor argument `{0}` requires a type to be injected: `{0}: Type = ...`",
+                    ident
+                ),
+            );
+            return ConstructorArg::Field(ident);
+        }
+        // name: Type = annotations
+        (Some(ty), value) => (ty, value),
+    };
+
+    // The argument is injected as a field with the annotations as `#[inject(...)]`
+    let attr = match value {
+        Some(ConstructorArgValue::Literal(lit)) => {
+            let mut attr = InjectAttribute::default();
+            let item = InjectItem {
+                span: lit.span(),
+                value: Some(lit),
+            };
 
-            Ident::new(container_name.as_str(), Span::call_site())
+            attr.insert(keys::DEFAULT.to_owned(), item);
+            attr
         }
-        Fields::Unnamed(_) => Ident::new(CONTAINER_IDENT, Span::call_site()),
-        Fields::Unit => Ident::new("_", Span::call_site()),
+        Some(ConstructorArgValue::Annotations(annotations)) => {
+            parse_inject_items(annotations.iter(), errors)
+        }
+        None => InjectAttribute::default(),
+    };
+
+    let field = Field {
+        attrs: Vec::new(),
+        vis: Visibility::Inherited,
+        ident: Some(ident.clone()),
+        colon_token: Some(Default::default()),
+        ty,
+    };
+
+    let container = Ident::new("container", Span::call_site());
+    let target_field = TargetField::Named(ident);
+    let mut dependency = new_dependency(target_field, &field, &attr, &container, errors);
+    set_dependency_attributes(&attr, &mut dependency, errors);
+    ConstructorArg::Dependency(Box::new(dependency))
+}
+
+// #[inject(post_construct="init")]
+fn get_post_construct(attr: &InjectAttribute, errors: &mut Errors) -> Option<ExprPath> {
+    let item = attr.get(keys::POST_CONSTRUCT)?;
+    parse_fn_path(keys::POST_CONSTRUCT, &item.value, errors)
+}
+
+// Keys of `#[inject(...)]` on a struct
+const STRUCT_KEYS: &[&str] = &[keys::CONSTRUCTOR, keys::POST_CONSTRUCT];
+
+// Keys of `#[inject(...)]` on an enum
+const ENUM_KEYS: &[&str] = &[
+    keys::VARIANT,
+    keys::SELECTOR,
+    keys::SCOPE,
+    keys::POST_CONSTRUCT,
+];
+
+// Parses the `#[inject(...)]` of the type, all the keys require a string literal
+fn get_target_attribute(
+    input: &DeriveInput,
+    expected: &[&str],
+    target: &str,
+    errors: &mut Errors,
+) -> InjectAttribute {
+    let mut attr = InjectAttribute::default();
+
+    for attribute in input.attrs.iter().filter(|a| a.path.is_ident(keys::INJECT)) {
+        let nested = match attribute.parse_meta() {
+            Ok(Meta::List(list)) => list.nested,
+            Ok(meta) => {
+                errors.push_spanned(
+                    meta,
+                    format!(
+                        "invalid attribute, expected `#[inject({}=\"...\")]`",
+                        expected[0]
+                    ),
+                );
+                continue;
+            }
+            Err(error) => {
+                errors.push(error);
+                continue;
+            }
+        };
+
+        for meta in nested.iter() {
+            let (path, value) = match meta {
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    (&name_value.path, Some(&name_value.lit))
+                }
+                NestedMeta::Meta(Meta::Path(path)) => (path, None),
+                _ => {
+                    errors.push_spanned(
+                        meta,
+                        format!(
+                            "invalid `#[inject]` argument, expected `{}=\"...\"`",
+                            expected[0]
+                        ),
+                    );
+                    continue;
+                }
+            };
+
+            let name = path_to_string(path);
+
+            if !expected.contains(&name.as_str()) {
+                let message = if FIELD_KEYS.contains(&name.as_str()) {
+                    format!(
+                        "`{}` can only be declared on the fields of the {}",
+                        name, target
+                    )
+                } else if TARGET_KEYS.contains(&name.as_str()) {
+                    format!("`{}` cannot be declared on the {}", name, target)
+                } else {
+                    unknown_value_message("unknown `#[inject]` key", &name, expected)
+                };
+
+                errors.push_spanned(path, message);
+                continue;
+            }
+
+            let value = match value {
+                Some(Lit::Str(lit_str)) => lit_str,
+                Some(lit) => {
+                    errors.push_spanned(
+                        lit,
+                        format!(
+                            "expected string literal for `{0}`: `#[inject({0}=\"...\")]`",
+                            name
+                        ),
+                    );
+                    continue;
+                }
+                None => {
+                    errors.push_spanned(
+                        path,
+                        format!("`{0}` requires a value: `#[inject({0}=\"...\")]`", name),
+                    );
+                    continue;
+                }
+            };
+
+            if attr.contains_key(&name) {
+                errors.push_spanned(path, format!("duplicated `#[inject]` key `{}`", name));
+                continue;
+            }
+
+            let item = InjectItem {
+                span: path.span(),
+                value: Some(Lit::Str(value.clone())),
+            };
+
+            attr.insert(name, item);
+        }
+    }
+
+    attr
+}
+
+// All the keys of `#[inject(...)]` on a type
+const TARGET_KEYS: &[&str] = &[
+    keys::CONSTRUCTOR,
+    keys::VARIANT,
+    keys::SELECTOR,
+    keys::POST_CONSTRUCT,
+];
+
+fn get_container_identifier<'a>(fields: impl IntoIterator<Item = &'a Fields>) -> Ident {
+    const CONTAINER_IDENT: &str = "container";
+
+    let mut used = false;
+    let mut field_names = Vec::new();
+
+    for fields in fields {
+        match fields {
+            Fields::Named(fields) => {
+                used = true;
+                field_names.extend(fields.named.iter().map(|f| f.ident.as_ref().unwrap()));
+            }
+            Fields::Unnamed(_) => used = true,
+            Fields::Unit => {}
+        }
+    }
+
+    // Unit values don't use the container
+    if !used {
+        return Ident::new("_", Span::call_site());
     }
+
+    // We prevent name collisions
+    // If there is a field named `container` we try other name
+    // following the pattern: `container(N)` where N is the number of collisions
+    let mut container_name = CONTAINER_IDENT.to_owned();
+    let mut matches = 1_usize;
+
+    while field_names.iter().any(|name| **name == container_name) {
+        container_name = format!("{}{}", CONTAINER_IDENT, matches);
+        matches += 1;
+    }
+
+    Ident::new(container_name.as_str(), Span::call_site())
 }
 
-fn get_deps(fields: &Fields) -> Vec<Dependency> {
-    let mut deps = Vec::new();
+fn get_deps(fields: &Fields, errors: &mut Errors) -> Vec<Dependency> {
     let container = Ident::new("container", Span::call_site());
 
-    match fields {
-        Fields::Unit => deps,
-        Fields::Named(fields_named) => {
-            for f in &fields_named.named {
-                let field = TargetField::Named(f.ident.clone().unwrap());
-                let (field_type, scope) = get_type_and_scope(&f.ty);
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, f)| {
+            let field = match &f.ident {
+                Some(ident) => TargetField::Named(ident.clone()),
+                None => TargetField::Unnamed(index),
+            };
+
+            let attr = get_inject_attribute(f, errors).unwrap_or_default();
+            let mut dependency = new_dependency(field, f, &attr, &container, errors);
+            set_dependency_attributes(&attr, &mut dependency, errors);
+            dependency
+        })
+        .collect()
+}
+
+fn new_dependency(
+    field: TargetField,
+    f: &Field,
+    attr: &InjectAttribute,
+    container: &Ident,
+    errors: &mut Errors,
+) -> Dependency {
+    // `#[inject(with)]` and `#[inject(map)]` construct the field type from a function
+    if attr.contains_key(keys::WITH) || attr.contains_key(keys::MAP) {
+        return Dependency::new(field, f.ty.clone(), Scope::Scoped, container.clone());
+    }
+
+    // `#[inject(all)]` injects all the values of `T` in a `Vec<T>`
+    if attr.contains_key(keys::ALL) {
+        match get_vec_type(&f.ty) {
+            Some(ty) => {
+                let (field_type, scope) = get_type_and_scope(&ty);
                 let mut dependency = Dependency::new(field, field_type, scope, container.clone());
-                set_dependency_attributes(f, &mut dependency);
-                deps.push(dependency);
+                dependency.set_all(true);
+                return dependency;
             }
+            None => errors.push_spanned(
+                &f.ty,
+                format!(
+                    "`#[inject(all)]` requires a field of type `Vec<T>` but was `{}`",
+                    f.ty.to_token_stream()
+                ),
+            ),
+        }
+    }
 
-            deps
+    // `Option<T>` is injected as `None` if there is no provider, unless `#[inject(optional=false)]`
+    let optional_attr = get_optional_attribute(attr, errors);
+    let (ty, optional) = match (get_option_type(&f.ty), optional_attr) {
+        (Some(inner), None | Some(true)) => (inner, true),
+        (None, Some(true)) => {
+            errors.push_spanned(
+                &f.ty,
+                format!(
+                    "`#[inject(optional)]` requires a field of type `Option<T>` but was `{}`",
+                    f.ty.to_token_stream()
+                ),
+            );
+            (f.ty.clone(), false)
         }
-        Fields::Unnamed(fields_unnamed) => {
-            for (index, f) in fields_unnamed.unnamed.iter().enumerate() {
-                let field = TargetField::Unnamed(index);
-                let (field_type, scope) = get_type_and_scope(&f.ty);
-                let mut dependency = Dependency::new(field, field_type, scope, container.clone());
-                set_dependency_attributes(f, &mut dependency);
-                deps.push(dependency);
+        (_, _) => (f.ty.clone(), false),
+    };
+
+    let mut dependency = match get_handle_type(&ty) {
+        Some((inner, handle)) => {
+            let mut dependency = Dependency::new(field, inner, Scope::Scoped, container.clone());
+            dependency.set_handle(handle);
+            dependency
+        }
+        None => {
+            let (field_type, scope) = get_type_and_scope(&ty);
+            Dependency::new(field, field_type, scope, container.clone())
+        }
+    };
+
+    dependency.set_optional(optional);
+    dependency
+}
+
+// Is `Option<T>`
+fn get_option_type(ty: &Type) -> Option<Type> {
+    get_generic_type(
+        ty,
+        &[
+            "Option",
+            "option::Option",
+            "std::option::Option",
+            "core::option::Option",
+        ],
+    )
+}
+
+// Is `Vec<T>`
+fn get_vec_type(ty: &Type) -> Option<Type> {
+    get_generic_type(ty, &["Vec", "vec::Vec", "std::vec::Vec"])
+}
+
+// Returns `T` if the type is one of the given paths with a generic argument `T`
+fn get_generic_type(ty: &Type, paths: &[&str]) -> Option<Type> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let path = type_path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+
+            if !paths.contains(&path.as_str()) {
+                return None;
             }
 
-            deps
+            // SAFETY: path have at least 1 element
+            let segment = type_path.path.segments.last().unwrap();
+            if let PathArguments::AngleBracketed(bracketed) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner)) = bracketed.args.first() {
+                    return Some(inner.clone());
+                }
+            }
+
+            None
+        }
+        _ => None,
+    }
+}
+
+// Returns the last `#[inject(...)]` of the field, if any
+fn get_inject_attribute(field: &Field, errors: &mut Errors) -> Option<InjectAttribute> {
+    let attr = field
+        .attrs
+        .iter()
+        .rfind(|attr| attr.path.is_ident(keys::INJECT))?;
+
+    Some(parse_inject_attribute(attr, errors))
+}
+
+// Returns the value of `#[inject(optional)]` or `#[inject(optional=bool)]`, if any
+fn get_optional_attribute(attr: &InjectAttribute, errors: &mut Errors) -> Option<bool> {
+    match &attr.get(keys::OPTIONAL)?.value {
+        None => Some(true),
+        Some(Lit::Bool(value)) => Some(value.value),
+        Some(lit) => {
+            errors.push_spanned(
+                lit,
+                "expected boolean literal for `optional`: `#[inject(optional=true)]`",
+            );
+            None
         }
     }
 }
@@ -289,8 +1050,9 @@ fn get_singleton_type(ty: &Type) -> Option<Type> {
             // Is `Singleton<T>` or `Arc<T>`
             if is_singleton(ty) && has_generics {
                 if let PathArguments::AngleBracketed(bracketed) = &segment.arguments {
-                    let generic_arg = bracketed.args.first().unwrap();
-                    if let GenericArgument::Type(Type::Path(generic_type)) = generic_arg {
+                    if let Some(GenericArgument::Type(Type::Path(generic_type))) =
+                        bracketed.args.first()
+                    {
                         return Some(Type::Path(generic_type.clone()));
                     }
                 }
@@ -302,98 +1064,160 @@ fn get_singleton_type(ty: &Type) -> Option<Type> {
     }
 }
 
-fn get_struct_kind(data_struct: &DataStruct) -> StructKind {
-    match data_struct.fields {
+// Is `Lazy<T>` or `Factory<T>`
+fn get_handle_type(ty: &Type) -> Option<(Type, Handle)> {
+    match ty {
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let path = type_path
+                .path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+
+            let handle = match path.as_str() {
+                "Lazy" | "dilib::Lazy" => Handle::Lazy,
+                "Factory" | "dilib::Factory" => Handle::Factory,
+                _ => return None,
+            };
+
+            // SAFETY: path have at least 1 element
+            let segment = type_path.path.segments.last().unwrap();
+            if let PathArguments::AngleBracketed(bracketed) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner)) = bracketed.args.first() {
+                    return Some((inner.clone(), handle));
+                }
+            }
+
+            None
+        }
+        _ => None,
+    }
+}
+
+fn get_struct_kind(fields: &Fields) -> StructKind {
+    match fields {
         Fields::Named(_) => StructKind::Named,
         Fields::Unnamed(_) => StructKind::Tuple,
         Fields::Unit => StructKind::Unit,
     }
 }
 
-fn set_dependency_attributes(field: &Field, dependency: &mut Dependency) {
-    use crate::keys;
-
-    let attributes = field
-        .attrs
-        .iter()
-        .cloned()
-        .map(|attr| MacroAttribute::new(attr).unwrap())
-        .filter(|attr| attr.path() == keys::INJECT)
-        .collect::<Vec<_>>();
-
-    if !attributes.is_empty() {
-        let attr = attributes.last().unwrap();
-
-        match crate::utils::convert_to_inject_attribute_map(attr) {
-            Ok(map) => {
-                for (name, value) in map {
-                    match name.as_str() {
-                        keys::DEFAULT => {
-                            if let Some(default_value) = value {
-                                let lit =
-                                    default_value.as_literal().cloned().unwrap_or_else(|| {
-                                        panic!("expected literal for default value: {}", attr)
-                                    });
-
-                                dependency.set_default_value(DefaultValue::Literal(lit))
-                            } else {
-                                dependency.set_default_value(DefaultValue::Infer);
-                            }
-                        }
-                        keys::NAME => {
-                            let s = value
-                                .as_ref()
-                                .cloned()
-                                .unwrap()
-                                .to_string_literal()
-                                .unwrap_or_else(|| {
-                                    panic!(
-                                "expected string literal for `name` but was: `#[inject(name={})]`",
-                                value.unwrap()
-                            )
-                                });
-
-                            dependency.set_name(s);
-                        }
-                        keys::SCOPE => {
-                            let s = value.unwrap().to_string_literal().expect(
-                                "expected string literal for scope: `#[inject(scope=\"...\")]`",
-                            );
-
-                            let scope = match s.as_str() {
-                                "singleton" => Scope::Singleton,
-                                "scoped" => Scope::Scoped,
-                                _ => panic!(
-                                    "invalid scope value: `{}`, expected \"singleton\" or \"scoped\"", s
-                                )
-                            };
-
-                            dependency.set_scope(scope);
-                        }
-                        _ => unreachable!(),
+fn set_dependency_attributes(
+    attr: &InjectAttribute,
+    dependency: &mut Dependency,
+    errors: &mut Errors,
+) {
+    for (name, item) in attr.iter() {
+        match name {
+            keys::DEFAULT => match &item.value {
+                Some(lit) => dependency.set_default_value(DefaultValue::Literal(lit.clone())),
+                None => dependency.set_default_value(DefaultValue::Infer),
+            },
+            keys::NAME => match &item.value {
+                Some(Lit::Str(lit_str)) => dependency.set_name(lit_str.value()),
+                value => errors.push_spanned(
+                    value,
+                    "expected string literal for `name`: `#[inject(name=\"...\")]`",
+                ),
+            },
+            keys::SCOPE => match &item.value {
+                Some(Lit::Str(lit_str)) => {
+                    if let Some(scope) = parse_scope(lit_str, errors) {
+                        dependency.set_scope(scope);
                     }
                 }
+                value => errors.push_spanned(
+                    value,
+                    "expected string literal for `scope`: `#[inject(scope=\"...\")]`",
+                ),
+            },
+            keys::WITH => {
+                if let Some(path) = parse_fn_path(keys::WITH, &item.value, errors) {
+                    dependency.set_with(path);
+                }
+            }
+            keys::MAP => {
+                if let Some(path) = parse_fn_path(keys::MAP, &item.value, errors) {
+                    dependency.set_map(path);
+                }
             }
-            Err(error) => panic_for_inject_error(error, attr),
+            // Resolved when the dependency is created
+            keys::OPTIONAL | keys::ALL => {}
+            _ => unreachable!(),
         }
     }
-}
 
-fn panic_for_inject_error(error: InjectError, attr: &MacroAttribute) -> ! {
-    match error {
-        InjectError::DuplicatedKey(s) => {
-            panic!("duplicated `#[inject]` key: {}", s);
+    // The keys that cannot be declared together
+    let conflicts = [
+        (keys::DEFAULT, keys::NAME),
+        (keys::ALL, keys::DEFAULT),
+        (keys::ALL, keys::NAME),
+        (keys::WITH, keys::NAME),
+        (keys::WITH, keys::SCOPE),
+        (keys::WITH, keys::DEFAULT),
+        (keys::WITH, keys::OPTIONAL),
+        (keys::WITH, keys::ALL),
+        (keys::WITH, keys::MAP),
+        (keys::MAP, keys::DEFAULT),
+        (keys::MAP, keys::OPTIONAL),
+        (keys::MAP, keys::ALL),
+    ];
+
+    for (key, other) in conflicts {
+        if let (Some(_), Some(item)) = (attr.get(key), attr.get(other)) {
+            errors.push_at(
+                item.span,
+                format!("`#[inject({})]` cannot contain a `{}` field", key, other),
+            );
         }
-        InjectError::InvalidKey(s) => {
-            // panic!(
-            //     "invalid `#[inject]` key: `{}`, valid keys: `name`, `scope` and `default`",
-            //     s
-            // );
+    }
+}
 
-            panic!("invalid `#[inject]` key: `{}`", s);
+// with="path::to::fn" or map="path::to::fn"
+fn parse_fn_path(key: &str, value: &Option<Lit>, errors: &mut Errors) -> Option<ExprPath> {
+    match value {
+        Some(Lit::Str(lit_str)) => match lit_str.parse::<ExprPath>() {
+            Ok(path) => Some(path),
+            Err(_) => {
+                errors.push_spanned(
+                    lit_str,
+                    format!(
+                        "invalid function `{}`, expected a path like `path::to::fn`",
+                        lit_str.value()
+                    ),
+                );
+                None
+            }
+        },
+        value => {
+            errors.push_spanned(
+                value,
+                format!(
+                    "expected string literal for `{0}`: `#[inject({0}=\"path::to::fn\")]`",
+                    key
+                ),
+            );
+            None
         }
-        InjectError::InvalidAttribute => {
-            panic!("invalid attribute: {}", attr);
+    }
+}
+
+// scope="singleton", scope="scoped_singleton" or scope="scoped"
+fn parse_scope(lit_str: &LitStr, errors: &mut Errors) -> Option<Scope> {
+    match lit_str.value().as_str() {
+        "singleton" => Some(Scope::Singleton),
+        "scoped_singleton" => Some(Scope::ScopedSingleton),
+        "scoped" => Some(Scope::Scoped),
+        value => {
+            let message = unknown_value_message(
+                "invalid scope value",
+                value,
+                &["singleton", "scoped_singleton", "scoped"],
+            );
+            errors.push_spanned(lit_str, message);
+            None
         }
     }
 }
@@ -1,47 +1,137 @@
+use crate::error::{unknown_value_message, Errors};
 use crate::keys;
-use mattro::{MacroAttribute, MetaItem, NameValue, Value};
-use std::collections::HashMap;
+use proc_macro2::Span;
+use quote::ToTokens;
+use syn::spanned::Spanned;
+use syn::{Attribute, Lit, Meta, NestedMeta, Path};
 
-pub type InjectAttribute = HashMap<String, Option<Value>>;
+// Keys of `#[inject(...)]` on a field
+pub const FIELD_KEYS: &[&str] = &[
+    keys::NAME,
+    keys::SCOPE,
+    keys::DEFAULT,
+    keys::OPTIONAL,
+    keys::ALL,
+    keys::WITH,
+    keys::MAP,
+];
 
-pub enum InjectError {
-    DuplicatedKey(String),
-    InvalidKey(String),
-    InvalidAttribute,
+// Keys of `#[inject(...)]` that can be declared without a value
+const PATH_KEYS: &[&str] = &[keys::DEFAULT, keys::OPTIONAL, keys::ALL];
+
+/// A key of `#[inject(...)]` and its value, if any.
+pub struct InjectItem {
+    pub span: Span,
+    pub value: Option<Lit>,
+}
+
+/// The keys of an `#[inject(...)]` attribute in declaration order.
+#[derive(Default)]
+pub struct InjectAttribute {
+    items: Vec<(String, InjectItem)>,
 }
 
-pub fn convert_to_inject_attribute_map(
-    attribute: &MacroAttribute,
-) -> Result<InjectAttribute, InjectError> {
-    let mut items = HashMap::new();
+impl InjectAttribute {
+    pub fn get(&self, key: &str) -> Option<&InjectItem> {
+        self.items
+            .iter()
+            .find(|(name, _)| name == key)
+            .map(|(_, item)| item)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    pub fn insert(&mut self, name: String, item: InjectItem) {
+        self.items.push((name, item));
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &InjectItem)> {
+        self.items.iter().map(|(name, item)| (name.as_str(), item))
+    }
+}
 
-    // helper
-    let mut add_item = |name: &str, value: Option<&Value>| {
-        if items.insert(name.to_owned(), value.cloned()).is_some() {
-            return Err(InjectError::DuplicatedKey(name.to_owned()));
+// Parses the `#[inject(...)]` of a field, the invalid keys are added to the errors and skipped
+pub fn parse_inject_attribute(attribute: &Attribute, errors: &mut Errors) -> InjectAttribute {
+    match attribute.parse_meta() {
+        Ok(Meta::List(list)) => parse_inject_items(list.nested.iter(), errors),
+        // #[inject]
+        Ok(Meta::Path(_)) => InjectAttribute::default(),
+        Ok(meta) => {
+            errors.push_spanned(meta, "invalid attribute, expected `#[inject(...)]`");
+            InjectAttribute::default()
         }
+        Err(error) => {
+            errors.push(error);
+            InjectAttribute::default()
+        }
+    }
+}
 
-        Ok(())
-    };
+// Parses the keys of a field, as declared in `#[inject(...)]`
+pub fn parse_inject_items<'a, I>(nested: I, errors: &mut Errors) -> InjectAttribute
+where
+    I: IntoIterator<Item = &'a NestedMeta>,
+{
+    let mut attr = InjectAttribute::default();
 
-    for meta_item in attribute.iter() {
-        match meta_item {
-            MetaItem::Path(path) => {
-                if path == keys::DEFAULT {
-                    add_item(path, None)?;
-                }
+    for meta in nested {
+        let (path, value) = match meta {
+            NestedMeta::Meta(Meta::Path(path)) => (path, None),
+            NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                (&name_value.path, Some(name_value.lit.clone()))
             }
-            MetaItem::NameValue(NameValue { name, value }) => match name.as_str() {
-                keys::DEFAULT => add_item(name, Some(value))?,
-                keys::SCOPE => add_item(name, Some(value))?,
-                keys::NAME => add_item(name, Some(value))?,
-                _ => return Err(InjectError::InvalidKey(name.clone())),
-            },
             _ => {
-                return Err(InjectError::InvalidAttribute);
+                errors.push_spanned(
+                    meta,
+                    "invalid `#[inject]` argument, expected `key` or `key=value`",
+                );
+                continue;
             }
+        };
+
+        let name = path_to_string(path);
+
+        if !FIELD_KEYS.contains(&name.as_str()) {
+            if name == keys::CONSTRUCTOR {
+                errors.push_spanned(
+                    path,
+                    "`constructor` can only be declared on the struct: `#[inject(constructor=\"name(arg1, arg2, ...)\")]`",
+                );
+            } else {
+                let message = unknown_value_message("unknown `#[inject]` key", &name, FIELD_KEYS);
+                errors.push_spanned(path, message);
+            }
+
+            continue;
         }
+
+        if value.is_none() && !PATH_KEYS.contains(&name.as_str()) {
+            errors.push_spanned(
+                path,
+                format!("`{0}` requires a value: `#[inject({0}=\"...\")]`", name),
+            );
+            continue;
+        }
+
+        if value.is_some() && name == keys::ALL {
+            errors.push_spanned(meta, "`all` does not take a value: `#[inject(all)]`");
+            continue;
+        }
+
+        if attr.contains_key(&name) {
+            errors.push_spanned(path, format!("duplicated `#[inject]` key `{}`", name));
+            continue;
+        }
+
+        let span = path.span();
+        attr.insert(name, InjectItem { span, value });
     }
 
-    Ok(items)
+    attr
+}
+
+pub fn path_to_string(path: &Path) -> String {
+    crate::helpers::token_stream_to_string_non_whitespace(&path.to_token_stream())
 }
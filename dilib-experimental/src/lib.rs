@@ -1,3 +1,2 @@
-pub mod error;
 pub mod resolve_call;
 pub mod typing;
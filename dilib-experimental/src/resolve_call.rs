@@ -83,7 +83,7 @@ mod tests {
     #[test]
     fn resolve_call_test_1() {
         let repeater = |a: &String| {
-            return a.repeat(2);
+            a.repeat(2)
         };
 
         let mut container = Container::new();
@@ -97,7 +97,7 @@ mod tests {
     fn resolve_call_fn_test_2() {
         let repeater = move |a: &String, count: &Mutex<usize>| {
             let count = *count.lock().expect("unable to get lock");
-            return a.repeat(count);
+            a.repeat(count)
         };
 
         let mut container = Container::new();
@@ -111,7 +111,7 @@ mod tests {
     #[test]
     fn resolve_call_mut_test_1() {
         let mut repeater = |a: &String| {
-            return a.repeat(2);
+            a.repeat(2)
         };
 
         let mut container = Container::new();
@@ -125,7 +125,7 @@ mod tests {
     fn resolve_call_fn_mut_test_2() {
         let mut repeater = move |a: &String, count: &Mutex<usize>| {
             let count = *count.lock().expect("unable to get lock");
-            return a.repeat(count);
+            a.repeat(count)
         };
 
         let mut container = Container::new();
@@ -0,0 +1,214 @@
+use crate::error::Errors;
+use crate::keys;
+use crate::resolve_fn_arg::ResolvedFnArg;
+use crate::utils::format_tokens;
+use proc_macro2::{Span, TokenStream};
+use quote::quote;
+use syn::{
+    Attribute, AttributeArgs, ImplItem, ImplItemMethod, ItemFn, ItemImpl, Meta, NestedMeta,
+    ReturnType, Type,
+};
+
+#[derive(Debug)]
+pub struct InjectableAttribute {
+    item_impl: ItemImpl,
+    constructor: syn::Ident,
+    asyncness: bool,
+    args: Vec<ResolvedFnArg>,
+}
+
+impl InjectableAttribute {
+    pub fn new(attr: AttributeArgs, mut item_impl: ItemImpl) -> syn::Result<Self> {
+        // The errors of all the arguments are reported at once
+        let mut errors = Errors::default();
+
+        if let Some(meta) = attr.first() {
+            errors.push_spanned(
+                meta,
+                format!("#[{}] does not take arguments", keys::INJECTABLE),
+            );
+        }
+
+        if let Some((_, path, _)) = &item_impl.trait_ {
+            errors.push_spanned(
+                path,
+                format!(
+                    "#[{}] must be declared on an `impl` without a trait",
+                    keys::INJECTABLE
+                ),
+            );
+        }
+
+        let mut constructors = item_impl
+            .items
+            .iter_mut()
+            .filter_map(|item| match item {
+                ImplItem::Method(method) if method.attrs.iter().any(is_constructor_attribute) => {
+                    Some(method)
+                }
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        for method in constructors.iter().skip(1) {
+            errors.push_spanned(
+                &method.sig.ident,
+                format!(
+                    "#[{}] expected a single constructor marked with `#[inject]`",
+                    keys::INJECTABLE
+                ),
+            );
+        }
+
+        let mut constructor = None;
+        let mut asyncness = false;
+        let mut args = Vec::new();
+
+        match constructors.first_mut() {
+            Some(method) => {
+                check_constructor_signature(method, &item_impl.self_ty, &mut errors);
+
+                // The arguments are resolved as the arguments of a `#[provide]` function
+                let item_fn = ItemFn {
+                    attrs: method
+                        .attrs
+                        .iter()
+                        .filter(|attr| !is_constructor_attribute(attr))
+                        .cloned()
+                        .collect(),
+                    vis: method.vis.clone(),
+                    sig: method.sig.clone(),
+                    block: Box::new(method.block.clone()),
+                };
+
+                match ResolvedFnArg::from_fn(&item_fn) {
+                    Ok(resolved_args) => args = resolved_args,
+                    Err(error) => errors.push(error),
+                }
+
+                // `#[inject]` is only used for configuration
+                method.attrs.retain(|attr| !attr.path.is_ident(keys::INJECT));
+                constructor = Some(method.sig.ident.clone());
+                asyncness = method.sig.asyncness.is_some();
+            }
+            None => errors.push_spanned(
+                &item_impl.self_ty,
+                format!(
+                    "#[{}] expected a constructor marked with `#[inject]`: `#[inject] fn new(...) -> Self`",
+                    keys::INJECTABLE
+                ),
+            ),
+        }
+
+        errors.finish()?;
+        Ok(InjectableAttribute {
+            item_impl,
+            // SAFETY: an error is reported if there is no constructor
+            constructor: constructor.unwrap(),
+            asyncness,
+            args,
+        })
+    }
+
+    pub fn expand(&self) -> TokenStream {
+        let item_impl = &self.item_impl;
+        let self_ty = &item_impl.self_ty;
+        let constructor = &self.constructor;
+        let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+        let resolved_args = self.args.as_slice();
+        let arg_names = resolved_args
+            .iter()
+            .map(|arg| syn::Ident::new(&arg.arg_name, Span::call_site()))
+            .collect::<Vec<_>>();
+        let dependencies = resolved_args.iter().map(|arg| arg.emit_info());
+
+        let inject = if self.asyncness {
+            let args = resolved_args.iter().map(|arg| arg.emit_async());
+            quote! {
+                impl #impl_generics dilib::AsyncInject for #self_ty #where_clause {
+                    #[allow(unused)]
+                    fn inject_async<'c>(container: &'c dilib::Container<'c>) -> dilib::InjectFuture<'c, Self> {
+                        std::boxed::Box::pin(async move {
+                            #(#args)*
+                            Self :: #constructor ( #(#arg_names),* ).await
+                        })
+                    }
+
+                    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                        <Self as dilib::InjectMetadata>::dependencies()
+                    }
+                }
+            }
+        } else {
+            quote! {
+                impl #impl_generics dilib::Inject for #self_ty #where_clause {
+                    #[allow(unused)]
+                    fn inject(container: &dilib::Container) -> Self {
+                        #(#resolved_args)*
+                        Self :: #constructor ( #(#arg_names),* )
+                    }
+
+                    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                        <Self as dilib::InjectMetadata>::dependencies()
+                    }
+                }
+            }
+        };
+
+        quote! {
+            #item_impl
+
+            #inject
+
+            impl #impl_generics dilib::InjectMetadata for #self_ty #where_clause {
+                fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+                    std::vec::Vec::from([ #(#dependencies),* ])
+                }
+            }
+        }
+    }
+}
+
+// `#[inject]` or `#[inject(constructor)]`
+fn is_constructor_attribute(attr: &Attribute) -> bool {
+    if !attr.path.is_ident(keys::INJECT) {
+        return false;
+    }
+
+    match attr.parse_meta() {
+        Ok(Meta::Path(_)) => true,
+        Ok(Meta::List(list)) => {
+            list.nested.len() == 1
+                && matches!(list.nested.first(), Some(NestedMeta::Meta(Meta::Path(path))) if path.is_ident(keys::CONSTRUCTOR))
+        }
+        _ => false,
+    }
+}
+
+// fn name(arg1: A, arg2: B, ...) -> Self
+fn check_constructor_signature(method: &ImplItemMethod, self_ty: &Type, errors: &mut Errors) {
+    let returns_self = match &method.sig.output {
+        ReturnType::Type(_, ty) => {
+            let ty = format_tokens(ty);
+            ty == "Self" || ty == format_tokens(self_ty)
+        }
+        ReturnType::Default => false,
+    };
+
+    if !returns_self {
+        errors.push_spanned(
+            &method.sig.ident,
+            format!("the constructor `{}` must return `Self`", method.sig.ident),
+        );
+    }
+
+    if !method.sig.generics.params.is_empty() {
+        errors.push_spanned(
+            &method.sig.generics,
+            format!(
+                "the constructor `{}` cannot have generic parameters",
+                method.sig.ident
+            ),
+        );
+    }
+}
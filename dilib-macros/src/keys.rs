@@ -7,8 +7,23 @@ pub const INJECT: &str = "inject";
 /// `#[provide(name="instance_name")]`
 pub const NAME: &str = "name";
 
-/// `#[provide(scope="...")]` `singleton` or `scoped` instance.
+/// `#[provide(scope="...")]` `singleton`, `scoped_singleton` or `scoped` instance.
 pub const SCOPE: &str = "scope";
 
 /// `#[provide(bind="SomeTrait")]` trait to bind this instance to.
 pub const BIND: &str = "bind";
+
+/// `#[inject(param, optional)]` resolves an `Option<T>` argument as `None` if there is no provider.
+pub const OPTIONAL: &str = "optional";
+
+/// `#[provide(multi)]` adds the provider to the multi-bindings of the type.
+pub const MULTI: &str = "multi";
+
+/// `#[provide(priority=10)]` the order of the provider in `get_all`, higher first.
+pub const PRIORITY: &str = "priority";
+
+/// `#[injectable]` on an `impl` to implement `Inject` with a constructor.
+pub const INJECTABLE: &str = "injectable";
+
+/// `#[inject(constructor)]` marks the constructor of an `#[injectable]` impl.
+pub const CONSTRUCTOR: &str = "constructor";
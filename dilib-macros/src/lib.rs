@@ -1,3 +1,5 @@
+mod error;
+mod injectable_attr;
 mod keys;
 mod provide_attr;
 mod resolve_fn_arg;
@@ -5,6 +7,7 @@ mod scope;
 mod target;
 mod utils;
 
+use crate::injectable_attr::InjectableAttribute;
 use crate::provide_attr::ProvideAttribute;
 use proc_macro::TokenStream;
 use syn::parse_macro_input;
@@ -14,8 +17,19 @@ use target::Target;
 ///
 /// # Arguments
 /// - `name`: The name of the provider.
-/// - `scope`: The scope 'singleton' or 'scoped', defaults to 'scoped'.
+/// - `scope`: The scope 'singleton', 'scoped_singleton' or 'scoped', defaults to 'scoped'.
 /// - `bind`: The trait to bind this instance to. This adds `Send + Sync` to the type.
+/// - `multi`: Adds the provider to the multi-bindings of the type, which are returned by `get_all`.
+///   Cannot be used with `name`, the scope must be 'singleton' or 'scoped'.
+/// - `priority`: The order of the provider in `get_all`, higher first, defaults to `0`.
+///   A negative priority must be written as a string, `priority="-1"`.
+///
+/// When used over an `async fn` registers an async provider, this requires the `async` feature
+/// of `dilib` and the value must be resolved with `get_async`.
+/// An `async fn` cannot be provided as `scoped_singleton`.
+///
+/// The function arguments are resolved from the container, `Arc<T>` and `Singleton<T>` as singletons,
+/// and `Lazy<T>` and `Factory<T>` as handles of `T`. `Option<T>` arguments are `None` if there is no provider for `T`.
 ///
 /// # Example
 ///
@@ -63,19 +77,41 @@ pub fn provide(attr: TokenStream, item: TokenStream) -> TokenStream {
     let target = match parse_macro_input!(item as syn::Item) {
         syn::Item::Fn(item_fn) => Target::Fn(item_fn),
         syn::Item::Struct(item_struct) => Target::Struct(item_struct),
-        _ => {
-            let call_site = proc_macro2::Span::call_site();
-            return syn::Error::new(call_site, "Expected a function or struct")
+        item => {
+            return syn::Error::new_spanned(item, "Expected a function or struct")
                 .into_compile_error()
                 .into();
         }
     };
 
-    ProvideAttribute::new(attr, target).expand().into()
+    match ProvideAttribute::new(attr, target.clone()) {
+        Ok(provide) => provide.expand().into(),
+        Err(error) => {
+            // The item is kept so only the errors of the attribute are reported,
+            // the `#[inject]` were already checked so are removed
+            let error = error.into_compile_error();
+            let target = match target {
+                Target::Fn(mut item_fn) => {
+                    item_fn.attrs.retain(|attr| !attr.path.is_ident("inject"));
+                    Target::Fn(item_fn)
+                }
+                target => target,
+            };
+
+            quote::quote!(#error #target).into()
+        }
+    }
 }
 
 /// Specify the dependency to inject to a function argument.
 ///
+/// # Arguments
+/// - `name`: The name of the dependency.
+/// - `scope`: The scope 'singleton', 'scoped_singleton' or 'scoped', defaults to 'singleton'
+///   for `Arc<T>` and `Singleton<T>` and 'scoped' for other types.
+/// - `optional`: Whether an `Option<T>` argument is `None` if there is no provider for `T`, defaults to `true`.
+///   With `optional=false` the argument is resolved as a value of type `Option<T>`.
+///
 /// # Example
 /// ```rust,no_run
 /// use std::sync::Arc;
@@ -83,7 +119,8 @@ pub fn provide(attr: TokenStream, item: TokenStream) -> TokenStream {
 ///
 /// #[provide]
 /// #[inject(value, name="initial_value")]
-/// fn compute(hash: String, value: Arc<usize>) -> usize {
+/// #[inject(cache, scope="scoped_singleton")]
+/// fn compute(hash: String, value: Arc<usize>, cache: Arc<Vec<usize>>) -> usize {
 ///    0 // ...
 /// }
 /// ```
@@ -91,3 +128,55 @@ pub fn provide(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn inject(_: TokenStream, item: TokenStream) -> TokenStream {
     item
 }
+
+/// Implements `Inject` for a type using the constructor of the `impl` marked with `#[inject]`.
+///
+/// The constructor is an associated function that returns `Self`, its arguments are resolved
+/// from the container as the arguments of a `#[provide]` function, `Arc<T>` and `Singleton<T>` as singletons,
+/// and configured with `#[inject(arg, name="...", scope="...")]`. `#[inject(constructor)]` can also be used
+/// to mark the constructor.
+///
+/// An `async fn` constructor implements `AsyncInject` instead, this requires the `async` feature of `dilib`.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use dilib::macros::injectable;
+///
+/// struct Repository;
+///
+/// pub struct UserService {
+///     repository: Arc<Repository>,
+///     label: String,
+/// }
+///
+/// #[injectable]
+/// impl UserService {
+///     #[inject]
+///     #[inject(label, name="label")]
+///     pub fn new(repository: Arc<Repository>, label: String) -> Self {
+///         UserService { repository, label }
+///     }
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn injectable(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = parse_macro_input!(attr as syn::AttributeArgs);
+    let item_impl = parse_macro_input!(item as syn::ItemImpl);
+
+    match InjectableAttribute::new(attr, item_impl.clone()) {
+        Ok(injectable) => injectable.expand().into(),
+        Err(error) => {
+            // The impl is kept so only the errors of the attribute are reported
+            let error = error.into_compile_error();
+            let mut item_impl = item_impl;
+            for item in item_impl.items.iter_mut() {
+                if let syn::ImplItem::Method(method) = item {
+                    method.attrs.retain(|attr| !attr.path.is_ident("inject"));
+                }
+            }
+
+            quote::quote!(#error #item_impl).into()
+        }
+    }
+}
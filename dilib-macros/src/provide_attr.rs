@@ -1,88 +1,233 @@
+use crate::error::{unknown_value_message, Errors};
 use crate::keys;
 use crate::resolve_fn_arg::ResolvedFnArg;
 use crate::scope::Scope;
 use crate::target::Target;
 use crate::utils::format_tokens;
-use mattro::{MacroAttribute, Value};
 use proc_macro2::{Span, TokenStream};
 use quote::{quote, ToTokens};
-use std::collections::HashMap;
-use syn::{AttrStyle, AttributeArgs, ItemFn, ItemStruct, ReturnType, Type};
+use syn::{AttributeArgs, ItemFn, ItemStruct, Lit, LitStr, Meta, NestedMeta, ReturnType, Type};
+
+// Keys of `#[provide(...)]`
+const PROVIDE_KEYS: &[&str] = &[
+    keys::NAME,
+    keys::SCOPE,
+    keys::BIND,
+    keys::MULTI,
+    keys::PRIORITY,
+];
 
 #[derive(Debug)]
 pub struct ProvideAttribute {
     name: Option<String>,
     scope: Scope,
     target: Target,
-    bind: Option<Vec<Box<Type>>>,
+    bind: Option<Vec<Type>>,
+    multi: bool,
+    priority: i32,
+    args: Vec<ResolvedFnArg>,
 }
 
 impl ProvideAttribute {
-    pub fn new(attr: AttributeArgs, target: Target) -> Self {
-        let attr = MacroAttribute::from_attribute_args(keys::PROVIDE, attr, AttrStyle::Outer);
-
-        let name_value_attr = attr
-            .into_name_values()
-            .unwrap_or_else(|_| panic!("#[{}] have invalid arguments", keys::PROVIDE));
-
-        let mut map = name_value_attr
-            .into_iter()
-            .collect::<HashMap<String, Value>>();
-
-        let name = map.remove_entry(keys::NAME).map(|(_, value)| {
-            value.to_string_literal().unwrap_or_else(|| {
-                panic!(
-                    "#[{}] '{}' must be a string literal",
-                    keys::PROVIDE,
-                    keys::NAME
-                )
-            })
-        });
-
-        let scope = map
-            .remove_entry("scope")
-            .map(|(_, value)| {
-                value.to_string_literal().unwrap_or_else(|| {
-                    panic!(
-                        "#[{}] '{}' must be a string literal",
+    pub fn new(attr: AttributeArgs, target: Target) -> syn::Result<Self> {
+        // The errors of all the arguments are reported at once
+        let mut errors = Errors::default();
+        let mut keys_found = Vec::<String>::new();
+
+        let mut name = None;
+        let mut scope = None;
+        let mut bind = None;
+        let mut multi = None;
+        let mut priority = None;
+
+        for meta in attr.iter() {
+            let (path, lit) = match meta {
+                NestedMeta::Meta(Meta::Path(path)) => (path, None),
+                NestedMeta::Meta(Meta::NameValue(name_value)) => {
+                    (&name_value.path, Some(&name_value.lit))
+                }
+                _ => {
+                    errors
+                        .push_spanned(meta, format!("#[{}] have invalid arguments", keys::PROVIDE));
+                    continue;
+                }
+            };
+
+            let key = format_tokens(path);
+            if !PROVIDE_KEYS.contains(&key.as_str()) {
+                let message = format!("#[{}] has invalid key", keys::PROVIDE);
+                errors.push_spanned(path, unknown_value_message(&message, &key, PROVIDE_KEYS));
+                continue;
+            }
+
+            if keys_found.contains(&key) {
+                errors.push_spanned(
+                    path,
+                    format!("#[{}] has duplicated key '{}'", keys::PROVIDE, key),
+                );
+                continue;
+            }
+
+            keys_found.push(key.clone());
+
+            // `multi` is the only argument without value
+            let lit = match (key.as_str(), lit) {
+                (keys::MULTI, None) => {
+                    multi = Some(path);
+                    continue;
+                }
+                (keys::MULTI, Some(_)) => {
+                    errors.push_spanned(
+                        meta,
+                        format!(
+                            "#[{}] '{}' does not take a value",
+                            keys::PROVIDE,
+                            keys::MULTI
+                        ),
+                    );
+                    continue;
+                }
+                (_, None) => {
+                    errors.push_spanned(
+                        path,
+                        format!(
+                            "#[{0}] '{1}' requires a value: `{1}=\"...\"`",
+                            keys::PROVIDE,
+                            key
+                        ),
+                    );
+                    continue;
+                }
+                (_, Some(lit)) => lit,
+            };
+
+            match key.as_str() {
+                keys::NAME => match lit {
+                    Lit::Str(lit_str) => name = Some((lit_str.value(), path)),
+                    _ => errors.push_spanned(
+                        lit,
+                        format!(
+                            "#[{}] '{}' must be a string literal",
+                            keys::PROVIDE,
+                            keys::NAME
+                        ),
+                    ),
+                },
+                keys::SCOPE => match Scope::from_lit(lit) {
+                    Ok(value) => scope = Some((value, lit)),
+                    Err(error) => errors.push(error),
+                },
+                keys::BIND => match lit {
+                    Lit::Str(lit_str) => bind = Some(get_bind_types(lit_str, &mut errors)),
+                    _ => errors.push_spanned(
+                        lit,
+                        format!(
+                            "#[{}] '{}' must be a string literal",
+                            keys::PROVIDE,
+                            keys::BIND
+                        ),
+                    ),
+                },
+                keys::PRIORITY => {
+                    // A negative priority can only be written as a string, `priority="-1"`
+                    let value = match lit {
+                        Lit::Int(lit_int) => lit_int.base10_parse::<i32>().ok(),
+                        Lit::Str(lit_str) => lit_str.value().parse::<i32>().ok(),
+                        _ => None,
+                    };
+
+                    match value {
+                        Some(value) => priority = Some(value),
+                        None => errors.push_spanned(
+                            lit,
+                            format!(
+                                "#[{}] '{}' must be an integer literal",
+                                keys::PROVIDE,
+                                keys::PRIORITY
+                            ),
+                        ),
+                    }
+                }
+                _ => unreachable!(),
+            }
+        }
+
+        if multi.is_some() {
+            if let Some((_, name_path)) = &name {
+                errors.push_spanned(
+                    name_path,
+                    format!(
+                        "#[{}] '{}' providers cannot have a '{}'",
                         keys::PROVIDE,
-                        keys::SCOPE
-                    )
-                })
-            })
-            .map(|s| Scope::from_str(&s))
-            .unwrap_or(Scope::Scoped);
-
-        let bind = map.remove_entry(keys::BIND).map(|(_, values)| {
-            let mut types = vec![];
-            let bind_values_str = values.to_string_literal().unwrap_or_else(|| {
-                panic!(
-                    "#[{}] '{}' must be a string literal",
-                    keys::PROVIDE,
-                    keys::BIND
-                )
-            });
-            for value in bind_values_str.split(",") {
-                let type_string = value;
-                // We need: Box<dyn TraitType + Send + Sync>
-                let boxed_type = format!("std::boxed::Box<dyn {} + Send + Sync>", type_string);
-                types.push(syn::parse_str::<Box<Type>>(&boxed_type)
-                    .unwrap_or_else(|_| panic!("'{}' is not a valid trait type", type_string)))
+                        keys::MULTI,
+                        keys::NAME
+                    ),
+                );
             }
-            types
-        });
 
-        // Handle unknowns key-value
-        if let Some((invalid_key, _)) = map.iter().next() {
-            panic!("#[{}] has invalid key: {}", keys::PROVIDE, invalid_key);
+            if let Some((Scope::ScopedSingleton, lit)) = &scope {
+                errors.push_spanned(
+                    lit,
+                    format!(
+                        "#[{}] '{}' providers must be 'singleton' or 'scoped'",
+                        keys::PROVIDE,
+                        keys::MULTI
+                    ),
+                );
+            }
+        }
+
+        let mut args = Vec::new();
+
+        if let Target::Fn(item_fn) = &target {
+            // We need a return type for the function
+            if item_fn.sig.output == ReturnType::Default {
+                errors.push_spanned(
+                    &item_fn.sig.ident,
+                    format!("function {} must have a return type", item_fn.sig.ident),
+                );
+            }
+
+            if let Some(asyncness) = &item_fn.sig.asyncness {
+                if multi.is_some() {
+                    errors.push_spanned(
+                        asyncness,
+                        format!(
+                            "async function {} cannot be provided as '{}'",
+                            item_fn.sig.ident,
+                            keys::MULTI
+                        ),
+                    );
+                }
+
+                if let Some((Scope::ScopedSingleton, lit)) = &scope {
+                    errors.push_spanned(
+                        lit,
+                        format!(
+                            "async function {} cannot be provided as 'scoped_singleton', expected 'singleton' or 'scoped'",
+                            item_fn.sig.ident
+                        ),
+                    );
+                }
+            }
+
+            match ResolvedFnArg::from_fn(item_fn) {
+                Ok(resolved_args) => args = resolved_args,
+                Err(error) => errors.push(error),
+            }
         }
 
-        ProvideAttribute {
-            name,
-            scope,
+        errors.finish()?;
+
+        Ok(ProvideAttribute {
+            name: name.map(|(name, _)| name),
+            scope: scope.map(|(scope, _)| scope).unwrap_or(Scope::Scoped),
             target,
             bind,
-        }
+            multi: multi.is_some(),
+            priority: priority.unwrap_or(0),
+            args,
+        })
     }
 
     pub fn expand(self) -> proc_macro2::TokenStream {
@@ -90,63 +235,83 @@ impl ProvideAttribute {
         let scope = self.scope;
         let target = self.target;
         let bind = self.bind;
+        let multi = self.multi;
+        let args = self.args;
+        let priority = proc_macro2::Literal::i32_unsuffixed(self.priority);
         let ty = target.target_type();
         let mut result_code = quote! {
             #target
         };
 
-        let bind_types = bind.clone().unwrap_or(vec![ty.clone()]);
+        let bind_types = bind.clone().unwrap_or_else(|| vec![ty.as_ref().clone()]);
 
         for bind_type in bind_types {
             let key = get_injection_key(&bind_type, name.as_deref());
             let local_bind = bind.clone().map(|_| bind_type);
-            let local_bind = local_bind.as_deref();
-
-            // We need a return type for the function
-            if let Target::Fn(item_fn) = &target {
-                if item_fn.sig.output == ReturnType::Default {
-                    panic!("function {} must have a return type", item_fn.sig.ident);
-                }
-            }
+            let local_bind = local_bind.as_ref();
 
             let provider = match &target {
+                // Async scoped singletons are rejected when the attribute is parsed
+                Target::Fn(item_fn) if item_fn.sig.asyncness.is_some() => match scope {
+                    Scope::Singleton => {
+                        get_async_singleton_provider(item_fn, &args, &ty, local_bind)
+                    }
+                    Scope::Scoped | Scope::ScopedSingleton => {
+                        get_async_scoped_provider(item_fn, &args, &ty, local_bind)
+                    }
+                },
                 Target::Fn(item_fn) => match scope {
-                    Scope::Scoped => {
+                    Scope::Scoped | Scope::ScopedSingleton => {
                         if item_fn.sig.inputs.is_empty() {
-                            get_scoped_provider(item_fn, local_bind)
+                            get_scoped_provider(item_fn, scope, local_bind)
                         } else {
-                            get_resolved_scoped_provider(item_fn, &ty, local_bind)
+                            get_resolved_scoped_provider(item_fn, &args, &ty, scope, local_bind)
                         }
                     }
                     Scope::Singleton => {
                         if item_fn.sig.inputs.is_empty() {
                             get_singleton_provider(item_fn, local_bind)
                         } else {
-                            get_resolved_singleton_provider(item_fn, &ty, local_bind)
+                            get_resolved_singleton_provider(item_fn, &args, &ty, local_bind)
                         }
                     }
                 },
                 Target::Struct(item_struct) => match scope {
-                    Scope::Scoped => get_inject_provider(item_struct, local_bind),
+                    Scope::Scoped | Scope::ScopedSingleton => {
+                        get_inject_provider(item_struct, scope, local_bind)
+                    }
                     Scope::Singleton => get_singleton_inject_provider(item_struct, local_bind),
                 },
             };
 
             let add_provider = quote! {
-            let mut lock = dilib::global::PROVIDERS.lock().expect("unable to get providers lock");
-            let providers = lock.as_mut().expect("unable to get providers");
-
-            providers.push(dilib::global::InjectProvider {
-                key: #key,
-                provider: #provider,
-            });
-        };
+                let mut lock = dilib::global::PROVIDERS.lock().expect("unable to get providers lock");
+                let providers = lock.as_mut().expect("unable to get providers");
+
+                providers.push(dilib::global::InjectProvider {
+                    key: #key,
+                    provider: #provider,
+                    multi: #multi,
+                    priority: #priority,
+                });
+            };
 
             let ctor_name = generate_fn_name(&ty, &target);
 
+            // Async providers fail to compile if the `async` feature is not enabled
+            let require_async = match &target {
+                Target::Fn(item_fn) if item_fn.sig.asyncness.is_some() => {
+                    let fn_name = item_fn.sig.ident.to_string();
+                    quote! { dilib::__require_async!(#fn_name); }
+                }
+                _ => quote! {},
+            };
+
             result_code = quote! {
                 // We hide the generated function
                 const _: () = {
+                    #require_async
+
                     #[cold]
                     #[doc(hidden)]
                     #[allow(non_snake_case)]
@@ -165,6 +330,25 @@ impl ProvideAttribute {
     }
 }
 
+// bind="Trait1, Trait2", each trait is bound as `Box<dyn Trait + Send + Sync>`
+fn get_bind_types(lit_str: &LitStr, errors: &mut Errors) -> Vec<Type> {
+    let mut types = vec![];
+
+    for type_string in lit_str.value().split(',') {
+        // We need: Box<dyn TraitType + Send + Sync>
+        let boxed_type = format!("std::boxed::Box<dyn {} + Send + Sync>", type_string);
+        match syn::parse_str::<Type>(&boxed_type) {
+            Ok(ty) => types.push(ty),
+            Err(_) => errors.push_spanned(
+                lit_str,
+                format!("'{}' is not a valid trait type", type_string.trim()),
+            ),
+        }
+    }
+
+    types
+}
+
 fn generate_fn_name(ty: &syn::Type, target: &Target) -> syn::Ident {
     fn sanitize_type_name<T: ToTokens>(t: &T) -> String {
         let mut tokens = format_tokens(t)
@@ -217,8 +401,17 @@ fn get_injection_key(ty: &syn::Type, name: Option<&str>) -> TokenStream {
     }
 }
 
-fn get_scoped_provider(item_fn: &ItemFn, bind: Option<&syn::Type>) -> TokenStream {
+// `dilib::Provider::Scoped` or `dilib::Provider::ScopedSingleton`, both take a `dilib::Scoped`
+fn get_scoped_variant(scope: Scope) -> TokenStream {
+    match scope {
+        Scope::ScopedSingleton => quote! { dilib::Provider::ScopedSingleton },
+        _ => quote! { dilib::Provider::Scoped },
+    }
+}
+
+fn get_scoped_provider(item_fn: &ItemFn, scope: Scope, bind: Option<&syn::Type>) -> TokenStream {
     let fn_name = item_fn.sig.ident.clone();
+    let variant = get_scoped_variant(scope);
 
     if let Some(bind) = bind {
         let factory = quote! {
@@ -226,13 +419,13 @@ fn get_scoped_provider(item_fn: &ItemFn, bind: Option<&syn::Type>) -> TokenStrea
         };
 
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_factory(#factory)
             )
         }
     } else {
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_factory(#fn_name)
             )
         }
@@ -241,32 +434,37 @@ fn get_scoped_provider(item_fn: &ItemFn, bind: Option<&syn::Type>) -> TokenStrea
 
 fn get_resolved_scoped_provider(
     item_fn: &ItemFn,
+    resolved_args: &[ResolvedFnArg],
     ty: &syn::Type,
+    scope: Scope,
     bind: Option<&syn::Type>,
 ) -> TokenStream {
     let fn_name = item_fn.sig.ident.clone();
-    let resolved_args = ResolvedFnArg::from_fn(item_fn);
+    let variant = get_scoped_variant(scope);
     let arg_names = resolved_args
         .iter()
         .map(|arg| syn::Ident::new(&arg.arg_name, Span::call_site()))
         .collect::<Vec<_>>();
+    let dependencies = resolved_args.iter().map(|arg| arg.emit_info());
 
     if let Some(bind) = bind {
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_construct(|container: &dilib::Container| -> #bind {
                     #(#resolved_args)*
                     std::boxed::Box::new(#fn_name(#(#arg_names),*))
                 })
+                .with_dependencies([ #(#dependencies),* ])
             )
         }
     } else {
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_construct(|container: &dilib::Container| -> #ty {
                     #(#resolved_args)*
                     #fn_name(#(#arg_names),*)
                 })
+                .with_dependencies([ #(#dependencies),* ])
             )
         }
     }
@@ -294,56 +492,139 @@ fn get_singleton_provider(item_fn: &ItemFn, bind: Option<&syn::Type>) -> TokenSt
 
 fn get_resolved_singleton_provider(
     item_fn: &ItemFn,
+    resolved_args: &[ResolvedFnArg],
     ty: &syn::Type,
     bind: Option<&syn::Type>,
 ) -> TokenStream {
     let fn_name = item_fn.sig.ident.clone();
-    let resolved_args = ResolvedFnArg::from_fn(item_fn);
     let arg_names = resolved_args
         .iter()
         .map(|arg| syn::Ident::new(&arg.arg_name, Span::call_site()))
         .collect::<Vec<_>>();
+    let dependencies = resolved_args.iter().map(|arg| arg.emit_info());
 
     if let Some(bind) = bind {
         quote! {
             dilib::Provider::Singleton(
-                dilib::Shared::from_factory(|container: &dilib::Container| -> #bind {
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> #bind {
                     #(#resolved_args)*
                     std::boxed::Box::new(#fn_name(#(#arg_names),*))
                 })
+                .with_dependencies([ #(#dependencies),* ])
             )
         }
     } else {
         quote! {
             dilib::Provider::Singleton(
-                dilib::Shared::from_factory(|container: &dilib::Container| -> #ty {
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> #ty {
                     #(#resolved_args)*
                     #fn_name(#(#arg_names),*)
                 })
+                .with_dependencies([ #(#dependencies),* ])
             )
         }
     }
 }
 
-fn get_inject_provider(item_struct: &ItemStruct, bind: Option<&syn::Type>) -> TokenStream {
+// Async function arguments are resolved awaiting the async providers
+fn get_async_scoped_provider(
+    item_fn: &ItemFn,
+    resolved_args: &[ResolvedFnArg],
+    ty: &syn::Type,
+    bind: Option<&syn::Type>,
+) -> TokenStream {
+    let fn_name = item_fn.sig.ident.clone();
+    let arg_names = resolved_args
+        .iter()
+        .map(|arg| syn::Ident::new(&arg.arg_name, Span::call_site()))
+        .collect::<Vec<_>>();
+    let args = resolved_args.iter().map(|arg| arg.emit_async());
+    let dependencies = resolved_args.iter().map(|arg| arg.emit_info());
+
+    let (value_type, value) = match bind {
+        Some(bind) => (
+            bind,
+            quote! { std::boxed::Box::new(#fn_name(#(#arg_names),*).await) },
+        ),
+        None => (ty, quote! { #fn_name(#(#arg_names),*).await }),
+    };
+
+    quote! {
+        dilib::Provider::Scoped(
+            dilib::Scoped::from_async_construct::<#value_type, _>(|container| {
+                std::boxed::Box::pin(async move {
+                    #(#args)*
+                    let value: #value_type = #value;
+                    value
+                })
+            })
+            .with_dependencies([ #(#dependencies),* ])
+        )
+    }
+}
+
+fn get_async_singleton_provider(
+    item_fn: &ItemFn,
+    resolved_args: &[ResolvedFnArg],
+    ty: &syn::Type,
+    bind: Option<&syn::Type>,
+) -> TokenStream {
+    let fn_name = item_fn.sig.ident.clone();
+    let arg_names = resolved_args
+        .iter()
+        .map(|arg| syn::Ident::new(&arg.arg_name, Span::call_site()))
+        .collect::<Vec<_>>();
+    let args = resolved_args.iter().map(|arg| arg.emit_async());
+    let dependencies = resolved_args.iter().map(|arg| arg.emit_info());
+
+    let (value_type, value) = match bind {
+        Some(bind) => (
+            bind,
+            quote! { std::boxed::Box::new(#fn_name(#(#arg_names),*).await) },
+        ),
+        None => (ty, quote! { #fn_name(#(#arg_names),*).await }),
+    };
+
+    quote! {
+        dilib::Provider::Singleton(
+            dilib::Shared::from_async_construct::<#value_type, _>(|container| {
+                std::boxed::Box::pin(async move {
+                    #(#args)*
+                    let value: #value_type = #value;
+                    value
+                })
+            })
+            .with_dependencies([ #(#dependencies),* ])
+        )
+    }
+}
+
+fn get_inject_provider(
+    item_struct: &ItemStruct,
+    scope: Scope,
+    bind: Option<&syn::Type>,
+) -> TokenStream {
     let struct_name = item_struct.ident.clone();
+    let variant = get_scoped_variant(scope);
 
     if let Some(bind) = bind {
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_construct(|container: &dilib::Container| -> #bind {
                         std::boxed::Box::new(<#struct_name as dilib::Inject> :: inject(container))
                     }
                 )
+                .with_dependencies(<#struct_name as dilib::Inject> :: dependencies())
             )
         }
     } else {
         quote! {
-            dilib::Provider::Scoped(
+            #variant(
                 dilib::Scoped::from_construct(|container: &dilib::Container| -> #struct_name {
                         <#struct_name as dilib::Inject> :: inject(container)
                     }
                 )
+                .with_dependencies(<#struct_name as dilib::Inject> :: dependencies())
             )
         }
     }
@@ -362,6 +643,7 @@ fn get_singleton_inject_provider(
                         std::boxed::Box::new(<#struct_name as dilib::Inject> :: inject(container))
                     }
                 )
+                .with_dependencies(<#struct_name as dilib::Inject> :: dependencies())
             )
         }
     } else {
@@ -371,6 +653,7 @@ fn get_singleton_inject_provider(
                         <#struct_name as dilib::Inject> :: inject(container)
                     }
                 )
+                .with_dependencies(<#struct_name as dilib::Inject> :: dependencies())
             )
         }
     }
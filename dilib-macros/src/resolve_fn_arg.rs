@@ -1,144 +1,452 @@
+use crate::error::{unknown_value_message, Errors};
 use crate::keys;
+use crate::scope::Scope;
 use crate::utils::format_tokens;
-use mattro::{MacroAttribute, MetaItem};
 use proc_macro2::TokenStream;
 use quote::{quote, ToTokens, TokenStreamExt};
-use syn::{ItemFn, PathArguments};
+use syn::{Attribute, ItemFn, Lit, Meta, NestedMeta, PathArguments};
 
 const INVALID_SIGNATURE: &str = const_format::formatcp!(
-    "invalid #[{0}] signature, expected: #[{0}(param, {1} = \"value\")]",
+    "invalid #[{0}] signature, expected: #[{0}(param, {1} = \"value\", {2} = \"value\")]",
     keys::INJECT,
-    keys::NAME
+    keys::NAME,
+    keys::SCOPE
 );
 
+// Keys of `#[inject(param, ...)]`
+const INJECT_KEYS: &[&str] = &[keys::NAME, keys::SCOPE, keys::OPTIONAL];
+
+#[derive(Debug)]
 pub struct ResolvedFnArg {
     pub name: Option<String>,
     pub arg_name: String,
     pub ty: Box<syn::Type>,
+    pub scope: Scope,
+    pub handle: Option<Handle>,
+    pub optional: bool,
+}
+
+/// A handle that resolves the argument after the value is constructed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Handle {
+    /// A `dilib::Lazy<T>` resolved the first time is dereferenced.
+    Lazy,
+    /// A `dilib::Factory<T>` that creates a new value each time is called.
+    Factory,
 }
 
 impl ResolvedFnArg {
-    pub fn from_fn(item_fn: &ItemFn) -> Vec<ResolvedFnArg> {
-        let sig = &item_fn.sig;
-        let mut args = Vec::new();
+    fn new(arg_name: String, ty: Box<syn::Type>, allow_optional: bool) -> syn::Result<Self> {
+        // `Option<T>` is resolved with the type `T`, and is `None` if there is no provider
+        let (ty, optional) = match get_option_type(&ty) {
+            Some(inner) if allow_optional => (inner, true),
+            _ => (ty, false),
+        };
 
-        for arg in sig.inputs.iter() {
-            let arg_name = match arg {
-                syn::FnArg::Typed(syn::PatType { pat, .. }) => match pat.as_ref() {
-                    syn::Pat::Ident(syn::PatIdent { ref ident, .. }) => ident.to_string(),
-                    _ => panic!("unexpected pattern in function argument"),
-                },
-                _ => panic!("expected named argument"),
-            };
+        // `Arc<T>` and `Singleton<T>` are singletons by default
+        let scope = if is_singleton(&ty) {
+            if get_generic_type(&ty).is_none() {
+                return Err(syn::Error::new_spanned(
+                    &ty,
+                    "expected 'Singleton<T>' or 'Arc<T>' type",
+                ));
+            }
 
-            let ty = match arg {
-                syn::FnArg::Typed(syn::PatType { ty, .. }) => ty.clone(),
-                _ => panic!("expected named argument"),
-            };
+            Scope::Singleton
+        } else {
+            Scope::Scoped
+        };
 
-            args.push(ResolvedFnArg {
-                name: None,
-                arg_name,
-                ty,
-            });
-        }
+        // `Lazy<T>` and `Factory<T>` are resolved with the type `T`
+        let (ty, handle) = match get_handle_type(&ty) {
+            Some((inner, handle)) => (inner, Some(handle)),
+            None => (ty, None),
+        };
 
-        let attrs = item_fn
-            .attrs
-            .iter()
-            .cloned()
-            .filter_map(|att| MacroAttribute::new(att).ok())
-            .filter(|att| att.path() == keys::INJECT)
-            .collect::<Vec<_>>();
-
-        if !attrs.is_empty() {
-            for attr in attrs.iter() {
-                let arg = match attr.get(0) {
-                    Some(MetaItem::Path(path)) => path.clone(),
-                    _ => panic!("{}", INVALID_SIGNATURE),
-                };
+        Ok(ResolvedFnArg {
+            name: None,
+            arg_name,
+            ty,
+            scope,
+            handle,
+            optional,
+        })
+    }
 
-                match args.iter_mut().find(|x| x.arg_name == arg) {
-                    Some(resolved_arg) => match attr.get(1) {
-                        Some(MetaItem::NameValue(x)) => {
-                            let name = &x.name;
-                            let value = &x.value;
+    pub fn from_fn(item_fn: &ItemFn) -> syn::Result<Vec<ResolvedFnArg>> {
+        // The errors of all the arguments are reported at once
+        let mut errors = Errors::default();
+        let mut args = Vec::new();
 
-                            if name != keys::NAME {
-                                panic!("{}", INVALID_SIGNATURE);
-                            }
+        // The declared type of each argument, to resolve `Option<T>` as a value with `optional=false`
+        let mut declared_types = Vec::new();
 
-                            resolved_arg.name = match value.to_string_literal() {
-                                Some(value) => Some(value),
-                                None => panic!("{}", INVALID_SIGNATURE),
-                            };
+        for arg in item_fn.sig.inputs.iter() {
+            match arg {
+                syn::FnArg::Typed(syn::PatType { pat, ty, .. }) => match pat.as_ref() {
+                    syn::Pat::Ident(syn::PatIdent { ident, .. }) => {
+                        match ResolvedFnArg::new(ident.to_string(), ty.clone(), true) {
+                            Ok(resolved_arg) => {
+                                args.push(resolved_arg);
+                                declared_types.push(ty.clone());
+                            }
+                            Err(error) => errors.push(error),
                         }
-                        _ => panic!("{}", INVALID_SIGNATURE),
-                    },
-                    None => {
-                        panic!("unable to find '{0}' for '#[inject({0}, ...)]'", arg);
                     }
+                    _ => errors.push_spanned(pat, "unexpected pattern in function argument"),
+                },
+                syn::FnArg::Receiver(receiver) => {
+                    errors.push_spanned(receiver, "expected named argument")
                 }
             }
         }
 
-        args
+        let attrs = item_fn
+            .attrs
+            .iter()
+            .filter(|attr| attr.path.is_ident(keys::INJECT));
+
+        for attr in attrs {
+            set_inject_attribute(attr, &mut args, &declared_types, &mut errors);
+        }
+
+        errors.finish()?;
+        Ok(args)
+    }
+
+    // Emits the `dilib::DependencyInfo` of this argument
+    pub fn emit_info(&self) -> TokenStream {
+        let ty = &self.ty;
+        let info = match (self.handle, self.scope) {
+            // Factories always create scoped values
+            (Some(Handle::Factory), _) => quote! { dilib::DependencyInfo::scoped::<#ty>() },
+            (Some(Handle::Lazy), Scope::Singleton) => {
+                quote! { dilib::DependencyInfo::singleton::<#ty>() }
+            }
+            (Some(Handle::Lazy), Scope::ScopedSingleton) => {
+                quote! { dilib::DependencyInfo::scoped_singleton::<#ty>() }
+            }
+            (None, Scope::Singleton) => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { dilib::DependencyInfo::singleton::<#inner>() }
+            }
+            (None, Scope::ScopedSingleton) => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { dilib::DependencyInfo::scoped_singleton::<#inner>() }
+            }
+            (_, Scope::Scoped) => quote! { dilib::DependencyInfo::scoped::<#ty>() },
+        };
+
+        let info = match &self.name {
+            Some(name) => quote! { #info.with_name(#name) },
+            None => info,
+        };
+
+        // A `Lazy` resolves a provider of any kind
+        let info = match self.handle {
+            Some(Handle::Lazy) => quote! { #info.with_deferred().with_any_kind() },
+            Some(Handle::Factory) => quote! { #info.with_deferred() },
+            None => info,
+        };
+
+        // Optional arguments are not required to be registered
+        if self.optional {
+            quote! { #info.with_default() }
+        } else {
+            info
+        }
     }
 }
 
-impl ToTokens for ResolvedFnArg {
-    fn to_tokens(&self, tokens: &mut TokenStream) {
-        let ResolvedFnArg { name, arg_name, ty } = self;
+impl ResolvedFnArg {
+    // Emits the argument resolved awaiting the async providers,
+    // scoped singletons are always resolved synchronously
+    pub fn emit_async(&self) -> TokenStream {
+        self.emit(true)
+    }
 
-        let get_provider = match (is_singleton(ty), name) {
-            (true, Some(name)) => {
+    fn emit(&self, asynchronous: bool) -> TokenStream {
+        let ResolvedFnArg {
+            name,
+            arg_name,
+            ty,
+            scope,
+            handle,
+            optional,
+        } = self;
+
+        let get_provider = match (scope, name) {
+            // Handles are created without resolving the value
+            _ if handle.is_some() => {
+                let handle = match handle {
+                    Some(Handle::Lazy) => "lazy",
+                    _ => "factory",
+                };
+
+                match name {
+                    Some(name) => {
+                        let method = quote::format_ident!("try_get_{}_with_name", handle);
+                        quote! { #method::<#ty>(#name) }
+                    }
+                    None => {
+                        let method = quote::format_ident!("try_get_{}", handle);
+                        quote! { #method::<#ty>() }
+                    }
+                }
+            }
+            (Scope::Singleton, Some(name)) if asynchronous => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { try_get_singleton_async_with_name::<#inner>(#name).await }
+            }
+            (Scope::Singleton, None) if asynchronous => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { try_get_singleton_async::<#inner>().await }
+            }
+            (Scope::Scoped, Some(name)) if asynchronous => {
+                quote! { try_get_scoped_async_with_name::<#ty>(#name).await }
+            }
+            (Scope::Scoped, None) if asynchronous => quote! { try_get_scoped_async::<#ty>().await },
+            (Scope::Singleton, Some(name)) => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { try_get_singleton_with_name::<#inner>(#name) }
+            }
+            (Scope::Singleton, None) => {
+                let inner = get_singleton_inner_type(ty);
+                quote! { try_get_singleton::<#inner>() }
+            }
+            (Scope::ScopedSingleton, Some(name)) => {
                 let inner = get_singleton_inner_type(ty);
-                quote! { get_singleton_with_name::<#inner>(#name) }
+                quote! { try_get_scoped_singleton_with_name::<#inner>(#name) }
             }
-            (true, None) => {
+            (Scope::ScopedSingleton, None) => {
                 let inner = get_singleton_inner_type(ty);
-                quote! { get_singleton::<#inner>() }
+                quote! { try_get_scoped_singleton::<#inner>() }
             }
-            (false, Some(name)) => quote! { get_scoped_with_name::<#ty>(#name) },
-            (false, None) => quote! { get_scoped::<#ty>() },
+            (Scope::Scoped, Some(name)) => quote! { try_get_scoped_with_name::<#ty>(#name) },
+            (Scope::Scoped, None) => quote! { try_get_scoped::<#ty>() },
         };
 
         let arg_name = syn::Ident::new(arg_name, proc_macro2::Span::call_site());
-        let type_name = if is_singleton(ty) {
-            format_tokens(&get_singleton_inner_type(ty))
+        let type_name = match scope {
+            _ if handle.is_some() => format_tokens(ty),
+            Scope::Scoped => format_tokens(ty),
+            _ => format_tokens(&get_singleton_inner_type(ty)),
+        };
+
+        let get_provider = quote! { container . #get_provider };
+        let get_provider = if *optional {
+            quote! { dilib::ResolveResultExt::optional(#get_provider) }
         } else {
-            format_tokens(ty)
+            get_provider
         };
 
-        let result = quote! {
-            let #arg_name = container . #get_provider .expect(concat!("failed to resolve: ", #type_name));
+        quote! {
+            let #arg_name = #get_provider .expect(concat!("failed to resolve: ", #type_name));
+        }
+    }
+}
+
+impl ToTokens for ResolvedFnArg {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        tokens.append_all(self.emit(false));
+    }
+}
+
+// Applies `#[inject(param, name="...", scope="...", optional)]` to the argument `param`
+fn set_inject_attribute(
+    attr: &Attribute,
+    args: &mut [ResolvedFnArg],
+    declared_types: &[Box<syn::Type>],
+    errors: &mut Errors,
+) {
+    let nested = match attr.parse_meta() {
+        Ok(Meta::List(list)) if list.nested.len() >= 2 => list.nested,
+        Ok(meta) => return errors.push_spanned(meta, INVALID_SIGNATURE),
+        Err(error) => return errors.push(error),
+    };
+
+    let arg_path = match nested.first() {
+        Some(NestedMeta::Meta(Meta::Path(path))) => path,
+        meta => return errors.push_spanned(meta, INVALID_SIGNATURE),
+    };
+
+    let arg_name = format_tokens(arg_path);
+    let index = match args.iter().position(|arg| arg.arg_name == arg_name) {
+        Some(index) => index,
+        None if args.is_empty() => {
+            let message = format!("unable to find '{0}' for '#[inject({0}, ...)]'", arg_name);
+            return errors.push_spanned(arg_path, message);
+        }
+        None => {
+            let arg_names = args
+                .iter()
+                .map(|arg| arg.arg_name.as_str())
+                .collect::<Vec<_>>();
+
+            let message = unknown_value_message("unable to find argument", &arg_name, &arg_names);
+            return errors.push_spanned(arg_path, message);
+        }
+    };
+
+    let resolved_arg = &mut args[index];
+
+    // `optional` is applied first because it can change the type of the argument
+    for meta in nested.iter().skip(1) {
+        let (optional, path) = match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident(keys::OPTIONAL) => (true, path),
+            NestedMeta::Meta(Meta::NameValue(x)) if x.path.is_ident(keys::OPTIONAL) => match &x.lit
+            {
+                Lit::Bool(value) => (value.value, &x.path),
+                lit => {
+                    errors.push_spanned(lit, "'optional' must be a boolean literal");
+                    continue;
+                }
+            },
+            _ => continue,
+        };
+
+        if optional && !resolved_arg.optional {
+            errors.push_spanned(
+                path,
+                format!(
+                    "'{}' must be 'Option<T>' to be injected as optional",
+                    resolved_arg.arg_name
+                ),
+            );
+        }
+
+        if !optional && resolved_arg.optional {
+            let ty = declared_types[index].clone();
+            match ResolvedFnArg::new(resolved_arg.arg_name.clone(), ty, false) {
+                Ok(arg) => *resolved_arg = arg,
+                Err(error) => errors.push(error),
+            }
+        }
+    }
+
+    let mut scope_lit = None;
+
+    for meta in nested.iter().skip(1) {
+        let (path, lit) = match meta {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident(keys::OPTIONAL) => continue,
+            NestedMeta::Meta(Meta::NameValue(x)) if x.path.is_ident(keys::OPTIONAL) => continue,
+            NestedMeta::Meta(Meta::NameValue(x)) => (&x.path, &x.lit),
+            NestedMeta::Meta(Meta::Path(path)) => {
+                let key = format_tokens(path);
+                if INJECT_KEYS.contains(&key.as_str()) {
+                    let message = format!("'{0}' requires a value: `{0}=\"...\"`", key);
+                    errors.push_spanned(path, message);
+                } else {
+                    let message = format!("invalid #[{}] key", keys::INJECT);
+                    errors.push_spanned(path, unknown_value_message(&message, &key, INJECT_KEYS));
+                }
+                continue;
+            }
+            _ => {
+                errors.push_spanned(meta, INVALID_SIGNATURE);
+                continue;
+            }
         };
 
-        tokens.append_all(result);
+        let key = format_tokens(path);
+        match key.as_str() {
+            keys::NAME => match lit {
+                Lit::Str(lit_str) => resolved_arg.name = Some(lit_str.value()),
+                _ => errors.push_spanned(lit, "'name' must be a string literal"),
+            },
+            keys::SCOPE => match Scope::from_lit(lit) {
+                Ok(scope) => {
+                    resolved_arg.scope = scope;
+                    scope_lit = Some(lit);
+                }
+                Err(error) => errors.push(error),
+            },
+            _ => {
+                let message = format!("invalid #[{}] key", keys::INJECT);
+                errors.push_spanned(path, unknown_value_message(&message, &key, INJECT_KEYS));
+            }
+        }
+    }
+
+    if let Some(lit) = scope_lit {
+        if resolved_arg.scope != Scope::Scoped
+            && resolved_arg.handle.is_none()
+            && !is_singleton(&resolved_arg.ty)
+        {
+            errors.push_spanned(
+                lit,
+                format!(
+                    "'{}' must be 'Singleton<T>' or 'Arc<T>' to be injected as a singleton",
+                    resolved_arg.arg_name
+                ),
+            );
+        }
     }
 }
 
-fn get_singleton_inner_type(ty: &syn::Type) -> Box<syn::Type> {
+// Is `Option<T>`
+fn get_option_type(ty: &syn::Type) -> Option<Box<syn::Type>> {
     match ty {
-        syn::Type::Path(syn::TypePath { path, .. }) => {
-            let last = path.segments.last().expect("expected generic type");
-            match &last.arguments {
-                PathArguments::AngleBracketed(angle_bracketed) => {
-                    let args = angle_bracketed.args.iter().collect::<Vec<_>>();
+        syn::Type::Path(syn::TypePath { path, qself: None }) => {
+            let path_str = path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
 
-                    if args.len() != 1 {
-                        panic!("expected 'Singleton<T>' or 'Arc<T>' type");
-                    }
+            match path_str.as_str() {
+                "Option" | "option::Option" | "std::option::Option" | "core::option::Option" => {
+                    get_generic_type(ty)
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
 
-                    match args.first().unwrap() {
-                        syn::GenericArgument::Type(ty) => Box::new(ty.clone()),
-                        _ => panic!("expected generic type"),
-                    }
+// The types are validated when the argument is created, so `T` is always found
+fn get_singleton_inner_type(ty: &syn::Type) -> Box<syn::Type> {
+    get_generic_type(ty).unwrap_or_else(|| Box::new(ty.clone()))
+}
+
+// Returns `T` of a type with a single generic argument, like `Arc<T>`
+fn get_generic_type(ty: &syn::Type) -> Option<Box<syn::Type>> {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, .. }) => match &path.segments.last()?.arguments {
+            PathArguments::AngleBracketed(angle_bracketed) if angle_bracketed.args.len() == 1 => {
+                match angle_bracketed.args.first()? {
+                    syn::GenericArgument::Type(ty) => Some(Box::new(ty.clone())),
+                    _ => None,
                 }
-                _ => panic!("expected generic type"),
             }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+// Is `Lazy<T>` or `Factory<T>`
+fn get_handle_type(ty: &syn::Type) -> Option<(Box<syn::Type>, Handle)> {
+    match ty {
+        syn::Type::Path(syn::TypePath { path, qself: None }) => {
+            let path_str = path
+                .segments
+                .iter()
+                .map(|segment| segment.ident.to_string())
+                .collect::<Vec<_>>()
+                .join("::");
+
+            let handle = match path_str.as_str() {
+                "Lazy" | "dilib::Lazy" => Handle::Lazy,
+                "Factory" | "dilib::Factory" => Handle::Factory,
+                _ => return None,
+            };
+
+            Some((get_generic_type(ty)?, handle))
         }
-        _ => panic!("expected 'Singleton<T>' or 'Ar<T>' type"),
+        _ => None,
     }
 }
 
@@ -165,7 +473,8 @@ fn is_singleton(ty: &syn::Type) -> bool {
     }
 }
 
-#[cfg(feature = "when macro attribute in fn args get stabilized")]
+// Enable when macro attribute in fn args get stabilized
+#[cfg(any())]
 fn get_inject_name(attr: &[Attribute]) -> Option<String> {
     let attributes = attr
         .iter()
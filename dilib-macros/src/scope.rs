@@ -1,15 +1,38 @@
-#[derive(Debug, Clone, Copy)]
+use crate::error::unknown_value_message;
+use syn::Lit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Scope {
     Singleton,
+    ScopedSingleton,
     Scoped,
 }
 
 impl Scope {
-    pub fn from_str(s: &str) -> Self {
-        match s {
-            "singleton" => Scope::Singleton,
-            "scoped" => Scope::Scoped,
-            _ => panic!("Invalid scope: '{}', expected 'singleton' or 'scoped'", s),
+    // Parses `scope="..."`, the error points to the literal
+    pub fn from_lit(lit: &Lit) -> syn::Result<Self> {
+        let value = match lit {
+            Lit::Str(lit_str) => lit_str.value(),
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "expected string literal for 'scope': `scope=\"...\"`",
+                ))
+            }
+        };
+
+        match value.as_str() {
+            "singleton" => Ok(Scope::Singleton),
+            "scoped_singleton" => Ok(Scope::ScopedSingleton),
+            "scoped" => Ok(Scope::Scoped),
+            _ => Err(syn::Error::new_spanned(
+                lit,
+                unknown_value_message(
+                    "invalid scope",
+                    &value,
+                    &["singleton", "scoped_singleton", "scoped"],
+                ),
+            )),
         }
     }
 }
@@ -1,18 +1,132 @@
+use crate::dispose::DisposeHook;
 use crate::provider::Provider;
+use crate::resolution::ResolvingGuard;
 use crate::scoped::Scoped;
-use crate::{Inject, InjectionKey, Resolved, Shared};
+use crate::{
+    AsyncDispose, DependencyGraph, Dispose, Factory, Inject, InjectionKey, ProviderKind,
+    ResolveError, Resolved, Shared, TryInject, ValidationError,
+};
 use std::any::TypeId;
 use std::collections::hash_map::{Iter, Values};
-use std::collections::HashMap;
-use std::sync::Arc;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use std::sync::{Arc, Mutex, MutexGuard, RwLock};
+#[cfg(feature = "async")]
+use {crate::resolution::ResolvingFuture, crate::AsyncInject, std::future::Future};
+
+#[cfg(feature = "lazy")]
+use crate::Lazy;
 
 /// A convenient singleton type.
 pub type Singleton<T> = Arc<T>;
 
+/// What a [`Container`] does when a provider is added for a key that already have a provider.
+///
+/// The policy is used by all the `add_*` methods, the `replace_*` and `try_add_*` methods
+/// always replace or keep the existing provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DuplicatePolicy {
+    /// The provider is not added and is returned as an error, this is the default.
+    Error,
+    /// The existing provider is replaced.
+    Replace,
+    /// The existing provider is kept and the new one is discarded.
+    KeepFirst,
+}
+
+impl Default for DuplicatePolicy {
+    fn default() -> Self {
+        DuplicatePolicy::Error
+    }
+}
+
+type ScopedInstance = Arc<dyn std::any::Any + Send + Sync>;
+type ScopedInstances = HashMap<InjectionKey<'static>, Arc<ScopedCell>>;
+
+// The instance of a scoped singleton, constructed at most once by scope
+#[derive(Default)]
+struct ScopedCell {
+    value: RwLock<Option<ScopedInstance>>,
+    init: Mutex<()>,
+}
+
+impl ScopedCell {
+    fn get(&self) -> Option<ScopedInstance> {
+        self.value
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    fn get_or_try_init<F>(&self, f: F) -> Result<Option<ScopedInstance>, ResolveError>
+    where
+        F: FnOnce() -> Result<Option<ScopedInstance>, ResolveError>,
+    {
+        if let Some(value) = self.get() {
+            return Ok(Some(value));
+        }
+
+        // Only one resolver constructs the value, the others wait for it.
+        // A constructor that panics leaves the cell empty, so the lock is never left in an invalid state
+        let _init = self.init.lock().unwrap_or_else(|err| err.into_inner());
+        if let Some(value) = self.get() {
+            return Ok(Some(value));
+        }
+
+        let value = f()?;
+        *self.value.write().unwrap_or_else(|err| err.into_inner()) = value.clone();
+        Ok(value)
+    }
+}
+
+impl Clone for ScopedCell {
+    fn clone(&self) -> Self {
+        ScopedCell {
+            value: RwLock::new(self.get()),
+            init: Mutex::new(()),
+        }
+    }
+}
+
+// Shared by all the containers, so the registrations of a parent are always before the ones of its scopes
+static NEXT_SEQUENCE: AtomicU64 = AtomicU64::new(0);
+
+// The position of a provider in `Container::get_all`
+#[derive(Debug, Clone, Copy)]
+struct Order {
+    priority: i32,
+    sequence: u64,
+}
+
+impl Order {
+    fn new(priority: i32) -> Self {
+        Order {
+            priority,
+            sequence: NEXT_SEQUENCE.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    // Higher priority first, then in registration order
+    fn sort_key(&self) -> (std::cmp::Reverse<i32>, u64) {
+        (std::cmp::Reverse(self.priority), self.sequence)
+    }
+}
+
 /// Represents a store to register and retrieve objects.
-#[derive(Default, Clone)]
+#[derive(Default)]
 pub struct Container<'a> {
     providers: HashMap<InjectionKey<'a>, Provider<'a>>,
+    multi_providers: HashMap<InjectionKey<'a>, Vec<(Order, Provider<'a>)>>,
+    orders: HashMap<InjectionKey<'a>, Order>,
+    duplicate_policy: DuplicatePolicy,
+    parent: Option<&'a Container<'a>>,
+    scoped_instances: Arc<Mutex<ScopedInstances>>,
+    dispose_hooks: HashMap<InjectionKey<'a>, DisposeHook>,
+    is_shutdown: AtomicBool,
+    detached: Mutex<Option<Arc<Container<'static>>>>,
+    #[cfg(feature = "testing")]
+    recorder: Option<Arc<crate::testing::Recorder>>,
 }
 
 impl<'a> Container<'a> {
@@ -20,16 +134,76 @@ impl<'a> Container<'a> {
     pub fn new() -> Self {
         Container {
             providers: Default::default(),
+            multi_providers: Default::default(),
+            orders: Default::default(),
+            duplicate_policy: Default::default(),
+            parent: None,
+            scoped_instances: Default::default(),
+            dispose_hooks: Default::default(),
+            is_shutdown: Default::default(),
+            detached: Default::default(),
+            #[cfg(feature = "testing")]
+            recorder: None,
+        }
+    }
+
+    /// Creates a child `Container` that resolves the providers of this container
+    /// when it does not have a provider for the requested type.
+    ///
+    /// The child can register its own providers, which take precedence over the providers of the parent,
+    /// scoped providers are constructed with the child so its dependencies can be overridden,
+    /// and each child have its own values of the scoped singletons.
+    ///
+    /// Singletons are shared with the parent and constructed with the container that registered them.
+    pub fn create_scope(&self) -> Container<'_> {
+        Container {
+            providers: Default::default(),
+            multi_providers: Default::default(),
+            orders: Default::default(),
+            duplicate_policy: self.duplicate_policy,
+            parent: Some(self),
+            scoped_instances: Default::default(),
+            dispose_hooks: Default::default(),
+            is_shutdown: Default::default(),
+            detached: Default::default(),
+            #[cfg(feature = "testing")]
+            recorder: self.recorder.clone(),
         }
     }
 
+    /// Constructs a new `Container` with the given [`DuplicatePolicy`].
+    pub fn with_duplicate_policy(policy: DuplicatePolicy) -> Self {
+        let mut container = Container::new();
+        container.set_duplicate_policy(policy);
+        container
+    }
+
+    /// Returns the [`DuplicatePolicy`] of this container.
+    #[inline]
+    pub fn duplicate_policy(&self) -> DuplicatePolicy {
+        self.duplicate_policy
+    }
+
+    /// Sets the [`DuplicatePolicy`] used when adding a provider for a key that already have one,
+    /// the scopes created after this call use the same policy.
+    #[inline]
+    pub fn set_duplicate_policy(&mut self, policy: DuplicatePolicy) {
+        self.duplicate_policy = policy;
+    }
+
+    /// Returns the `Container` this scope was created from, if any.
+    #[inline]
+    pub fn parent(&self) -> Option<&Container<'a>> {
+        self.parent
+    }
+
     /// Adds a scoped factory function.
     ///
     /// # Returns
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_scoped<T, F>(&mut self, f: F) -> Result<(), Provider>
+    pub fn add_scoped<T, F>(&mut self, f: F) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: Fn() -> T + Send + Sync + 'static,
@@ -43,7 +217,7 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_scoped_with_name<T, F>(&mut self, name: &str, f: F) -> Result<(), Provider>
+    pub fn add_scoped_with_name<T, F>(&mut self, name: &str, f: F) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: Fn() -> T + Send + Sync + 'static,
@@ -57,7 +231,7 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_singleton<T>(&mut self, value: T) -> Result<(), Provider>
+    pub fn add_singleton<T>(&mut self, value: T) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
     {
@@ -70,7 +244,7 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_singleton_with_name<T>(&mut self, name: &str, value: T) -> Result<(), Provider>
+    pub fn add_singleton_with_name<T>(&mut self, name: &str, value: T) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
     {
@@ -79,7 +253,7 @@ impl<'a> Container<'a> {
 
     #[inline]
     #[cfg(feature = "lazy")]
-    pub fn add_lazy_singleton<T, F>(&mut self, f: F) -> Result<(), Provider>
+    pub fn add_lazy_singleton<T, F>(&mut self, f: F) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: FnOnce(&Container) -> T + Send + Sync + 'static,
@@ -89,7 +263,11 @@ impl<'a> Container<'a> {
 
     #[inline]
     #[cfg(feature = "lazy")]
-    pub fn add_lazy_singleton_with_name<T, F>(&mut self, name: &str, f: F) -> Result<(), Provider>
+    pub fn add_lazy_singleton_with_name<T, F>(
+        &mut self,
+        name: &str,
+        f: F,
+    ) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: FnOnce(&Container) -> T + Send + Sync + 'static,
@@ -97,17 +275,80 @@ impl<'a> Container<'a> {
         self.add_singleton_internal::<T>(Some(name), Shared::new_lazy(f))
     }
 
+    /// Adds a singleton for each scope, created with the given function
+    /// the first time is requested from a scope.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_scoped_singleton<T, F>(&mut self, f: F) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> T + Send + Sync + 'static,
+    {
+        self.add_scoped_singleton_internal::<T>(Scoped::from_construct(f), None)
+    }
+
+    /// Adds a singleton with a name for each scope, created with the given function
+    /// the first time is requested from a scope.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_scoped_singleton_with_name<T, F>(
+        &mut self,
+        name: &str,
+        f: F,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> T + Send + Sync + 'static,
+    {
+        self.add_scoped_singleton_internal::<T>(Scoped::from_construct(f), Some(name))
+    }
+
+    /// Adds an `Inject` that is constructed once for each scope.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_scoped_singleton_deps<T>(&mut self) -> Result<(), Provider<'a>>
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_scoped_singleton_internal::<T>(scoped, None)
+    }
+
+    /// Adds a named `Inject` that is constructed once for each scope.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_scoped_singleton_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider<'a>>
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_scoped_singleton_internal::<T>(scoped, Some(name))
+    }
+
     /// Adds a scoped `Inject` that depends on others providers.
     ///
     /// # Returns
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_deps<T>(&mut self) -> Result<(), Provider>
+    pub fn add_deps<T>(&mut self) -> Result<(), Provider<'a>>
     where
         T: Inject + Send + Sync + 'static,
     {
-        self.add_scoped_internal::<T>(Scoped::from_construct(T::inject), None)
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, None)
     }
 
     /// Adds a scoped named `Inject` that depends on others providers.
@@ -116,11 +357,44 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider>
+    pub fn add_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider<'a>>
     where
         T: Inject + Send + Sync + 'static,
     {
-        self.add_scoped_internal::<T>(Scoped::from_construct(T::inject), Some(name))
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, Some(name))
+    }
+
+    /// Adds a scoped `TryInject` that depends on others providers.
+    ///
+    /// If the construction fails the error is returned by the `try_get` methods.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_try_deps<T>(&mut self) -> Result<(), Provider<'a>>
+    where
+        T: TryInject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_try_construct(T::try_inject).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, None)
+    }
+
+    /// Adds a scoped named `TryInject` that depends on others providers.
+    ///
+    /// If the construction fails the error is returned by the `try_get` methods.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    pub fn add_try_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider<'a>>
+    where
+        T: TryInject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_try_construct(T::try_inject).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, Some(name))
     }
 
     /// Adds a scoped `Inject` that depends on others providers.
@@ -129,7 +403,7 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_deps_fn<T, F>(&mut self, f: F) -> Result<(), Provider>
+    pub fn add_deps_fn<T, F>(&mut self, f: F) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: Fn(&Container) -> T + Send + Sync + 'static,
@@ -143,7 +417,7 @@ impl<'a> Container<'a> {
     /// `Ok(())` if the provider was added, or `Err(Provider)` if
     /// there is a provider registered for that type.
     #[inline]
-    pub fn add_deps_fn_with_name<T, F>(&mut self, name: &str, f: F) -> Result<(), Provider>
+    pub fn add_deps_fn_with_name<T, F>(&mut self, name: &str, f: F) -> Result<(), Provider<'a>>
     where
         T: Send + Sync + 'static,
         F: Fn(&Container) -> T + Send + Sync + 'static,
@@ -151,608 +425,2843 @@ impl<'a> Container<'a> {
         self.add_scoped_internal::<T>(Scoped::from_construct(f), Some(name))
     }
 
-    /// Returns a value registered for the given type or `None`
-    /// if no provider is register for the given type.
+    /// Replaces the provider of the type with a scoped factory function,
+    /// or adds it if there is no provider for the type.
     ///
-    /// The returning value could be either scoped or a singleton.
+    /// # Returns
+    /// The previous provider for the type, if any.
     #[inline]
-    pub fn get<T>(&self) -> Option<Resolved<T>>
+    pub fn replace_scoped<T, F>(&mut self, f: F) -> Option<Provider<'a>>
     where
         T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
     {
-        self.get_internal::<T>(None)
+        let key = InjectionKey::of::<T>();
+        self.replace_provider_internal(key, Provider::Scoped(Scoped::from_factory(f)))
     }
 
-    /// Returns a value registered for the given type and name or `None`
-    /// if no provider is register for the given type.
+    /// Replaces the provider of the type and name with a scoped factory function,
+    /// or adds it if there is no provider for the type and name.
     ///
-    /// The returning value could be either scoped or a singleton.
+    /// # Returns
+    /// The previous provider for the type and name, if any.
     #[inline]
-    pub fn get_with_name<T>(&self, name: &str) -> Option<Resolved<T>>
+    pub fn replace_scoped_with_name<T, F>(&mut self, name: &str, f: F) -> Option<Provider<'a>>
     where
         T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
     {
-        self.get_internal::<T>(Some(name))
+        let key = InjectionKey::with_name::<T>(name);
+        self.replace_provider_internal(key, Provider::Scoped(Scoped::from_factory(f)))
     }
 
-    /// Returns all the values registered for the given type.
-    pub fn get_all<T>(&self) -> Vec<Resolved<T>>
+    /// Replaces the provider of the type with a singleton,
+    /// or adds it if there is no provider for the type.
+    ///
+    /// # Returns
+    /// The previous provider for the type, if any.
+    #[inline]
+    pub fn replace_singleton<T>(&mut self, value: T) -> Option<Provider<'a>>
     where
         T: Send + Sync + 'static,
     {
-        let type_id = TypeId::of::<T>();
-        self.iter()
-            .filter(|(key, _)| key.type_id() == type_id)
-            .filter_map(|(_, provider)| self.get_resolver_for(provider))
-            .collect()
+        let key = InjectionKey::of::<T>();
+        self.replace_provider_internal(key, Provider::Singleton(Shared::new(value)))
     }
 
-    /// Returns a value registered for the given type, or `None`
-    /// if no provider is register for the given type.
+    /// Replaces the provider of the type and name with a singleton,
+    /// or adds it if there is no provider for the type and name.
+    ///
+    /// # Returns
+    /// The previous provider for the type and name, if any.
     #[inline]
-    pub fn get_scoped<T>(&self) -> Option<T>
+    pub fn replace_singleton_with_name<T>(&mut self, name: &str, value: T) -> Option<Provider<'a>>
     where
         T: Send + Sync + 'static,
     {
-        self.get_internal::<T>(None).and_then(|r| r.into_scoped())
+        let key = InjectionKey::with_name::<T>(name);
+        self.replace_provider_internal(key, Provider::Singleton(Shared::new(value)))
     }
 
-    /// Returns a value registered for the given type and name, or `None`
-    /// if no provider is register for the given type and name.
+    /// Replaces the provider of the type with a scoped `Inject`,
+    /// or adds it if there is no provider for the type.
+    ///
+    /// # Returns
+    /// The previous provider for the type, if any.
     #[inline]
-    pub fn get_scoped_with_name<T>(&self, name: &str) -> Option<T>
+    pub fn replace_deps<T>(&mut self) -> Option<Provider<'a>>
     where
-        T: Send + Sync + 'static,
+        T: Inject + Send + Sync + 'static,
     {
-        self.get_internal::<T>(Some(name))
-            .and_then(|r| r.into_scoped())
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.replace_provider_internal(InjectionKey::of::<T>(), Provider::Scoped(scoped))
     }
 
-    /// Returns a singleton registered for the given type, or `None`
-    /// if no provider is register for the given type.
+    /// Replaces the provider of the type and name with a scoped `Inject`,
+    /// or adds it if there is no provider for the type and name.
+    ///
+    /// # Returns
+    /// The previous provider for the type and name, if any.
     #[inline]
-    pub fn get_singleton<T>(&self) -> Option<Singleton<T>>
+    pub fn replace_deps_with_name<T>(&mut self, name: &str) -> Option<Provider<'a>>
     where
-        T: Send + Sync + 'static,
+        T: Inject + Send + Sync + 'static,
     {
-        self.get_internal::<T>(None)
-            .and_then(|r| r.into_singleton())
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        let key = InjectionKey::with_name::<T>(name);
+        self.replace_provider_internal(key, Provider::Scoped(scoped))
     }
 
-    /// Returns a singleton registered for the given type and name, or `None`
-    /// if no provider is register for the given type and name.
+    /// Adds a scoped factory function only if there is no provider for the type.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn get_singleton_with_name<T>(&self, name: &str) -> Option<Singleton<T>>
+    pub fn try_add_scoped<T, F>(&mut self, f: F) -> bool
     where
         T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
     {
-        self.get_internal::<T>(Some(name))
-            .and_then(|r| r.into_singleton())
+        let key = InjectionKey::of::<T>();
+        self.try_add_provider_internal(key, Provider::Scoped(Scoped::from_factory(f)))
     }
 
-    /// Returns `true` if the `Container` have a provider for the given `InjectionKey`.
+    /// Adds a scoped factory function with a name only if there is no provider for the type and name.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn contains(&self, key: InjectionKey) -> bool {
-        self.providers.contains_key(&key)
+    pub fn try_add_scoped_with_name<T, F>(&mut self, name: &str, f: F) -> bool
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        let key = InjectionKey::with_name::<T>(name);
+        self.try_add_provider_internal(key, Provider::Scoped(Scoped::from_factory(f)))
     }
 
-    /// Removes the provider with the given `InjectionKey` and returns it,
-    /// or `None` if the provider is not found.
+    /// Adds a singleton only if there is no provider for the type.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn remove(&mut self, key: InjectionKey<'a>) -> Option<Provider> {
-        self.providers.remove(&key)
+    pub fn try_add_singleton<T>(&mut self, value: T) -> bool
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::of::<T>();
+        self.try_add_provider_internal(key, Provider::Singleton(Shared::new(value)))
     }
 
-    /// Returns the number of providers in this `Container`.
+    /// Adds a singleton with a name only if there is no provider for the type and name.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn len(&self) -> usize {
-        self.providers.len()
+    pub fn try_add_singleton_with_name<T>(&mut self, name: &str, value: T) -> bool
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::with_name::<T>(name);
+        self.try_add_provider_internal(key, Provider::Singleton(Shared::new(value)))
     }
 
-    /// Returns `true` is this container have no providers.
+    /// Adds a scoped `Inject` only if there is no provider for the type.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.providers.is_empty()
+    pub fn try_add_deps<T>(&mut self) -> bool
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.try_add_provider_internal(InjectionKey::of::<T>(), Provider::Scoped(scoped))
     }
 
-    /// Removes all the providers in this `Container`.
+    /// Adds a scoped `Inject` with a name only if there is no provider for the type and name.
+    ///
+    /// # Returns
+    /// `true` if the provider was added.
     #[inline]
-    pub fn clear(&mut self) {
-        self.providers.clear();
+    pub fn try_add_deps_with_name<T>(&mut self, name: &str) -> bool
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        let key = InjectionKey::with_name::<T>(name);
+        self.try_add_provider_internal(key, Provider::Scoped(scoped))
     }
 
-    /// Returns an iterator over the providers of this container.
+    /// Adds a scoped factory function to the multi-bindings of the type,
+    /// which are returned by [`Container::get_all`] in the order they were added.
+    ///
+    /// A type can have any number of multi-bindings, which are not returned by [`Container::get`].
     #[inline]
-    pub fn providers(&self) -> Values<'_, InjectionKey<'a>, Provider> {
-        self.providers.values()
+    pub fn add_scoped_multi<T, F>(&mut self, f: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.add_multi_provider::<T>(Provider::Scoped(Scoped::from_factory(f)), 0)
     }
 
-    /// Returns an iterator over the keys and providers of this container.
+    /// Adds a scoped factory function to the multi-bindings of the type with a priority,
+    /// the values with higher priority are returned first by [`Container::get_all`].
     #[inline]
-    pub fn iter(&self) -> Iter<'_, InjectionKey<'a>, Provider> {
-        self.providers.iter()
+    pub fn add_scoped_multi_with_priority<T, F>(&mut self, priority: i32, f: F)
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.add_multi_provider::<T>(Provider::Scoped(Scoped::from_factory(f)), priority)
     }
 
-    ////// Helper methods
-
-    fn add_scoped_internal<T>(&mut self, scoped: Scoped, name: Option<&str>) -> Result<(), Provider>
+    /// Adds a singleton to the multi-bindings of the type,
+    /// which are returned by [`Container::get_all`] in the order they were added.
+    ///
+    /// A type can have any number of multi-bindings, which are not returned by [`Container::get`].
+    #[inline]
+    pub fn add_singleton_multi<T>(&mut self, value: T)
     where
         T: Send + Sync + 'static,
     {
-        let name = name.map(|s| s.to_string());
-        self.add_provider::<T>(Provider::Scoped(scoped), name)
+        self.add_multi_provider::<T>(Provider::Singleton(Shared::new(value)), 0)
     }
 
-    fn add_singleton_internal<T>(
-        &mut self,
-        name: Option<&str>,
-        shared: Shared<'a>,
-    ) -> Result<(), Provider>
+    /// Adds a singleton to the multi-bindings of the type with a priority,
+    /// the values with higher priority are returned first by [`Container::get_all`].
+    #[inline]
+    pub fn add_singleton_multi_with_priority<T>(&mut self, priority: i32, value: T)
     where
         T: Send + Sync + 'static,
     {
-        let name = name.map(|s| s.to_string());
-        self.add_provider::<T>(Provider::Singleton(shared), name)
+        self.add_multi_provider::<T>(Provider::Singleton(Shared::new(value)), priority)
     }
 
-    fn get_internal<T>(&self, name: Option<&str>) -> Option<Resolved<T>>
+    /// Adds a scoped `Inject` to the multi-bindings of the type,
+    /// which are returned by [`Container::get_all`] in the order they were added.
+    #[inline]
+    pub fn add_deps_multi<T>(&mut self)
     where
-        T: Send + Sync + 'static,
+        T: Inject + Send + Sync + 'static,
     {
-        let type_id = TypeId::of::<T>();
-        let key = InjectionKey::new(type_id, name);
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_multi_provider::<T>(Provider::Scoped(scoped), 0)
+    }
 
-        if let Some(provider) = self.providers.get(&key) {
-            self.get_resolver_for(provider)
-        } else {
-            None
-        }
+    /// Adds a scoped `Inject` to the multi-bindings of the type with a priority,
+    /// the values with higher priority are returned first by [`Container::get_all`].
+    #[inline]
+    pub fn add_deps_multi_with_priority<T>(&mut self, priority: i32)
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let scoped = Scoped::from_construct(T::inject).with_dependencies(T::dependencies());
+        self.add_multi_provider::<T>(Provider::Scoped(scoped), priority)
     }
 
-    pub fn get_resolver_for<T>(&self, provider: &Provider) -> Option<Resolved<T>>
+    /// Returns a value registered for the given type or `None`
+    /// if no provider is register for the given type.
+    ///
+    /// The returning value could be either scoped or a singleton.
+    #[inline]
+    pub fn get<T>(&self) -> Option<Resolved<T>>
     where
         T: Send + Sync + 'static,
     {
-        match provider {
-            Provider::Scoped(x) => match x {
-                Scoped::Factory(_) => x.call_factory().map(|x| Resolved::Scoped(x)),
-                Scoped::Construct(_) => x.call_construct(self).map(|x| Resolved::Scoped(x)),
-            },
-            Provider::Singleton(x) => match x {
-                Shared::Instance(_) => x.get().map(Resolved::Singleton),
-
-                #[cfg(feature = "lazy")]
-                Shared::Lazy(_) => x.get_with(self).map(Resolved::Singleton),
-
-                #[cfg(not(feature = "lazy"))]
-                Shared::__NonExhaustive(_) => None,
-            },
-        }
+        self.try_get::<T>().ok()
     }
 
-    pub(crate) fn add_provider<T: 'static>(
+    /// Returns a value registered for the given type and name or `None`
+    /// if no provider is register for the given type.
+    ///
+    /// The returning value could be either scoped or a singleton.
+    #[inline]
+    pub fn get_with_name<T>(&self, name: &str) -> Option<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_with_name::<T>(name).ok()
+    }
+
+    /// Returns all the values registered for the given type, including the multi-bindings
+    /// and the ones of the parent scopes not overridden by this scope.
+    ///
+    /// The values are returned from the highest to the lowest priority, see [`Container::set_priority`],
+    /// and the values with the same priority in the order they were added,
+    /// the ones of the parent scopes first.
+    ///
+    /// The values that fail to resolve are skipped, use [`Container::try_get_all`] to get the error.
+    pub fn get_all<T>(&self) -> Vec<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        if self.is_shutdown() {
+            return Vec::new();
+        }
+
+        self.all_providers_of::<T>()
+            .into_iter()
+            .filter_map(|(scope, key, provider)| {
+                let _guard = ResolvingGuard::enter(key).ok()?;
+                self.try_resolve(scope, key, provider, None).ok()
+            })
+            .collect()
+    }
+
+    /// Returns all the values registered for the given type in the same order than [`Container::get_all`],
+    /// or the [`ResolveError`] of the first value that fails to resolve.
+    pub fn try_get_all<T>(&self) -> Result<Vec<Resolved<T>>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        // The values of a container that was shutdown may be disposed
+        if self.is_shutdown() {
+            return Err(ResolveError::shutdown::<T>(None));
+        }
+
+        self.all_providers_of::<T>()
+            .into_iter()
+            .map(|(scope, key, provider)| {
+                let _guard = ResolvingGuard::enter(key)
+                    .map_err(|cycle| ResolveError::circular_dependency::<T>(key.name(), cycle))?;
+
+                self.try_resolve(scope, key, provider, key.name())
+                    .map_err(ResolveError::into_indirect)
+            })
+            .collect()
+    }
+
+    /// Returns all the scoped values registered for the given type, see [`Container::get_all`].
+    pub fn get_all_scoped<T>(&self) -> Vec<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.get_all::<T>()
+            .into_iter()
+            .filter_map(Resolved::into_scoped)
+            .collect()
+    }
+
+    /// Returns all the scoped values registered for the given type,
+    /// or the [`ResolveError`] of the first value that fails to resolve, see [`Container::try_get_all`].
+    pub fn try_get_all_scoped<T>(&self) -> Result<Vec<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let values = self.try_get_all::<T>()?;
+        Ok(values
+            .into_iter()
+            .filter_map(Resolved::into_scoped)
+            .collect())
+    }
+
+    /// Returns all the singletons and scoped singletons registered for the given type,
+    /// see [`Container::get_all`].
+    pub fn get_all_singletons<T>(&self) -> Vec<Singleton<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.get_all::<T>()
+            .into_iter()
+            .filter_map(Resolved::into_singleton)
+            .collect()
+    }
+
+    /// Returns all the singletons and scoped singletons registered for the given type,
+    /// or the [`ResolveError`] of the first value that fails to resolve, see [`Container::try_get_all`].
+    pub fn try_get_all_singletons<T>(&self) -> Result<Vec<Singleton<T>>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let values = self.try_get_all::<T>()?;
+        Ok(values
+            .into_iter()
+            .filter_map(Resolved::into_singleton)
+            .collect())
+    }
+
+    /// Returns a value registered for the given type, or `None`
+    /// if no provider is register for the given type.
+    #[inline]
+    pub fn get_scoped<T>(&self) -> Option<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped::<T>().ok()
+    }
+
+    /// Returns a value registered for the given type and name, or `None`
+    /// if no provider is register for the given type and name.
+    #[inline]
+    pub fn get_scoped_with_name<T>(&self, name: &str) -> Option<T>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_with_name::<T>(name).ok()
+    }
+
+    /// Returns a singleton registered for the given type, or `None`
+    /// if no provider is register for the given type.
+    #[inline]
+    pub fn get_singleton<T>(&self) -> Option<Singleton<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton::<T>().ok()
+    }
+
+    /// Returns a singleton registered for the given type and name, or `None`
+    /// if no provider is register for the given type and name.
+    #[inline]
+    pub fn get_singleton_with_name<T>(&self, name: &str) -> Option<Singleton<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton_with_name::<T>(name).ok()
+    }
+
+    /// Returns the singleton of the current scope registered for the given type, or `None`
+    /// if no provider is register for the given type.
+    #[inline]
+    pub fn get_scoped_singleton<T>(&self) -> Option<Singleton<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_singleton::<T>().ok()
+    }
+
+    /// Returns the singleton of the current scope registered for the given type and name, or `None`
+    /// if no provider is register for the given type and name.
+    #[inline]
+    pub fn get_scoped_singleton_with_name<T>(&self, name: &str) -> Option<Singleton<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_singleton_with_name::<T>(name).ok()
+    }
+
+    /// Returns a value registered for the given type, or a [`ResolveError`]
+    /// if the value cannot be resolved.
+    ///
+    /// The returning value could be either scoped or a singleton.
+    #[inline]
+    pub fn try_get<T>(&self) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_internal::<T>(None, None)
+    }
+
+    /// Returns a value registered for the given type and name, or a [`ResolveError`]
+    /// if the value cannot be resolved.
+    ///
+    /// The returning value could be either scoped or a singleton.
+    #[inline]
+    pub fn try_get_with_name<T>(&self, name: &str) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_internal::<T>(Some(name), None)
+    }
+
+    /// Returns a scoped value registered for the given type, or a [`ResolveError`]
+    /// if there is no scoped provider for the given type.
+    #[inline]
+    pub fn try_get_scoped<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_internal::<T>(None)
+    }
+
+    /// Returns a scoped value registered for the given type and name, or a [`ResolveError`]
+    /// if there is no scoped provider for the given type and name.
+    #[inline]
+    pub fn try_get_scoped_with_name<T>(&self, name: &str) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_internal::<T>(Some(name))
+    }
+
+    /// Returns a singleton registered for the given type, or a [`ResolveError`]
+    /// if there is no singleton provider for the given type.
+    #[inline]
+    pub fn try_get_singleton<T>(&self) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton_internal::<T>(None)
+    }
+
+    /// Returns a singleton registered for the given type and name, or a [`ResolveError`]
+    /// if there is no singleton provider for the given type and name.
+    #[inline]
+    pub fn try_get_singleton_with_name<T>(&self, name: &str) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton_internal::<T>(Some(name))
+    }
+
+    /// Returns the singleton of the current scope registered for the given type, or a [`ResolveError`]
+    /// if there is no scoped singleton provider for the given type.
+    #[inline]
+    pub fn try_get_scoped_singleton<T>(&self) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_singleton_internal::<T>(None)
+    }
+
+    /// Returns the singleton of the current scope registered for the given type and name,
+    /// or a [`ResolveError`] if there is no scoped singleton provider for the given type and name.
+    #[inline]
+    pub fn try_get_scoped_singleton_with_name<T>(
+        &self,
+        name: &str,
+    ) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_singleton_internal::<T>(Some(name))
+    }
+
+    /// Returns a [`Factory`] that creates a new value of the scoped provider for the given type
+    /// each time is called, or `None` if there is no scoped provider for the given type.
+    #[inline]
+    pub fn get_factory<T>(&self) -> Option<Factory<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_factory_internal::<T>(None).ok()
+    }
+
+    /// Returns a [`Factory`] that creates a new value of the scoped provider for the given type and name
+    /// each time is called, or `None` if there is no scoped provider for the given type and name.
+    #[inline]
+    pub fn get_factory_with_name<T>(&self, name: &str) -> Option<Factory<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_factory_internal::<T>(Some(name)).ok()
+    }
+
+    /// Returns a [`Factory`] that creates a new value of the scoped provider for the given type
+    /// each time is called, or a [`ResolveError`] if there is no scoped provider for the given type.
+    #[inline]
+    pub fn try_get_factory<T>(&self) -> Result<Factory<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_factory_internal::<T>(None)
+    }
+
+    /// Returns a [`Factory`] that creates a new value of the scoped provider for the given type and name
+    /// each time is called, or a [`ResolveError`] if there is no scoped provider for the given type and name.
+    #[inline]
+    pub fn try_get_factory_with_name<T>(&self, name: &str) -> Result<Factory<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_factory_internal::<T>(Some(name))
+    }
+
+    /// Returns a [`Lazy`] that resolves the value of the given type the first time is dereferenced,
+    /// or `None` if there is no provider for the given type.
+    #[inline]
+    #[cfg(feature = "lazy")]
+    pub fn get_lazy<T>(&self) -> Option<Lazy<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_lazy_internal::<T>(None).ok()
+    }
+
+    /// Returns a [`Lazy`] that resolves the value of the given type and name the first time is dereferenced,
+    /// or `None` if there is no provider for the given type and name.
+    #[inline]
+    #[cfg(feature = "lazy")]
+    pub fn get_lazy_with_name<T>(&self, name: &str) -> Option<Lazy<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_lazy_internal::<T>(Some(name)).ok()
+    }
+
+    /// Returns a [`Lazy`] that resolves the value of the given type the first time is dereferenced,
+    /// or a [`ResolveError`] if there is no provider for the given type.
+    #[inline]
+    #[cfg(feature = "lazy")]
+    pub fn try_get_lazy<T>(&self) -> Result<Lazy<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_lazy_internal::<T>(None)
+    }
+
+    /// Returns a [`Lazy`] that resolves the value of the given type and name the first time is dereferenced,
+    /// or a [`ResolveError`] if there is no provider for the given type and name.
+    #[inline]
+    #[cfg(feature = "lazy")]
+    pub fn try_get_lazy_with_name<T>(&self, name: &str) -> Result<Lazy<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_lazy_internal::<T>(Some(name))
+    }
+
+    /// Adds a scoped factory function that returns a future,
+    /// the value is resolved with [`Container::get_async`].
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_scoped<T, F, Fut>(&mut self, f: F) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.add_scoped_internal::<T>(Scoped::from_async_factory(f), None)
+    }
+
+    /// Adds a scoped factory function that returns a future with a name.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_scoped_with_name<T, F, Fut>(
         &mut self,
-        provider: Provider<'a>,
-        name: Option<String>,
-    ) -> Result<(), Provider> {
-        let type_id = TypeId::of::<T>();
-        let key = InjectionKey::new(type_id, name);
-        self.add_provider_internal(key, provider)
+        name: &str,
+        f: F,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.add_scoped_internal::<T>(Scoped::from_async_factory(f), Some(name))
+    }
+
+    /// Adds a singleton created from a future the first time is resolved with [`Container::get_async`],
+    /// the future is created with the `Container` that owns the singleton.
+    ///
+    /// The value is created only once even if is requested concurrently from many tasks.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_singleton<T, F, Fut>(&mut self, f: F) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.add_singleton_internal::<T>(None, Shared::new_async(f))
     }
 
-    pub(crate) fn add_provider_internal(
-        &mut self,
-        key: InjectionKey<'a>,
-        provider: Provider<'a>,
-    ) -> Result<(), Provider> {
-        match self.providers.insert(key, provider) {
-            Some(x) => Err(x),
-            None => Ok(()),
-        }
+    /// Adds a singleton created from a future with a name.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_singleton_with_name<T, F, Fut>(
+        &mut self,
+        name: &str,
+        f: F,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = T> + Send + 'static,
+    {
+        self.add_singleton_internal::<T>(Some(name), Shared::new_async(f))
+    }
+
+    /// Adds a scoped provider for a type that implements [`AsyncInject`].
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_deps<T>(&mut self) -> Result<(), Provider<'a>>
+    where
+        T: AsyncInject + Send + Sync + 'static,
+    {
+        let scoped =
+            Scoped::from_async_construct(T::inject_async).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, None)
+    }
+
+    /// Adds a scoped provider for a type that implements [`AsyncInject`] with a name.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider<'a>>
+    where
+        T: AsyncInject + Send + Sync + 'static,
+    {
+        let scoped =
+            Scoped::from_async_construct(T::inject_async).with_dependencies(T::dependencies());
+        self.add_scoped_internal::<T>(scoped, Some(name))
+    }
+
+    /// Adds a singleton of a type that implements [`AsyncInject`],
+    /// created the first time is resolved with [`Container::get_async`].
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_singleton_deps<T>(&mut self) -> Result<(), Provider<'a>>
+    where
+        T: AsyncInject + Send + Sync + 'static,
+    {
+        let shared =
+            Shared::from_async_construct(T::inject_async).with_dependencies(T::dependencies());
+        self.add_singleton_internal::<T>(None, shared)
+    }
+
+    /// Adds a singleton of a type that implements [`AsyncInject`] with a name.
+    ///
+    /// # Returns
+    /// `Ok(())` if the provider was added, or `Err(Provider)` if
+    /// there is a provider registered for that type.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub fn add_async_singleton_deps_with_name<T>(&mut self, name: &str) -> Result<(), Provider<'a>>
+    where
+        T: AsyncInject + Send + Sync + 'static,
+    {
+        let shared =
+            Shared::from_async_construct(T::inject_async).with_dependencies(T::dependencies());
+        self.add_singleton_internal::<T>(Some(name), shared)
+    }
+
+    /// Returns a value registered for the given type, awaiting the async providers,
+    /// or `None` if no value is available.
+    ///
+    /// Providers that are not async are also resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn get_async<T>(&self) -> Option<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(None, None).await.ok()
+    }
+
+    /// Returns a value registered for the given type and name, awaiting the async providers,
+    /// or `None` if no value is available.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn get_async_with_name<T>(&self, name: &str) -> Option<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(Some(name), None)
+            .await
+            .ok()
+    }
+
+    /// Returns a value registered for the given type, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_async<T>(&self) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(None, None).await
+    }
+
+    /// Returns a value registered for the given type and name, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_async_with_name<T>(&self, name: &str) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(Some(name), None).await
+    }
+
+    /// Returns a scoped value registered for the given type, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_scoped_async<T>(&self) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_async_internal::<T>(None).await
+    }
+
+    /// Returns a scoped value registered for the given type and name, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_scoped_async_with_name<T>(&self, name: &str) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_scoped_async_internal::<T>(Some(name)).await
+    }
+
+    /// Returns a singleton registered for the given type, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_singleton_async<T>(&self) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton_async_internal::<T>(None).await
+    }
+
+    /// Returns a singleton registered for the given type and name, awaiting the async providers,
+    /// or a [`ResolveError`] if the value cannot be resolved.
+    #[inline]
+    #[cfg(feature = "async")]
+    pub async fn try_get_singleton_async_with_name<T>(
+        &self,
+        name: &str,
+    ) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_singleton_async_internal::<T>(Some(name)).await
+    }
+
+    /// Checks that the dependencies of all the providers can be resolved.
+    ///
+    /// Only the dependencies declared by the providers are checked, those are the providers
+    /// added with `add_deps`, `add_try_deps`, `#[derive(Inject)]` and `#[provide]`.
+    ///
+    /// # Returns
+    /// `Ok(())` if all the dependencies can be resolved, otherwise a [`ValidationError`]
+    /// with all the missing dependencies, scope mismatches, ambiguous names and cycles found.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        crate::validation::validate(self)
+    }
+
+    /// Returns a graph of the providers of this `Container` and the dependencies between them,
+    /// which can be rendered with [`DependencyGraph::to_dot`].
+    ///
+    /// Like [`Container::validate`] only the dependencies declared by the providers are included.
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        crate::graph::dependency_graph(self)
+    }
+
+    /// Registers a [`Dispose`] hook for the singletons of type `T`,
+    /// which is called by [`Container::shutdown`].
+    ///
+    /// The hook also applies to the scoped singletons of type `T` and to the scopes of this container.
+    #[inline]
+    pub fn add_dispose<T>(&mut self)
+    where
+        T: Dispose + Send + Sync + 'static,
+    {
+        self.add_dispose_hook::<T>(DisposeHook::from_dispose::<T>(), None);
+    }
+
+    /// Registers a [`Dispose`] hook for the singletons of type `T` with the given name.
+    #[inline]
+    pub fn add_dispose_with_name<T>(&mut self, name: &str)
+    where
+        T: Dispose + Send + Sync + 'static,
+    {
+        self.add_dispose_hook::<T>(DisposeHook::from_dispose::<T>(), Some(name));
+    }
+
+    /// Registers an [`AsyncDispose`] hook for the singletons of type `T`,
+    /// which is only called by [`Container::shutdown_async`].
+    #[inline]
+    pub fn add_async_dispose<T>(&mut self)
+    where
+        T: AsyncDispose + Send + Sync + 'static,
+    {
+        self.add_dispose_hook::<T>(DisposeHook::from_async_dispose::<T>(), None);
+    }
+
+    /// Registers an [`AsyncDispose`] hook for the singletons of type `T` with the given name.
+    #[inline]
+    pub fn add_async_dispose_with_name<T>(&mut self, name: &str)
+    where
+        T: AsyncDispose + Send + Sync + 'static,
+    {
+        self.add_dispose_hook::<T>(DisposeHook::from_async_dispose::<T>(), Some(name));
+    }
+
+    /// Calls the [`Dispose`] hooks of the singletons and scoped singletons created by this `Container`,
+    /// the values are disposed before its dependencies, and then drops the scoped singletons.
+    ///
+    /// Lazy singletons that were never initialized are not disposed, neither are the values of the parent scopes.
+    /// Only the first call disposes the values, after that the values of the container and its scopes
+    /// cannot be resolved and return [`ResolveErrorKind::Shutdown`].
+    ///
+    /// # Panics
+    /// If a value created by this container has an [`AsyncDispose`] hook,
+    /// use [`Container::shutdown_async`] instead.
+    pub fn shutdown(&self) {
+        if self.is_shutdown.load(Ordering::SeqCst) {
+            return;
+        }
+
+        // The container is not shutdown, so the async hooks can still be called with `shutdown_async`
+        let disposables = crate::dispose::disposables(self);
+        if disposables
+            .iter()
+            .any(|(hook, _)| matches!(hook, DisposeHook::Async(_)))
+        {
+            panic!("the container has values with async dispose hooks, use `Container::shutdown_async` instead");
+        }
+
+        if self.is_shutdown.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        for (hook, value) in disposables {
+            if let DisposeHook::Sync(dispose) = hook {
+                dispose(value);
+            }
+        }
+
+        self.scoped_instances().clear();
+    }
+
+    /// Like [`Container::shutdown`] but also awaits the [`AsyncDispose`] hooks.
+    pub async fn shutdown_async(&self) {
+        if self.is_shutdown.swap(true, Ordering::SeqCst) {
+            return;
+        }
+
+        for (hook, value) in crate::dispose::disposables(self) {
+            match hook {
+                DisposeHook::Sync(dispose) => dispose(value),
+                DisposeHook::Async(dispose) => dispose(value).await,
+            }
+        }
+
+        self.scoped_instances().clear();
+    }
+
+    /// Returns `true` if this `Container` or one of its parents was shutdown.
+    pub fn is_shutdown(&self) -> bool {
+        self.scopes()
+            .any(|scope| scope.is_shutdown.load(Ordering::SeqCst))
+    }
+
+    /// Returns `true` if the `Container` have a provider for the given `InjectionKey`.
+    #[inline]
+    pub fn contains(&self, key: InjectionKey) -> bool {
+        self.providers.contains_key(&key)
+    }
+
+    /// Sets the priority of the provider with the given `InjectionKey` in [`Container::get_all`],
+    /// the values with higher priority are returned first, the default priority is `0`.
+    ///
+    /// # Returns
+    /// `true` if the priority was set, or `false` if the provider is not found.
+    pub fn set_priority(&mut self, key: InjectionKey<'a>, priority: i32) -> bool {
+        match self.orders.get_mut(&key) {
+            Some(order) => {
+                order.priority = priority;
+                self.invalidate_detached();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the provider with the given `InjectionKey` and returns it,
+    /// or `None` if the provider is not found.
+    #[inline]
+    pub fn remove(&mut self, key: InjectionKey<'a>) -> Option<Provider<'a>> {
+        self.invalidate_detached();
+        self.orders.remove(&key);
+        self.providers.remove(&key)
+    }
+
+    /// Returns the number of providers in this `Container`.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    /// Returns `true` is this container have no providers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// Removes all the providers, multi-bindings and the scoped singletons in this `Container`.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.invalidate_detached();
+        self.providers.clear();
+        self.multi_providers.clear();
+        self.orders.clear();
+        self.scoped_instances().clear();
+    }
+
+    /// Returns an iterator over the providers of this container.
+    #[inline]
+    pub fn providers(&self) -> Values<'_, InjectionKey<'a>, Provider<'a>> {
+        self.providers.values()
+    }
+
+    /// Returns an iterator over the keys and providers of this container.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, InjectionKey<'a>, Provider<'a>> {
+        self.providers.iter()
+    }
+
+    ////// Helper methods
+
+    fn add_scoped_internal<T>(
+        &mut self,
+        scoped: Scoped,
+        name: Option<&str>,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let name = name.map(|s| s.to_string());
+        self.add_provider::<T>(Provider::Scoped(scoped), name)
+    }
+
+    fn add_scoped_singleton_internal<T>(
+        &mut self,
+        scoped: Scoped,
+        name: Option<&str>,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let name = name.map(|s| s.to_string());
+        self.add_provider::<T>(Provider::ScopedSingleton(scoped), name)
+    }
+
+    fn add_singleton_internal<T>(
+        &mut self,
+        name: Option<&str>,
+        shared: Shared<'a>,
+    ) -> Result<(), Provider<'a>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let name = name.map(|s| s.to_string());
+        self.add_provider::<T>(Provider::Singleton(shared), name)
+    }
+
+    fn try_get_internal<T>(
+        &self,
+        name: Option<&str>,
+        expected: Option<ProviderKind>,
+    ) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::typed::<T, _>(name);
+        let (owner, provider) = self.find_provider_of::<T>(&key, name, expected)?;
+
+        let _guard = ResolvingGuard::enter(&key)
+            .map_err(|cycle| ResolveError::circular_dependency::<T>(name, cycle))?;
+
+        self.try_resolve(owner, &key, provider, name)
+            .map_err(ResolveError::into_indirect)
+    }
+
+    // Returns the provider for the key and the container that owns it,
+    // or an error if is missing or is not of the expected kind
+    fn find_provider_of<'s, T>(
+        &'s self,
+        key: &InjectionKey<'s>,
+        name: Option<&str>,
+        expected: Option<ProviderKind>,
+    ) -> Result<(&'s Container<'s>, &'s Provider<'s>), ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        // The values of a container that was shutdown may be disposed
+        if self.is_shutdown() {
+            return Err(ResolveError::shutdown::<T>(name));
+        }
+
+        let found = self.find_provider(key);
+
+        #[cfg(feature = "testing")]
+        if let Some(recorder) = &self.recorder {
+            recorder.record(key, found.is_some());
+        }
+
+        let (owner, provider) = match found {
+            Some(found) => found,
+            None => {
+                return match name {
+                    Some(name) => Err(ResolveError::missing_dependency_with_name::<T, _>(name)),
+                    None => Err(ResolveError::missing_dependency::<T>()),
+                };
+            }
+        };
+
+        if let Some(expected) = expected {
+            let found = provider.kind();
+            if found != expected {
+                return Err(ResolveError::scope_mismatch::<T>(name, expected, found));
+            }
+        }
+
+        Ok((owner, provider))
+    }
+
+    fn try_get_scoped_internal<T>(&self, name: Option<&str>) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_internal::<T>(name, Some(ProviderKind::Scoped))
+            .and_then(|r| {
+                r.into_scoped()
+                    .ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+            })
+    }
+
+    fn try_get_singleton_internal<T>(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_internal::<T>(name, Some(ProviderKind::Singleton))
+            .and_then(|r| {
+                r.into_singleton()
+                    .ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+            })
+    }
+
+    fn try_get_scoped_singleton_internal<T>(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_internal::<T>(name, Some(ProviderKind::ScopedSingleton))
+            .and_then(|r| {
+                r.into_singleton()
+                    .ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+            })
+    }
+
+    #[cfg(feature = "async")]
+    async fn try_get_async_internal<T>(
+        &self,
+        name: Option<&str>,
+        expected: Option<ProviderKind>,
+    ) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::typed::<T, _>(name);
+        let (owner, provider) = self.find_provider_of::<T>(&key, name, expected)?;
+
+        // The resolving guard is thread local, so it cannot be held across an `await`,
+        // the futures restore the keys being resolved each time they are polled
+        let circular_dependency = |cycle| ResolveError::circular_dependency::<T>(name, cycle);
+        let resolved = match provider {
+            Provider::Scoped(x) if x.is_async() => ResolvingFuture::enter(&key, x.call_async(self))
+                .map_err(circular_dependency)?
+                .await
+                .map_err(ResolveError::into_indirect)?
+                .map(|x| Resolved::Scoped(x)),
+            Provider::Singleton(x) if x.is_async() => {
+                // Checked before waiting for the singleton, which would never be initialized
+                ResolvingFuture::enter(&key, x.get_async(owner))
+                    .map_err(circular_dependency)?
+                    .await
+                    .map(Resolved::Singleton)
+            }
+            _ => {
+                let _guard = ResolvingGuard::enter(&key).map_err(circular_dependency)?;
+                return self
+                    .try_resolve(owner, &key, provider, name)
+                    .map_err(ResolveError::into_indirect);
+            }
+        };
+
+        resolved.ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+    }
+
+    #[cfg(feature = "async")]
+    async fn try_get_scoped_async_internal<T>(&self, name: Option<&str>) -> Result<T, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(name, Some(ProviderKind::Scoped))
+            .await
+            .and_then(|r| {
+                r.into_scoped()
+                    .ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+            })
+    }
+
+    #[cfg(feature = "async")]
+    async fn try_get_singleton_async_internal<T>(
+        &self,
+        name: Option<&str>,
+    ) -> Result<Singleton<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        self.try_get_async_internal::<T>(name, Some(ProviderKind::Singleton))
+            .await
+            .and_then(|r| {
+                r.into_singleton()
+                    .ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+            })
+    }
+
+    fn try_get_factory_internal<T>(&self, name: Option<&str>) -> Result<Factory<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::typed::<T, _>(name);
+        self.find_provider_of::<T>(&key, name, Some(ProviderKind::Scoped))?;
+        Ok(Factory::new(self.detached(), name))
+    }
+
+    #[cfg(feature = "lazy")]
+    fn try_get_lazy_internal<T>(&self, name: Option<&str>) -> Result<Lazy<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = InjectionKey::typed::<T, _>(name);
+        self.find_provider_of::<T>(&key, name, None)?;
+        Ok(Lazy::new(self.detached(), name))
+    }
+
+    // Returns a copy of this container and its parents that do not borrow them, used by the handles
+    // that outlive the container. The copy shares the singletons and the scoped singletons of this scope.
+    fn detached(&self) -> Arc<Container<'static>> {
+        let mut detached = self.detached.lock().unwrap_or_else(|err| err.into_inner());
+
+        if let Some(container) = detached.as_ref() {
+            return container.clone();
+        }
+
+        let mut providers = HashMap::new();
+        let mut multi_providers = HashMap::<_, Vec<_>>::new();
+        let mut orders = HashMap::new();
+        let mut dispose_hooks = HashMap::new();
+
+        // The providers of the innermost scopes take precedence
+        for scope in self.scopes() {
+            for (key, provider) in scope.providers.iter() {
+                if providers.contains_key(key) {
+                    continue;
+                }
+
+                let key = key.clone().into_owned();
+                orders.insert(key.clone(), scope.order_of(&key));
+                providers.insert(key, provider.clone().into_static());
+            }
+
+            for (key, hook) in scope.dispose_hooks.iter() {
+                dispose_hooks
+                    .entry(key.clone().into_owned())
+                    .or_insert_with(|| hook.clone());
+            }
+        }
+
+        // The multi-bindings of the outermost scopes go first
+        let scopes = self.scopes().collect::<Vec<_>>();
+        for scope in scopes.into_iter().rev() {
+            for (key, values) in scope.multi_providers.iter() {
+                multi_providers
+                    .entry(key.clone().into_owned())
+                    .or_default()
+                    .extend(values.iter().map(|(o, p)| (*o, p.clone().into_static())));
+            }
+        }
+
+        let container = Arc::new(Container {
+            providers,
+            multi_providers,
+            orders,
+            duplicate_policy: self.duplicate_policy,
+            parent: None,
+            scoped_instances: self.scoped_instances.clone(),
+            dispose_hooks,
+            is_shutdown: Default::default(),
+            detached: Default::default(),
+            #[cfg(feature = "testing")]
+            recorder: self.recorder.clone(),
+        });
+
+        *detached = Some(container.clone());
+        container
+    }
+
+    // The detached copy is created again after the providers change
+    fn invalidate_detached(&mut self) {
+        *self
+            .detached
+            .get_mut()
+            .unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Resolves a value of `T` with a provider of this container or its parents,
+    /// for example one returned by [`Container::iter`].
+    ///
+    /// The key of the provider is searched comparing the provider with all the providers
+    /// of this scope and its parents, use [`Container::get_resolver_for_key`] if the key is known.
+    /// If the provider is not registered is resolved as unnamed, except a scoped singleton
+    /// which returns `None` because its instance is stored by key.
+    #[deprecated(
+        since = "0.2.1",
+        note = "use `get_resolver_for_key`, which doesn't need to search the key of the provider"
+    )]
+    pub fn get_resolver_for<T>(&self, provider: &Provider) -> Option<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let key = self.scopes().find_map(|scope| {
+            scope
+                .iter()
+                .find(|(_, p)| std::ptr::eq(*p, provider))
+                .map(|(key, _)| key)
+        });
+
+        match key {
+            Some(key) => self.get_resolver_for_key(key, provider),
+            None if !provider.is_scoped_singleton() => {
+                let key = InjectionKey::typed::<T, String>(None);
+                self.try_resolve(self, &key, provider, None).ok()
+            }
+            None => None,
+        }
+    }
+
+    /// Resolves a value of `T` with the provider registered with the given key,
+    /// in this container or its parents, for example the ones returned by [`Container::iter`].
+    ///
+    /// Returns `None` if the provider cannot resolve a value of `T` or fails.
+    pub fn get_resolver_for_key<T>(
+        &self,
+        key: &InjectionKey,
+        provider: &Provider,
+    ) -> Option<Resolved<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        // Singletons are resolved by the scope that registers them
+        let owner = self
+            .scopes()
+            .find(|scope| scope.providers.contains_key(key))
+            .unwrap_or(self);
+
+        self.try_resolve(owner, key, provider, None).ok()
+    }
+
+    // Singletons are resolved with the container that owns them,
+    // and other providers with the current scope
+    #[cfg_attr(not(feature = "lazy"), allow(unused_variables))]
+    fn try_resolve<T>(
+        &self,
+        owner: &Container,
+        key: &InjectionKey,
+        provider: &Provider,
+        name: Option<&str>,
+    ) -> Result<Resolved<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        let resolved = match provider {
+            Provider::Scoped(x) => match x {
+                Scoped::Factory(..) => x.call_factory().map(|x| Resolved::Scoped(x)),
+                Scoped::Construct(..) => x.call_construct(self).map(|x| Resolved::Scoped(x)),
+                Scoped::TryConstruct(..) => {
+                    x.call_try_construct(self)?.map(|x| Resolved::Scoped(x))
+                }
+                #[cfg(feature = "async")]
+                Scoped::AsyncConstruct(..) => return Err(ResolveError::async_provider::<T>(name)),
+            },
+            Provider::ScopedSingleton(x) => self
+                .get_or_create_scoped_instance(key, x)?
+                .map(Resolved::Singleton),
+            Provider::Singleton(x) => match x {
+                Shared::Instance(..) => x.get().map(Resolved::Singleton),
+
+                #[cfg(feature = "lazy")]
+                Shared::Lazy(..) => x.get_with(owner).map(Resolved::Singleton),
+
+                // Async singletons can only be resolved synchronously after being initialized
+                #[cfg(feature = "async")]
+                Shared::AsyncLazy(..) => match x.is_initialized() {
+                    Some(true) => x.get().map(Resolved::Singleton),
+                    _ => return Err(ResolveError::async_provider::<T>(name)),
+                },
+
+                Shared::__NonExhaustive(_) => None,
+            },
+        };
+
+        resolved.ok_or_else(|| ResolveError::type_mismatch::<T>(name))
+    }
+
+    fn get_or_create_scoped_instance<T>(
+        &self,
+        key: &InjectionKey,
+        scoped: &Scoped,
+    ) -> Result<Option<Arc<T>>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        // The instances are not locked while constructing the value, which may resolve other scoped singletons
+        let cell = self
+            .scoped_instances()
+            .entry(key.clone().into_owned())
+            .or_default()
+            .clone();
+
+        let instance = cell.get_or_try_init(|| {
+            let value = scoped.call::<T>(self)?;
+            Ok(value.map(|value| Arc::new(value) as ScopedInstance))
+        })?;
+
+        Ok(instance.and_then(|instance| instance.downcast().ok()))
+    }
+
+    fn scoped_instances(&self) -> MutexGuard<'_, ScopedInstances> {
+        // The instances are never left in an invalid state
+        self.scoped_instances
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+
+    pub(crate) fn has_scoped_instance(&self, key: &InjectionKey) -> bool {
+        self.scoped_instances()
+            .get(key)
+            .map_or(false, |cell| cell.get().is_some())
+    }
+
+    // Returns the singletons and scoped singletons created by this container
+    pub(crate) fn instances(
+        &self,
+    ) -> Vec<(InjectionKey<'static>, Arc<dyn std::any::Any + Send + Sync>)> {
+        let mut instances = self
+            .providers
+            .iter()
+            .filter_map(|(key, provider)| match provider {
+                Provider::Singleton(shared) => shared
+                    .instance()
+                    .map(|value| (key.clone().into_owned(), value)),
+                _ => None,
+            })
+            .collect::<Vec<_>>();
+
+        instances.extend(
+            self.scoped_instances()
+                .iter()
+                .filter_map(|(key, cell)| cell.get().map(|value| (key.clone(), value))),
+        );
+
+        instances
+    }
+
+    pub(crate) fn get_dispose_hook<'s>(
+        &'s self,
+        key: &InjectionKey<'s>,
+    ) -> Option<&'s DisposeHook> {
+        self.scopes().find_map(|scope| scope.dispose_hooks.get(key))
+    }
+
+    fn add_dispose_hook<T: 'static>(&mut self, hook: DisposeHook, name: Option<&str>) {
+        let key = InjectionKey::typed::<T, _>(name.map(|s| s.to_string()));
+        self.invalidate_detached();
+        self.dispose_hooks.insert(key, hook);
+    }
+
+    // Returns this container and its parents, from the innermost to the outermost
+    fn scopes(&self) -> impl Iterator<Item = &Container<'a>> {
+        std::iter::successors(Some(self), |scope| scope.parent)
+    }
+
+    // Returns the provider for the key and the container that owns it
+    // Returns the providers of `T` of this container and its parents in the order of `get_all`,
+    // with the container that owns them and their key
+    #[allow(clippy::type_complexity)]
+    fn all_providers_of<T>(&self) -> Vec<(&Container<'a>, &InjectionKey<'a>, &Provider<'a>)>
+    where
+        T: 'static,
+    {
+        let type_id = TypeId::of::<T>();
+        let multi_key = InjectionKey::of::<T>();
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+
+        for scope in self.scopes() {
+            for (key, provider) in scope.iter() {
+                if key.type_id() != type_id || !seen.insert(key) {
+                    continue;
+                }
+
+                entries.push((scope.order_of(key), scope, key, provider));
+            }
+
+            if let Some(providers) = scope.multi_providers.get_key_value(&multi_key) {
+                let (key, providers) = providers;
+                for (order, provider) in providers {
+                    entries.push((*order, scope, key, provider));
+                }
+            }
+        }
+
+        entries.sort_by_key(|(order, ..)| order.sort_key());
+        entries
+            .into_iter()
+            .map(|(_, scope, key, provider)| (scope, key, provider))
+            .collect()
+    }
+
+    fn find_provider<'s>(
+        &'s self,
+        key: &InjectionKey<'s>,
+    ) -> Option<(&'s Container<'s>, &'s Provider<'s>)> {
+        self.scopes()
+            .find_map(|scope| scope.providers.get(key).map(|provider| (scope, provider)))
+    }
+
+    // Records the resolution requests of this container and its scopes
+    #[cfg(feature = "testing")]
+    pub(crate) fn set_recorder(&mut self, recorder: Arc<crate::testing::Recorder>) {
+        self.recorder = Some(recorder);
+    }
+
+    pub(crate) fn get_provider<'s>(&'s self, key: &InjectionKey<'s>) -> Option<&'s Provider<'s>> {
+        self.find_provider(key).map(|(_, provider)| provider)
+    }
+
+    pub(crate) fn add_provider<T: 'static>(
+        &mut self,
+        provider: Provider<'a>,
+        name: Option<String>,
+    ) -> Result<(), Provider<'a>> {
+        let key = InjectionKey::typed::<T, _>(name);
+        self.add_provider_internal(key, provider)
+    }
+
+    fn add_multi_provider<T: 'static>(&mut self, provider: Provider<'a>, priority: i32) {
+        self.add_multi_provider_internal(InjectionKey::of::<T>(), provider, priority)
+    }
+
+    pub(crate) fn add_multi_provider_internal(
+        &mut self,
+        key: InjectionKey<'a>,
+        provider: Provider<'a>,
+        priority: i32,
+    ) {
+        self.invalidate_detached();
+        self.multi_providers
+            .entry(key)
+            .or_default()
+            .push((Order::new(priority), provider));
+    }
+
+    // Providers without order are the last ones
+    fn order_of(&self, key: &InjectionKey) -> Order {
+        self.orders.get(key).copied().unwrap_or(Order {
+            priority: 0,
+            sequence: u64::MAX,
+        })
+    }
+
+    // The container is not modified if the provider is rejected by the `DuplicatePolicy`
+    pub(crate) fn add_provider_internal(
+        &mut self,
+        key: InjectionKey<'a>,
+        provider: Provider<'a>,
+    ) -> Result<(), Provider<'a>> {
+        if !self.providers.contains_key(&key) {
+            self.replace_provider_internal(key, provider);
+            return Ok(());
+        }
+
+        match self.duplicate_policy {
+            DuplicatePolicy::Error => Err(provider),
+            DuplicatePolicy::Replace => {
+                self.replace_provider_internal(key, provider);
+                Ok(())
+            }
+            DuplicatePolicy::KeepFirst => Ok(()),
+        }
+    }
+
+    fn try_add_provider_internal(&mut self, key: InjectionKey<'a>, provider: Provider<'a>) -> bool {
+        if self.providers.contains_key(&key) {
+            return false;
+        }
+
+        self.replace_provider_internal(key, provider);
+        true
+    }
+
+    // A replaced provider keeps its position in `get_all`
+    fn replace_provider_internal(
+        &mut self,
+        key: InjectionKey<'a>,
+        provider: Provider<'a>,
+    ) -> Option<Provider<'a>> {
+        self.invalidate_detached();
+
+        // The scoped singleton of the previous provider is not longer valid
+        self.scoped_instances().remove(&key.clone().into_owned());
+
+        self.orders
+            .entry(key.clone())
+            .or_insert_with(|| Order::new(0));
+
+        self.providers.insert(key, provider)
+    }
+}
+
+impl Clone for Container<'_> {
+    fn clone(&self) -> Self {
+        Container {
+            providers: self.providers.clone(),
+            multi_providers: self.multi_providers.clone(),
+            orders: self.orders.clone(),
+            duplicate_policy: self.duplicate_policy,
+            parent: self.parent,
+            scoped_instances: Arc::new(Mutex::new(
+                self.scoped_instances()
+                    .iter()
+                    .map(|(key, cell)| (key.clone(), Arc::new(cell.as_ref().clone())))
+                    .collect(),
+            )),
+            dispose_hooks: self.dispose_hooks.clone(),
+            is_shutdown: AtomicBool::new(self.is_shutdown.load(Ordering::SeqCst)),
+            detached: Default::default(),
+            #[cfg(feature = "testing")]
+            recorder: self.recorder.clone(),
+        }
+    }
+}
+
+/// Ensure the `Container` is `Sync` and `Send`
+///
+/// ```
+/// use dilib::Container;
+///
+/// #[inline(never)]
+/// fn assert_send_sync<T: Send + Sync>() {}
+/// assert_send_sync::<Container>();
+/// ```
+#[cfg(doctest)]
+fn _dummy() {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ResolveErrorKind;
+    use std::sync::Mutex;
+
+    #[test]
+    fn scoped_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| "hello world").unwrap(); // &str
+
+        assert_eq!(container.len(), 1);
+
+        let value = container.get_scoped::<&str>().unwrap();
+        assert_eq!(value, "hello world");
+
+        assert!(container.get_scoped::<String>().is_none());
+    }
+
+    #[test]
+    fn scoped_with_name_test() {
+        let mut container = Container::new();
+        container
+            .add_scoped_with_name("greet", || "hello world")
+            .unwrap(); // &str
+
+        assert_eq!(container.len(), 1);
+
+        let value = container.get_scoped_with_name::<&str>("greet").unwrap();
+        assert_eq!(value, "hello world");
+
+        assert!(container.get_scoped_with_name::<String>("greet").is_none());
+        assert!(container.get_scoped_with_name::<&str>("saludo").is_none());
+    }
+
+    #[test]
+    fn singleton_test() {
+        let mut container = Container::new();
+        container.add_singleton(42069_i32).unwrap();
+
+        assert_eq!(container.len(), 1);
+
+        let value = container.get_singleton::<i32>().unwrap();
+
+        assert_eq!(*value, 42069_i32);
+        assert!(container.get_singleton::<i64>().is_none());
+    }
+
+    #[test]
+    fn singleton_with_name_test() {
+        let mut container = Container::new();
+        container
+            .add_singleton_with_name("funny number", 42069_i32)
+            .unwrap();
+
+        assert_eq!(container.len(), 1);
+
+        let value = container
+            .get_singleton_with_name::<i32>("funny number")
+            .unwrap();
+
+        assert_eq!(*value, 42069_i32);
+        assert!(container.get_singleton_with_name::<i32>("number").is_none());
+    }
+
+    #[test]
+    #[cfg(feature = "lazy")]
+    fn lazy_singleton_test() {
+        let mut container = Container::new();
+        container
+            .add_lazy_singleton(|_| Mutex::new(128_isize))
+            .unwrap();
+
+        let s1 = container.get_singleton::<Mutex<isize>>().unwrap();
+        assert_eq!(*s1.lock().unwrap(), 128_isize);
+
+        {
+            *s1.lock().unwrap() += 128;
+        }
+
+        let s2 = container.get_singleton::<Mutex<isize>>().unwrap();
+        assert_eq!(*s2.lock().unwrap(), 256_isize);
+    }
+
+    #[test]
+    #[cfg(feature = "lazy")]
+    fn lazy_singleton_with_name_test() {
+        let mut container = Container::new();
+        container
+            .add_lazy_singleton_with_name("bits", |_| Mutex::new(128_isize))
+            .unwrap();
+
+        let s1 = container
+            .get_singleton_with_name::<Mutex<isize>>("bits")
+            .unwrap();
+        assert_eq!(*s1.lock().unwrap(), 128_isize);
+
+        {
+            *s1.lock().unwrap() += 128;
+        }
+
+        let s2 = container
+            .get_singleton_with_name::<Mutex<isize>>("bits")
+            .unwrap();
+        assert_eq!(*s2.lock().unwrap(), 256_isize);
+    }
+
+    #[test]
+    fn add_duplicate_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| String::from("first")).unwrap();
+
+        let rejected = container.add_scoped(|| String::from("second")).unwrap_err();
+        assert!(rejected.is_scoped());
+        assert_eq!(container.get_scoped::<String>().unwrap(), "first");
+
+        assert!(container.add_singleton(String::from("third")).is_err());
+        assert_eq!(container.get_scoped::<String>().unwrap(), "first");
+    }
+
+    #[test]
+    fn replace_test() {
+        let mut container = Container::new();
+        assert!(container.replace_scoped(|| 1_i32).is_none());
+
+        let previous = container.replace_singleton(2_i32).unwrap();
+        assert!(previous.is_scoped());
+        assert_eq!(*container.get_singleton::<i32>().unwrap(), 2);
+
+        container.add_singleton_with_name("a", 3_i32).unwrap();
+        assert!(container.replace_scoped_with_name("a", || 4_i32).is_some());
+        assert_eq!(container.get_scoped_with_name::<i32>("a").unwrap(), 4);
+
+        // Keeps the position in `get_all`
+        let values = container
+            .get_all::<i32>()
+            .iter()
+            .map(|v| v.cloned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec![2, 4]);
+    }
+
+    #[test]
+    fn replace_scoped_singleton_test() {
+        let mut container = Container::with_duplicate_policy(DuplicatePolicy::Replace);
+        container.add_scoped_singleton(|_| 1_i32).unwrap();
+        assert_eq!(*container.get_scoped_singleton::<i32>().unwrap(), 1);
+
+        // The value of the previous provider is discarded
+        container.add_scoped_singleton(|_| 2_i32).unwrap();
+        assert_eq!(*container.get_scoped_singleton::<i32>().unwrap(), 2);
+    }
+
+    #[test]
+    fn try_add_test() {
+        let mut container = Container::new();
+        assert!(container.try_add_scoped(|| 1_i32));
+        assert!(!container.try_add_singleton(2_i32));
+        assert!(container.try_add_singleton_with_name("two", 2_i32));
+        assert!(!container.try_add_scoped_with_name("two", || 3_i32));
+
+        assert_eq!(container.get_scoped::<i32>().unwrap(), 1);
+        assert_eq!(*container.get_singleton_with_name::<i32>("two").unwrap(), 2);
+    }
+
+    #[test]
+    fn duplicate_policy_test() {
+        let mut container = Container::with_duplicate_policy(DuplicatePolicy::Replace);
+        container.add_scoped(|| 1_i32).unwrap();
+        container.add_scoped(|| 2_i32).unwrap();
+        assert_eq!(container.get_scoped::<i32>().unwrap(), 2);
+
+        container.set_duplicate_policy(DuplicatePolicy::KeepFirst);
+        container.add_scoped(|| 3_i32).unwrap();
+        assert_eq!(container.get_scoped::<i32>().unwrap(), 2);
+
+        let mut scope = container.create_scope();
+        assert_eq!(scope.duplicate_policy(), DuplicatePolicy::KeepFirst);
+        scope.add_scoped(|| 4_i32).unwrap();
+        scope.add_scoped(|| 5_i32).unwrap();
+        assert_eq!(scope.get_scoped::<i32>().unwrap(), 4);
+    }
+
+    #[test]
+    fn contains_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| 200_i32).unwrap();
+        container
+            .add_scoped_with_name("number", || 999_i32)
+            .unwrap();
+        container
+            .add_singleton(String::from("have a good day"))
+            .unwrap();
+        container
+            .add_singleton_with_name("bye", "adios amigo")
+            .unwrap();
+
+        assert_eq!(container.len(), 4);
+
+        assert!(container.contains(InjectionKey::of::<i32>()));
+        assert!(container.contains(InjectionKey::with_name::<i32>("number")));
+        assert!(container.contains(InjectionKey::of::<String>()));
+        assert!(container.contains(InjectionKey::with_name::<&str>("bye")));
+    }
+
+    #[test]
+    fn deps_test() {
+        struct Greeter {
+            message: String,
+            total_greets: Singleton<Mutex<usize>>,
+        }
+
+        impl Greeter {
+            fn greet(&self) -> String {
+                *self.total_greets.lock().unwrap() += 1;
+                self.message.clone()
+            }
+        }
+
+        impl Inject for Greeter {
+            fn inject(container: &Container) -> Self {
+                let message = container.get_scoped_with_name::<String>("en_msg").unwrap();
+                let total_greets = container
+                    .get_singleton_with_name::<Mutex<usize>>("counter")
+                    .unwrap();
+                Greeter {
+                    message,
+                    total_greets,
+                }
+            }
+        }
+
+        let mut container = Container::new();
+        container
+            .add_singleton_with_name("counter", Mutex::new(0_usize))
+            .unwrap();
+        container.add_deps::<Greeter>().unwrap();
+        container
+            .add_scoped_with_name("en_msg", || String::from("hello"))
+            .unwrap();
+
+        let greeter = container.get_scoped::<Greeter>().unwrap();
+        let s = greeter.greet();
+        assert_eq!(s.as_str(), "hello");
+
+        greeter.greet();
+        greeter.greet();
+
+        assert_eq!(*greeter.total_greets.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn deps_with_name_test() {
+        struct Greeter {
+            message: String,
+            total_greets: Singleton<Mutex<usize>>,
+        }
+
+        impl Greeter {
+            fn greet(&self) -> String {
+                *self.total_greets.lock().unwrap() += 1;
+                self.message.clone()
+            }
+        }
+
+        impl Inject for Greeter {
+            fn inject(container: &Container) -> Self {
+                let message = container.get_scoped_with_name::<String>("en_msg").unwrap();
+                let total_greets = container
+                    .get_singleton_with_name::<Mutex<usize>>("counter")
+                    .unwrap();
+                Greeter {
+                    message,
+                    total_greets,
+                }
+            }
+        }
+
+        let mut container = Container::new();
+        container
+            .add_singleton_with_name("counter", Mutex::new(0_usize))
+            .unwrap();
+        container
+            .add_deps_with_name::<Greeter>("en_greeter")
+            .unwrap();
+        container
+            .add_scoped_with_name("en_msg", || String::from("hello"))
+            .unwrap();
+
+        let greeter = container
+            .get_scoped_with_name::<Greeter>("en_greeter")
+            .unwrap();
+        assert!(container
+            .get_scoped_with_name::<Greeter>("es_greeter")
+            .is_none());
+
+        let s = greeter.greet();
+        assert_eq!(s.as_str(), "hello");
+
+        greeter.greet();
+        greeter.greet();
+
+        assert_eq!(*greeter.total_greets.lock().unwrap(), 3);
+    }
+
+    #[test]
+    fn remove_test() {
+        let mut container = Container::new();
+        assert_eq!(container.len(), 0);
+
+        container.add_scoped(|| true).unwrap();
+        container.add_singleton(String::from("blue")).unwrap();
+        container
+            .add_scoped_with_name("number", || 200_i32)
+            .unwrap();
+        container
+            .add_singleton_with_name("color", String::from("red"))
+            .unwrap();
+
+        assert_eq!(container.len(), 4);
+
+        assert!(container.remove(InjectionKey::of::<bool>()).is_some());
+
+        // Provider already removed
+        assert!(container.remove(InjectionKey::of::<bool>()).is_none());
+
+        assert_eq!(container.len(), 3);
+
+        // Provider is of incorrect kind
+        assert!(container.remove(InjectionKey::of::<String>()).is_some());
+
+        assert_eq!(container.len(), 2);
+
+        assert!(container
+            .remove(InjectionKey::with_name::<i32>("number"))
+            .is_some());
+        assert!(container
+            .remove(InjectionKey::with_name::<String>("color"))
+            .is_some());
+        assert_eq!(container.len(), 0);
     }
-}
 
-/// Ensure the `Container` is `Sync` and `Send`
-///
-/// ```
-/// use dilib::Container;
-///
-/// #[inline(never)]
-/// fn assert_send_sync<T: Send + Sync>() {}
-/// assert_send_sync::<Container>();
-/// ```
-#[cfg(doctest)]
-fn _dummy() {}
+    #[test]
+    fn clear_test() {
+        let mut container = Container::new();
+        assert_eq!(container.len(), 0);
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::sync::Mutex;
+        container.add_scoped(|| true).unwrap();
+        container.add_singleton(String::from("blue")).unwrap();
+
+        assert_eq!(container.len(), 2);
+
+        container.clear();
+        assert_eq!(container.len(), 0);
+    }
 
     #[test]
-    fn scoped_test() {
+    fn providers_test() {
         let mut container = Container::new();
-        container.add_scoped(|| "hello world").unwrap(); // &str
+        container.add_scoped(|| true).unwrap();
+        container.add_singleton(0.25_f32).unwrap();
+        container.add_scoped(|| 200_usize).unwrap();
 
-        assert_eq!(container.len(), 1);
+        let providers = container.providers();
+        assert_eq!(3, providers.clone().count());
 
-        let value = container.get_scoped::<&str>().unwrap();
-        assert_eq!(value, "hello world");
+        let v1 = providers
+            .clone()
+            .filter_map(|p| p.get_scoped::<bool>())
+            .last();
 
-        assert!(container.get_scoped::<String>().is_none());
+        let v2 = providers
+            .clone()
+            .filter_map(|p| p.get_singleton::<f32>())
+            .last();
+
+        let v3 = providers
+            .clone()
+            .filter_map(|p| p.get_scoped::<usize>())
+            .last();
+
+        assert_eq!(Some(true), v1);
+        assert_eq!(0.25_f32, *v2.unwrap());
+        assert_eq!(Some(200_usize), v3);
     }
 
     #[test]
-    fn scoped_with_name_test() {
+    fn iter_test() {
         let mut container = Container::new();
         container
-            .add_scoped_with_name("greet", || "hello world")
-            .unwrap(); // &str
+            .add_scoped_with_name("truthfulness", || true)
+            .unwrap();
+        container.add_singleton(2500_i32).unwrap();
 
-        assert_eq!(container.len(), 1);
+        let iter = container.iter();
+        assert_eq!(2, iter.clone().count());
 
-        let value = container.get_scoped_with_name::<&str>("greet").unwrap();
-        assert_eq!(value, "hello world");
+        let (k1, p1) = iter
+            .clone()
+            .filter(|(k, _)| k.type_id() == TypeId::of::<bool>())
+            .last()
+            .unwrap();
 
-        assert!(container.get_scoped_with_name::<String>("greet").is_none());
-        assert!(container.get_scoped_with_name::<&str>("saludo").is_none());
+        let (k2, p2) = iter
+            .clone()
+            .filter(|(k, _)| k.type_id() == TypeId::of::<i32>())
+            .last()
+            .unwrap();
+
+        assert_eq!(Some("truthfulness"), k1.name());
+        assert_eq!(TypeId::of::<bool>(), k1.type_id());
+        assert_eq!(Some(true), p1.get_scoped::<bool>());
+
+        assert_eq!(None, k2.name());
+        assert_eq!(TypeId::of::<i32>(), k2.type_id());
+        assert_eq!(2500_i32, *p2.get_singleton::<i32>().unwrap());
     }
 
     #[test]
-    fn singleton_test() {
+    fn get_test() {
         let mut container = Container::new();
-        container.add_singleton(42069_i32).unwrap();
+        container.add_scoped(|| String::from("orange")).unwrap();
+        container.add_singleton(12_usize).unwrap();
 
-        assert_eq!(container.len(), 1);
+        assert_eq!(
+            Some(&String::from("orange")),
+            container.get::<String>().as_deref()
+        );
+        assert_eq!(Some(&12_usize), container.get::<usize>().as_deref());
+    }
 
-        let value = container.get_singleton::<i32>().unwrap();
+    #[test]
+    fn get_with_name_test() {
+        let mut container = Container::new();
+        container.add_scoped_with_name("truthy", || true).unwrap();
+        container.add_singleton_with_name("falsy", false).unwrap();
 
-        assert_eq!(*value, 42069_i32);
-        assert!(container.get_singleton::<i64>().is_none());
+        assert_eq!(
+            Some(&true),
+            container.get_with_name::<bool>("truthy").as_deref()
+        );
+
+        assert_eq!(
+            Some(&false),
+            container.get_with_name::<bool>("falsy").as_deref()
+        );
     }
 
     #[test]
-    fn singleton_with_name_test() {
+    fn get_all_test() {
         let mut container = Container::new();
+        container.add_scoped(|| 69_i32).unwrap();
+        container.add_scoped_with_name("truthy", || 42_i32).unwrap();
         container
-            .add_singleton_with_name("funny number", 42069_i32)
+            .add_singleton_with_name("funny_number", 420_i32)
             .unwrap();
 
-        assert_eq!(container.len(), 1);
+        let values = container
+            .get_all::<i32>()
+            .iter()
+            .map(|v| v.cloned())
+            .collect::<Vec<_>>();
 
-        let value = container
-            .get_singleton_with_name::<i32>("funny number")
-            .unwrap();
+        // In the order they were added
+        assert_eq!(values, vec![69, 42, 420]);
+    }
 
-        assert_eq!(*value, 42069_i32);
-        assert!(container.get_singleton_with_name::<i32>("number").is_none());
+    #[test]
+    fn get_all_priority_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| 1_i32).unwrap();
+        container.add_scoped_with_name("two", || 2_i32).unwrap();
+        container.add_scoped_multi_with_priority(5, || 3_i32);
+        container.add_singleton_multi_with_priority(-1, 4_i32);
+        container.add_scoped_multi(|| 5_i32);
+
+        assert!(container.set_priority(InjectionKey::with_name::<i32>("two"), 10));
+        assert!(!container.set_priority(InjectionKey::of::<u32>(), 10));
+
+        let values = container
+            .get_all::<i32>()
+            .iter()
+            .map(|v| v.cloned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec![2, 3, 1, 5, 4]);
     }
 
     #[test]
-    #[cfg(feature = "lazy")]
-    fn lazy_singleton_test() {
+    fn get_all_scopes_order_test() {
         let mut container = Container::new();
-        container
-            .add_lazy_singleton(|_| Mutex::new(128_isize))
-            .unwrap();
+        container.add_scoped_with_name("a", || 1_i32).unwrap();
+        container.add_scoped_with_name("b", || 2_i32).unwrap();
+
+        let mut scope = container.create_scope();
+        scope.add_scoped_with_name("c", || 3_i32).unwrap();
+        scope.add_scoped_with_name("a", || 4_i32).unwrap();
+        scope.add_scoped_multi_with_priority(1, || 5_i32);
+
+        let values = scope
+            .get_all::<i32>()
+            .iter()
+            .map(|v| v.cloned())
+            .collect::<Vec<_>>();
+
+        // The parent values are before the values of the scope, except the overridden ones
+        assert_eq!(values, vec![5, 2, 3, 4]);
+    }
 
-        let s1 = container.get_singleton::<Mutex<isize>>().unwrap();
-        assert_eq!(*s1.lock().unwrap(), 128_isize);
+    #[test]
+    fn get_resolver_for_key_test() {
+        let mut container = Container::new();
+        container.add_singleton(String::from("parent")).unwrap();
 
-        {
-            *s1.lock().unwrap() += 128;
+        let mut scope = container.create_scope();
+        scope.add_scoped_singleton(|_| 42_usize).unwrap();
+
+        let (key, provider) = scope.iter().next().unwrap();
+        let first = scope.get_resolver_for_key::<usize>(key, provider).unwrap();
+        let second = scope.get_resolver_for_key::<usize>(key, provider).unwrap();
+
+        // The scoped singleton is stored by its key
+        assert!(Arc::ptr_eq(
+            &first.into_singleton().unwrap(),
+            &second.into_singleton().unwrap()
+        ));
+
+        let (key, provider) = container.iter().next().unwrap();
+        let value = scope.get_resolver_for_key::<String>(key, provider).unwrap();
+        assert_eq!(value.cloned(), "parent");
+    }
+
+    #[test]
+    fn multi_test() {
+        trait Handler: Send + Sync {
+            fn handle(&self) -> &str;
         }
 
-        let s2 = container.get_singleton::<Mutex<isize>>().unwrap();
-        assert_eq!(*s2.lock().unwrap(), 256_isize);
+        struct First;
+        impl Handler for First {
+            fn handle(&self) -> &str {
+                "first"
+            }
+        }
+
+        struct Second;
+        impl Handler for Second {
+            fn handle(&self) -> &str {
+                "second"
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_scoped_multi(|| -> Box<dyn Handler> { Box::new(First) });
+        container.add_singleton_multi::<Box<dyn Handler>>(Box::new(Second));
+        container.add_scoped_multi(|| -> Box<dyn Handler> { Box::new(First) });
+
+        // Multi-bindings are not resolved as a single value
+        assert!(container.get::<Box<dyn Handler>>().is_none());
+
+        let handlers = container
+            .get_all::<Box<dyn Handler>>()
+            .iter()
+            .map(|h| h.handle().to_owned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(handlers, vec!["first", "second", "first"]);
+        assert_eq!(container.get_all_scoped::<Box<dyn Handler>>().len(), 2);
+        assert_eq!(container.get_all_singletons::<Box<dyn Handler>>().len(), 1);
     }
 
     #[test]
-    #[cfg(feature = "lazy")]
-    fn lazy_singleton_with_name_test() {
+    fn try_get_all_error_test() {
+        struct Stage(&'static str);
+        impl TryInject for Stage {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_scoped::<u32>()?;
+                Ok(Stage("failing"))
+            }
+        }
+
         let mut container = Container::new();
+        container.add_scoped_multi(|| Stage("first"));
         container
-            .add_lazy_singleton_with_name("bits", |_| Mutex::new(128_isize))
+            .add_try_deps_with_name::<Stage>("failing")
             .unwrap();
 
-        let s1 = container
-            .get_singleton_with_name::<Mutex<isize>>("bits")
-            .unwrap();
-        assert_eq!(*s1.lock().unwrap(), 128_isize);
+        // The failing value is skipped
+        assert_eq!(container.get_all_scoped::<Stage>().len(), 1);
 
-        {
-            *s1.lock().unwrap() += 128;
+        let error = container.try_get_all_scoped::<Stage>().err().unwrap();
+        assert_eq!(ResolveErrorKind::MissingProvider, error.kind());
+        assert_eq!(Some(std::any::type_name::<u32>()), error.type_name());
+
+        container.add_scoped(|| 1_u32).unwrap();
+        let stages = container.try_get_all_scoped::<Stage>().unwrap();
+        let names = stages.iter().map(|s| s.0).collect::<Vec<_>>();
+        assert_eq!(names, ["first", "failing"]);
+    }
+
+    #[test]
+    fn try_get_all_circular_dependency_test() {
+        struct Plugin;
+        impl TryInject for Plugin {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_all::<Plugin>()?;
+                Ok(Plugin)
+            }
         }
 
-        let s2 = container
-            .get_singleton_with_name::<Mutex<isize>>("bits")
-            .unwrap();
-        assert_eq!(*s2.lock().unwrap(), 256_isize);
+        let mut container = Container::new();
+        container.add_try_deps::<Plugin>().unwrap();
+
+        assert!(container.get_all::<Plugin>().is_empty());
+
+        let error = container.try_get_all::<Plugin>().err().unwrap();
+        assert_eq!(ResolveErrorKind::CircularDependency, error.kind());
+        assert_eq!(
+            error.cycle(),
+            &[InjectionKey::of::<Plugin>(), InjectionKey::of::<Plugin>()]
+        );
     }
 
     #[test]
-    fn contains_test() {
+    fn multi_with_scopes_test() {
         let mut container = Container::new();
-        container.add_scoped(|| 200_i32).unwrap();
-        container
-            .add_scoped_with_name("number", || 999_i32)
-            .unwrap();
-        container
-            .add_singleton(String::from("have a good day"))
-            .unwrap();
-        container
-            .add_singleton_with_name("bye", "adios amigo")
-            .unwrap();
+        container.add_scoped(|| 0_i32).unwrap();
+        container.add_scoped_multi(|| 1_i32);
+        container.add_singleton_multi(2_i32);
 
-        assert_eq!(container.len(), 4);
+        let mut scope = container.create_scope();
+        scope.add_scoped_multi(|| 3_i32);
 
-        assert!(container.contains(InjectionKey::of::<i32>()));
-        assert!(container.contains(InjectionKey::with_name::<i32>("number")));
-        assert!(container.contains(InjectionKey::of::<String>()));
-        assert!(container.contains(InjectionKey::with_name::<&str>("bye")));
+        let values = scope
+            .get_all::<i32>()
+            .iter()
+            .map(|v| v.cloned())
+            .collect::<Vec<_>>();
+
+        assert_eq!(values, vec![0, 1, 2, 3]);
+        assert_eq!(container.get_all::<i32>().len(), 3);
+
+        container.clear();
+        assert!(container.get_all::<i32>().is_empty());
+    }
+
+    #[test]
+    fn multi_deps_test() {
+        struct Greeter(String);
+        impl Inject for Greeter {
+            fn inject(container: &Container) -> Self {
+                Greeter(container.get_scoped::<String>().unwrap())
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_scoped(|| String::from("hello")).unwrap();
+        container.add_deps_multi::<Greeter>();
+        container.add_deps_multi::<Greeter>();
+
+        let greeters = container.get_all_scoped::<Greeter>();
+        assert_eq!(greeters.len(), 2);
+        assert!(greeters.iter().all(|g| g.0 == "hello"));
+    }
+
+    #[test]
+    fn try_get_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| String::from("lemon")).unwrap();
+        container.add_singleton_with_name("count", 3_usize).unwrap();
+
+        assert_eq!("lemon", container.try_get::<String>().unwrap().as_str());
+        assert_eq!(
+            3_usize,
+            *container.try_get_with_name::<usize>("count").unwrap()
+        );
+
+        let error = container.try_get::<bool>().unwrap_err();
+        assert_eq!(ResolveErrorKind::MissingProvider, error.kind());
+        assert_eq!(Some(std::any::type_name::<bool>()), error.type_name());
+
+        let error = container.try_get_with_name::<usize>("total").unwrap_err();
+        assert_eq!(ResolveErrorKind::MissingProvider, error.kind());
+        assert_eq!(Some("total"), error.name());
+    }
+
+    #[test]
+    fn try_get_scoped_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| 12_i32).unwrap();
+        container.add_singleton_with_name("tax", 0.25_f32).unwrap();
+
+        assert_eq!(12_i32, container.try_get_scoped::<i32>().unwrap());
+
+        let error = container
+            .try_get_scoped_with_name::<f32>("tax")
+            .unwrap_err();
+        assert_eq!(Some(ProviderKind::Scoped), error.expected_kind());
+        assert_eq!(Some(ProviderKind::Singleton), error.found_kind());
+        assert_eq!(Some("tax"), error.name());
+    }
+
+    #[test]
+    fn try_get_singleton_test() {
+        let mut container = Container::new();
+        container.add_singleton(String::from("cherry")).unwrap();
+        container.add_scoped_with_name("id", || 7_u64).unwrap();
+
+        assert_eq!(
+            "cherry",
+            container.try_get_singleton::<String>().unwrap().as_str()
+        );
+
+        let error = container
+            .try_get_singleton_with_name::<u64>("id")
+            .unwrap_err();
+        assert_eq!(
+            ResolveErrorKind::ScopeMismatch {
+                expected: ProviderKind::Singleton,
+                found: ProviderKind::Scoped
+            },
+            error.kind()
+        );
     }
 
     #[test]
-    fn deps_test() {
-        struct Greeter {
-            message: String,
-            total_greets: Singleton<Mutex<usize>>,
-        }
+    fn try_get_dependency_chain_test() {
+        struct Engine;
+        struct Car(#[allow(dead_code)] Engine);
 
-        impl Greeter {
-            fn greet(&self) -> String {
-                *self.total_greets.lock().unwrap() += 1;
-                self.message.clone()
+        impl Inject for Car {
+            fn inject(container: &Container) -> Self {
+                let error = container.try_get_scoped::<Engine>().err().unwrap();
+                assert_eq!(error.chain(), &[InjectionKey::of::<Car>()]);
+                Car(Engine)
             }
         }
 
-        impl Inject for Greeter {
-            fn inject(container: &Container) -> Self {
-                let message = container.get_scoped_with_name::<String>("en_msg").unwrap();
-                let total_greets = container
-                    .get_singleton_with_name::<Mutex<usize>>("counter")
-                    .unwrap();
-                Greeter {
-                    message,
-                    total_greets,
-                }
+        let mut container = Container::new();
+        container.add_deps::<Car>().unwrap();
+        assert!(container.try_get_scoped::<Car>().is_ok());
+    }
+
+    #[test]
+    fn try_deps_test() {
+        struct Database;
+        struct Service {
+            #[allow(dead_code)]
+            db: Singleton<Database>,
+        }
+
+        impl TryInject for Service {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                let db = container.try_get_singleton::<Database>()?;
+                Ok(Service { db })
             }
         }
 
         let mut container = Container::new();
+        container.add_try_deps::<Service>().unwrap();
         container
-            .add_singleton_with_name("counter", Mutex::new(0_usize))
-            .unwrap();
-        container.add_deps::<Greeter>().unwrap();
-        container
-            .add_scoped_with_name("en_msg", || String::from("hello"))
+            .add_try_deps_with_name::<Service>("backup")
             .unwrap();
 
-        let greeter = container.get_scoped::<Greeter>().unwrap();
-        let s = greeter.greet();
-        assert_eq!(s.as_str(), "hello");
-
-        greeter.greet();
-        greeter.greet();
+        let error = container.try_get_scoped::<Service>().err().unwrap();
+        assert_eq!(ResolveErrorKind::MissingProvider, error.kind());
+        assert_eq!(Some(std::any::type_name::<Database>()), error.type_name());
+        assert_eq!(error.chain(), &[InjectionKey::of::<Service>()]);
+        assert!(container.get_scoped::<Service>().is_none());
 
-        assert_eq!(*greeter.total_greets.lock().unwrap(), 3);
+        container.add_singleton(Database).unwrap();
+        assert!(container.try_get_scoped::<Service>().is_ok());
+        assert!(container
+            .get_scoped_with_name::<Service>("backup")
+            .is_some());
     }
 
     #[test]
-    fn deps_with_name_test() {
-        struct Greeter {
-            message: String,
-            total_greets: Singleton<Mutex<usize>>,
+    fn try_get_circular_dependency_test() {
+        struct A;
+        struct B;
+        struct C;
+
+        impl TryInject for A {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_scoped::<B>()?;
+                Ok(A)
+            }
         }
 
-        impl Greeter {
-            fn greet(&self) -> String {
-                *self.total_greets.lock().unwrap() += 1;
-                self.message.clone()
+        impl TryInject for B {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_scoped::<C>()?;
+                Ok(B)
             }
         }
 
-        impl Inject for Greeter {
-            fn inject(container: &Container) -> Self {
-                let message = container.get_scoped_with_name::<String>("en_msg").unwrap();
-                let total_greets = container
-                    .get_singleton_with_name::<Mutex<usize>>("counter")
-                    .unwrap();
-                Greeter {
-                    message,
-                    total_greets,
-                }
+        impl TryInject for C {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_scoped::<A>()?;
+                Ok(C)
             }
         }
 
+        let mut container = Container::new();
+        container.add_try_deps::<A>().unwrap();
+        container.add_try_deps::<B>().unwrap();
+        container.add_try_deps::<C>().unwrap();
+
+        let error = container.try_get_scoped::<A>().err().unwrap();
+        assert_eq!(ResolveErrorKind::CircularDependency, error.kind());
+        assert_eq!(
+            error.cycle(),
+            &[
+                InjectionKey::of::<A>(),
+                InjectionKey::of::<B>(),
+                InjectionKey::of::<C>(),
+                InjectionKey::of::<A>()
+            ]
+        );
+        assert!(error
+            .to_string()
+            .starts_with("circular dependency detected: "));
+
+        // The resolution stack is cleared after the error
+        assert!(crate::resolution::current_chain().is_empty());
+        assert!(container.get_scoped::<B>().is_none());
+    }
+
+    #[test]
+    fn lazy_singleton_circular_dependency_test() {
+        struct Node(bool);
+
         let mut container = Container::new();
         container
-            .add_singleton_with_name("counter", Mutex::new(0_usize))
-            .unwrap();
-        container
-            .add_deps_with_name::<Greeter>("en_greeter")
+            .add_lazy_singleton(|container: &Container| {
+                let error = container.try_get_singleton::<Node>().err().unwrap();
+                assert_eq!(ResolveErrorKind::CircularDependency, error.kind());
+                Node(true)
+            })
             .unwrap();
+
+        assert!(container.get_singleton::<Node>().unwrap().0);
+    }
+
+    #[test]
+    fn create_scope_test() {
+        struct Greeter(String);
+
+        let mut container = Container::new();
+        container.add_scoped(|| String::from("hello")).unwrap();
+        container.add_singleton(42_i32).unwrap();
         container
-            .add_scoped_with_name("en_msg", || String::from("hello"))
+            .add_deps_fn(|c| Greeter(c.get_scoped::<String>().unwrap()))
             .unwrap();
 
-        let greeter = container
-            .get_scoped_with_name::<Greeter>("en_greeter")
-            .unwrap();
-        assert!(container
-            .get_scoped_with_name::<Greeter>("es_greeter")
-            .is_none());
+        let mut scope = container.create_scope();
+        scope.add_scoped(|| String::from("hola")).unwrap();
 
-        let s = greeter.greet();
-        assert_eq!(s.as_str(), "hello");
+        assert!(scope.parent().is_some());
+        assert_eq!(scope.len(), 1);
+        assert_eq!(scope.get_scoped::<String>().unwrap(), "hola");
+        assert_eq!(*scope.get_singleton::<i32>().unwrap(), 42);
 
-        greeter.greet();
-        greeter.greet();
+        // Scoped providers of the parent are constructed with the child
+        assert_eq!(scope.get_scoped::<Greeter>().unwrap().0, "hola");
+        assert_eq!(container.get_scoped::<Greeter>().unwrap().0, "hello");
 
-        assert_eq!(*greeter.total_greets.lock().unwrap(), 3);
+        assert_eq!(scope.get_all::<String>().len(), 1);
     }
 
     #[test]
-    fn remove_test() {
+    fn scoped_singleton_test() {
         let mut container = Container::new();
-        assert_eq!(container.len(), 0);
-
-        container.add_scoped(|| true).unwrap();
-        container.add_singleton(String::from("blue")).unwrap();
-        container
-            .add_scoped_with_name("number", || 200_i32)
-            .unwrap();
         container
-            .add_singleton_with_name("color", String::from("red"))
+            .add_scoped_singleton(|_| Mutex::new(0_usize))
             .unwrap();
 
-        assert_eq!(container.len(), 4);
-
-        assert!(container.remove(InjectionKey::of::<bool>()).is_some());
-
-        // Provider already removed
-        assert!(container.remove(InjectionKey::of::<bool>()).is_none());
+        let scope1 = container.create_scope();
+        let scope2 = container.create_scope();
 
-        assert_eq!(container.len(), 3);
+        let a1 = scope1.get_scoped_singleton::<Mutex<usize>>().unwrap();
+        let a2 = scope1.get_scoped_singleton::<Mutex<usize>>().unwrap();
+        let b = scope2.get_scoped_singleton::<Mutex<usize>>().unwrap();
 
-        // Provider is of incorrect kind
-        assert!(container.remove(InjectionKey::of::<String>()).is_some());
+        *a1.lock().unwrap() += 1;
 
-        assert_eq!(container.len(), 2);
+        assert!(Arc::ptr_eq(&a1, &a2));
+        assert!(!Arc::ptr_eq(&a1, &b));
+        assert_eq!(*a2.lock().unwrap(), 1);
+        assert_eq!(*b.lock().unwrap(), 0);
 
-        assert!(container
-            .remove(InjectionKey::with_name::<i32>("number"))
-            .is_some());
-        assert!(container
-            .remove(InjectionKey::with_name::<String>("color"))
-            .is_some());
-        assert_eq!(container.len(), 0);
+        let error = scope1.try_get_singleton::<Mutex<usize>>().err().unwrap();
+        assert_eq!(
+            error.kind(),
+            ResolveErrorKind::ScopeMismatch {
+                expected: ProviderKind::Singleton,
+                found: ProviderKind::ScopedSingleton
+            }
+        );
     }
 
     #[test]
-    fn clear_test() {
+    fn scoped_singleton_concurrent_test() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+
         let mut container = Container::new();
-        assert_eq!(container.len(), 0);
+        container
+            .add_scoped_singleton(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+                // Gives time to the other threads to request the value
+                std::thread::sleep(std::time::Duration::from_millis(50));
+                String::from("connection")
+            })
+            .unwrap();
 
-        container.add_scoped(|| true).unwrap();
-        container.add_singleton(String::from("blue")).unwrap();
+        let scope = container.create_scope();
+        let values = std::thread::scope(|s| {
+            let handles = (0..4)
+                .map(|_| s.spawn(|| scope.get_scoped_singleton::<String>().unwrap()))
+                .collect::<Vec<_>>();
 
-        assert_eq!(container.len(), 2);
+            handles
+                .into_iter()
+                .map(|handle| handle.join().unwrap())
+                .collect::<Vec<_>>()
+        });
 
-        container.clear();
-        assert_eq!(container.len(), 0);
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(values.iter().all(|value| Arc::ptr_eq(value, &values[0])));
     }
 
     #[test]
-    fn providers_test() {
-        let mut container = Container::new();
-        container.add_scoped(|| true).unwrap();
-        container.add_singleton(0.25_f32).unwrap();
-        container.add_scoped(|| 200_usize).unwrap();
-
-        let providers = container.providers();
-        assert_eq!(3, providers.clone().count());
-
-        let v1 = providers
-            .clone()
-            .filter_map(|p| p.get_scoped::<bool>())
-            .last();
+    fn scoped_singleton_dropped_with_scope_test() {
+        struct Connection;
 
-        let v2 = providers
-            .clone()
-            .filter_map(|p| p.get_singleton::<f32>())
-            .last();
+        let mut container = Container::new();
+        container.add_scoped_singleton(|_| Connection).unwrap();
 
-        let v3 = providers
-            .clone()
-            .filter_map(|p| p.get_scoped::<usize>())
-            .last();
+        let weak = {
+            let scope = container.create_scope();
+            let connection = scope.get_scoped_singleton::<Connection>().unwrap();
+            Arc::downgrade(&connection)
+        };
 
-        assert_eq!(Some(true), v1);
-        assert_eq!(0.25_f32, *v2.unwrap());
-        assert_eq!(Some(200_usize), v3);
+        assert!(weak.upgrade().is_none());
     }
 
     #[test]
-    fn iter_test() {
+    fn scoped_singleton_deps_with_name_test() {
+        struct Request {
+            id: usize,
+        }
+
+        impl Inject for Request {
+            fn inject(container: &Container) -> Self {
+                let id = container.get_scoped::<usize>().unwrap();
+                Request { id }
+            }
+        }
+
+        let counter = Arc::new(Mutex::new(0_usize));
         let mut container = Container::new();
         container
-            .add_scoped_with_name("truthfulness", || true)
+            .add_scoped_singleton_deps_with_name::<Request>("current")
             .unwrap();
-        container.add_singleton(2500_i32).unwrap();
-
-        let iter = container.iter();
-        assert_eq!(2, iter.clone().count());
-
-        let (k1, p1) = iter
-            .clone()
-            .filter(|(k, _)| k.type_id() == TypeId::of::<bool>())
-            .last()
+        container
+            .add_scoped(move || {
+                let mut counter = counter.lock().unwrap();
+                *counter += 1;
+                *counter
+            })
             .unwrap();
 
-        let (k2, p2) = iter
-            .clone()
-            .filter(|(k, _)| k.type_id() == TypeId::of::<i32>())
-            .last()
+        let scope = container.create_scope();
+        let r1 = scope
+            .get_scoped_singleton_with_name::<Request>("current")
+            .unwrap();
+        let r2 = scope
+            .get_scoped_singleton_with_name::<Request>("current")
+            .unwrap();
+        let r3 = container
+            .create_scope()
+            .get_scoped_singleton_with_name::<Request>("current")
             .unwrap();
 
-        assert_eq!(Some("truthfulness"), k1.name());
-        assert_eq!(TypeId::of::<bool>(), k1.type_id());
-        assert_eq!(Some(true), p1.get_scoped::<bool>());
-
-        assert_eq!(None, k2.name());
-        assert_eq!(TypeId::of::<i32>(), k2.type_id());
-        assert_eq!(2500_i32, *p2.get_singleton::<i32>().unwrap());
+        assert_eq!(r1.id, 1);
+        assert_eq!(r2.id, 1);
+        assert_eq!(r3.id, 2);
     }
 
-    #[test]
-    fn get_test() {
+    #[tokio::test]
+    async fn async_scoped_test() {
         let mut container = Container::new();
-        container.add_scoped(|| String::from("orange")).unwrap();
-        container.add_singleton(12_usize).unwrap();
+        container.add_async_scoped(|| async { 42_i32 }).unwrap();
+        container
+            .add_async_scoped_with_name("greet", || async { String::from("hello") })
+            .unwrap();
 
+        assert_eq!(container.try_get_scoped_async::<i32>().await.unwrap(), 42);
         assert_eq!(
-            Some(&String::from("orange")),
-            container.get::<String>().as_deref()
+            container
+                .get_async_with_name::<String>("greet")
+                .await
+                .unwrap()
+                .cloned(),
+            "hello"
         );
-        assert_eq!(Some(&12_usize), container.get::<usize>().as_deref());
+
+        let error = container.try_get_scoped::<i32>().unwrap_err();
+        assert_eq!(ResolveErrorKind::AsyncProvider, error.kind());
     }
 
-    #[test]
-    fn get_with_name_test() {
+    #[tokio::test]
+    async fn async_singleton_initialized_once_test() {
+        use std::sync::atomic::AtomicUsize;
+
+        let calls = Arc::new(AtomicUsize::new(0));
         let mut container = Container::new();
-        container.add_scoped_with_name("truthy", || true).unwrap();
-        container.add_singleton_with_name("falsy", false).unwrap();
+        container
+            .add_async_singleton({
+                let calls = calls.clone();
+                move |_| {
+                    let calls = calls.clone();
+                    async move {
+                        calls.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+                        String::from("pool")
+                    }
+                }
+            })
+            .unwrap();
 
-        assert_eq!(
-            Some(&true),
-            container.get_with_name::<bool>("truthy").as_deref()
-        );
+        // Not initialized yet
+        let error = container.try_get_singleton::<String>().unwrap_err();
+        assert_eq!(ResolveErrorKind::AsyncProvider, error.kind());
+
+        let container = Arc::new(container);
+        let tasks = (0..10)
+            .map(|_| {
+                let container = container.clone();
+                tokio::spawn(async move { container.try_get_singleton_async::<String>().await })
+            })
+            .collect::<Vec<_>>();
+
+        let mut values = Vec::new();
+        for task in tasks {
+            values.push(task.await.unwrap().unwrap());
+        }
 
-        assert_eq!(
-            Some(&false),
-            container.get_with_name::<bool>("falsy").as_deref()
-        );
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert!(values.iter().all(|v| Arc::ptr_eq(v, &values[0])));
+
+        // Once initialized can be resolved synchronously
+        let value = container.get_singleton::<String>().unwrap();
+        assert!(Arc::ptr_eq(&value, &values[0]));
     }
 
-    #[test]
-    fn get_all_test() {
+    #[tokio::test]
+    async fn async_deps_test() {
+        struct Client {
+            url: Singleton<String>,
+            retries: usize,
+        }
+
+        impl AsyncInject for Client {
+            fn inject_async<'c>(container: &'c Container<'c>) -> crate::InjectFuture<'c, Self> {
+                Box::pin(async move {
+                    let url = container.try_get_singleton_async::<String>().await.unwrap();
+                    let retries = container.get_scoped::<usize>().unwrap();
+                    Client { url, retries }
+                })
+            }
+        }
+
         let mut container = Container::new();
-        container.add_scoped(|| 69_i32).unwrap();
-        container.add_scoped_with_name("truthy", || 42_i32).unwrap();
         container
-            .add_singleton_with_name("funny_number", 420_i32)
+            .add_async_singleton(|_| async { String::from("http://localhost") })
+            .unwrap();
+        container.add_scoped(|| 3_usize).unwrap();
+        container.add_async_deps::<Client>().unwrap();
+        container
+            .add_async_singleton_deps_with_name::<Client>("shared")
+            .unwrap();
+
+        let client = container.try_get_scoped_async::<Client>().await.unwrap();
+        assert_eq!(client.url.as_str(), "http://localhost");
+        assert_eq!(client.retries, 3);
+
+        let c1 = container
+            .try_get_singleton_async_with_name::<Client>("shared")
+            .await
+            .unwrap();
+        let c2 = container
+            .try_get_singleton_async_with_name::<Client>("shared")
+            .await
             .unwrap();
+        assert!(Arc::ptr_eq(&c1, &c2));
+
+        // Sync providers are also resolved
+        assert_eq!(*container.get_async::<usize>().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn async_singleton_circular_dependency_test() {
+        struct A {
+            error: Option<ResolveError>,
+        }
+
+        impl AsyncInject for A {
+            fn inject_async<'c>(container: &'c Container<'c>) -> crate::InjectFuture<'c, Self> {
+                Box::pin(async move {
+                    let error = container.try_get_singleton_async::<A>().await.err();
+                    A { error }
+                })
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_async_singleton_deps::<A>().unwrap();
+
+        let a = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            container.try_get_singleton_async::<A>(),
+        )
+        .await
+        .expect("the resolution of a circular dependency should not hang")
+        .unwrap();
+
+        let error = a.error.as_ref().unwrap();
+        assert_eq!(ResolveErrorKind::CircularDependency, error.kind());
+        assert_eq!(
+            error.cycle(),
+            &[InjectionKey::of::<A>(), InjectionKey::of::<A>()]
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn async_scoped_circular_dependency_test() {
+        struct A(#[allow(dead_code)] B);
+        struct B {
+            error: Option<ResolveError>,
+        }
+
+        impl AsyncInject for A {
+            fn inject_async<'c>(container: &'c Container<'c>) -> crate::InjectFuture<'c, Self> {
+                Box::pin(async move { A(container.try_get_scoped_async::<B>().await.unwrap()) })
+            }
+        }
+
+        impl AsyncInject for B {
+            fn inject_async<'c>(container: &'c Container<'c>) -> crate::InjectFuture<'c, Self> {
+                Box::pin(async move {
+                    // The keys being resolved are kept after the task is resumed
+                    tokio::time::sleep(std::time::Duration::from_millis(1)).await;
+                    let error = container.try_get_scoped_async::<A>().await.err();
+                    B { error }
+                })
+            }
+        }
 
-        let values = container.get_all::<i32>();
-        assert_eq!(3, values.len());
+        let mut container = Container::new();
+        container.add_async_deps::<A>().unwrap();
+        container.add_async_deps::<B>().unwrap();
 
-        let x1 = values.iter().find(|v| *v.as_ref() == 69_i32).unwrap();
-        let x2 = values.iter().find(|v| *v.as_ref() == 42_i32).unwrap();
-        let x3 = values.iter().find(|v| *v.as_ref() == 420_i32).unwrap();
+        let b = container.try_get_scoped_async::<A>().await.unwrap().0;
+        let error = b.error.unwrap();
+        assert_eq!(ResolveErrorKind::CircularDependency, error.kind());
+        assert_eq!(
+            error.cycle(),
+            &[
+                InjectionKey::of::<A>(),
+                InjectionKey::of::<B>(),
+                InjectionKey::of::<A>()
+            ]
+        );
 
-        assert_eq!(69_i32, x1.cloned());
-        assert_eq!(42_i32, x2.cloned());
-        assert_eq!(420_i32, x3.cloned());
+        // The keys are only tracked while resolving
+        assert!(crate::resolution::current_chain().is_empty());
+        let b = container.try_get_scoped_async::<A>().await.unwrap().0;
+        assert_eq!(b.error.unwrap().cycle().len(), 3);
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,334 @@
+use crate::{Container, InjectionKey};
+use std::any::Any;
+use std::collections::HashSet;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// The future returned by [`AsyncDispose::dispose_async`].
+pub type DisposeFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+type BoxedAny = Arc<dyn Any + Send + Sync>;
+
+/// A hook to release the resources of a singleton when the [`Container`] is shutdown.
+///
+/// The hook is registered with [`Container::add_dispose`].
+///
+/// # Example
+/// ```
+/// use dilib::{Container, Dispose};
+/// use std::sync::atomic::{AtomicBool, Ordering};
+///
+/// struct Pool { closed: AtomicBool }
+///
+/// impl Dispose for Pool {
+///     fn dispose(&self) {
+///         self.closed.store(true, Ordering::SeqCst);
+///     }
+/// }
+///
+/// let mut container = Container::new();
+/// container.add_singleton(Pool { closed: AtomicBool::new(false) }).unwrap();
+/// container.add_dispose::<Pool>();
+///
+/// let pool = container.get_singleton::<Pool>().unwrap();
+/// container.shutdown();
+/// assert!(pool.closed.load(Ordering::SeqCst));
+/// ```
+pub trait Dispose {
+    /// Releases the resources of this value.
+    fn dispose(&self);
+}
+
+/// An async hook to release the resources of a singleton when the [`Container`] is shutdown.
+///
+/// The hook is registered with [`Container::add_async_dispose`]
+/// and only called by [`Container::shutdown_async`].
+pub trait AsyncDispose {
+    /// Releases the resources of this value.
+    fn dispose_async(&self) -> DisposeFuture<'_>;
+}
+
+/// A dispose hook of a provider.
+#[derive(Clone)]
+pub(crate) enum DisposeHook {
+    Sync(Arc<dyn Fn(BoxedAny) + Send + Sync>),
+    Async(Arc<dyn Fn(BoxedAny) -> DisposeFuture<'static> + Send + Sync>),
+}
+
+impl DisposeHook {
+    pub(crate) fn from_dispose<T>() -> Self
+    where
+        T: Dispose + Send + Sync + 'static,
+    {
+        DisposeHook::Sync(Arc::new(|value: BoxedAny| {
+            if let Ok(value) = value.downcast::<T>() {
+                value.dispose();
+            }
+        }))
+    }
+
+    pub(crate) fn from_async_dispose<T>() -> Self
+    where
+        T: AsyncDispose + Send + Sync + 'static,
+    {
+        DisposeHook::Async(Arc::new(|value: BoxedAny| -> DisposeFuture<'static> {
+            Box::pin(async move {
+                if let Ok(value) = value.downcast::<T>() {
+                    value.dispose_async().await;
+                }
+            })
+        }))
+    }
+}
+
+/// Returns the dispose hooks and the values to dispose of the container,
+/// the values that depend on others are returned first.
+pub(crate) fn disposables(container: &Container) -> Vec<(DisposeHook, BoxedAny)> {
+    let mut instances = container.instances();
+
+    // Sorted by type name to dispose the values without dependencies always in the same order
+    let mut keys = instances.iter().map(|(k, _)| k.clone()).collect::<Vec<_>>();
+    keys.sort_by(|a, b| {
+        (a.type_name(), a.name())
+            .cmp(&(b.type_name(), b.name()))
+            .then_with(|| a.cmp(b))
+    });
+
+    // Post-order, so each dependency is before the values that depend on it
+    let mut visited = HashSet::new();
+    let mut order = Vec::new();
+    for key in keys {
+        visit(container, key, &mut visited, &mut order);
+    }
+
+    order
+        .into_iter()
+        .rev()
+        .filter_map(|key| {
+            let hook = container.get_dispose_hook(&key)?;
+            let pos = instances.iter().position(|(k, _)| k == &key)?;
+            let (_, value) = instances.swap_remove(pos);
+            Some((hook.clone(), value))
+        })
+        .collect()
+}
+
+fn visit(
+    container: &Container,
+    key: InjectionKey<'static>,
+    visited: &mut HashSet<InjectionKey<'static>>,
+    order: &mut Vec<InjectionKey<'static>>,
+) {
+    if !visited.insert(key.clone()) {
+        return;
+    }
+
+    if let Some(provider) = container.get_provider(&key) {
+        for dependency in provider.dependencies() {
+            visit(container, dependency.key(), visited, order);
+        }
+    }
+
+    order.push(key);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DependencyInfo, Inject, Provider, ResolveErrorKind, Shared};
+    use std::sync::Mutex;
+
+    // Records the order in which the values are disposed
+    type Log = Arc<Mutex<Vec<&'static str>>>;
+
+    struct Database(Log);
+    struct Repository(Log);
+    struct Api(Log);
+
+    impl Dispose for Database {
+        fn dispose(&self) {
+            self.0.lock().unwrap().push("database");
+        }
+    }
+
+    impl Dispose for Repository {
+        fn dispose(&self) {
+            self.0.lock().unwrap().push("repository");
+        }
+    }
+
+    impl AsyncDispose for Api {
+        fn dispose_async(&self) -> DisposeFuture<'_> {
+            Box::pin(async move {
+                self.0.lock().unwrap().push("api");
+            })
+        }
+    }
+
+    impl Inject for Repository {
+        fn inject(container: &Container) -> Self {
+            container.get_singleton::<Database>().unwrap();
+            let log = container.get_singleton::<Log>().unwrap();
+            Repository(log.as_ref().clone())
+        }
+
+        fn dependencies() -> Vec<DependencyInfo> {
+            vec![DependencyInfo::singleton::<Database>()]
+        }
+    }
+
+    impl Inject for Api {
+        fn inject(container: &Container) -> Self {
+            container.get_singleton::<Repository>().unwrap();
+            let log = container.get_singleton::<Log>().unwrap();
+            Api(log.as_ref().clone())
+        }
+
+        fn dependencies() -> Vec<DependencyInfo> {
+            vec![DependencyInfo::singleton::<Repository>()]
+        }
+    }
+
+    fn container(log: &Log) -> Container<'static> {
+        let mut container = Container::new();
+        container.add_singleton(log.clone()).unwrap();
+        container.add_singleton(Database(log.clone())).unwrap();
+        add_lazy_singleton_deps::<Repository>(&mut container);
+        add_lazy_singleton_deps::<Api>(&mut container);
+
+        container.add_dispose::<Database>();
+        container.add_dispose::<Repository>();
+        container.add_async_dispose::<Api>();
+        container
+    }
+
+    fn add_lazy_singleton_deps<T>(container: &mut Container)
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        let shared = Shared::new_lazy(T::inject).with_dependencies(T::dependencies());
+        container
+            .add_provider::<T>(Provider::Singleton(shared), None)
+            .unwrap();
+    }
+
+    // Polls a future that never waits
+    fn block_on<F: Future>(future: F) -> F::Output {
+        use std::task::{Context, Poll, RawWaker, RawWakerVTable, Waker};
+
+        fn raw_waker() -> RawWaker {
+            fn clone(_: *const ()) -> RawWaker {
+                raw_waker()
+            }
+            fn noop(_: *const ()) {}
+            static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, noop, noop, noop);
+            RawWaker::new(std::ptr::null(), &VTABLE)
+        }
+
+        // SAFETY: the waker does nothing
+        let waker = unsafe { Waker::from_raw(raw_waker()) };
+        let mut context = Context::from_waker(&waker);
+        let mut future = Box::pin(future);
+
+        loop {
+            if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+                return output;
+            }
+        }
+    }
+
+    #[test]
+    fn shutdown_test() {
+        let log = Log::default();
+        let container = container(&log);
+        container.get_singleton::<Repository>().unwrap();
+
+        container.shutdown();
+        container.shutdown();
+        assert_eq!(*log.lock().unwrap(), vec!["repository", "database"]);
+    }
+
+    #[test]
+    fn shutdown_with_async_hooks_test() {
+        let log = Log::default();
+        let container = container(&log);
+        container.get_singleton::<Api>().unwrap();
+
+        // Nothing is disposed, so the async hooks can still be called
+        let result =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| container.shutdown()));
+        assert!(result.is_err());
+        assert!(log.lock().unwrap().is_empty());
+        assert!(!container.is_shutdown());
+
+        block_on(container.shutdown_async());
+        assert_eq!(*log.lock().unwrap(), vec!["api", "repository", "database"]);
+    }
+
+    #[test]
+    fn resolve_after_shutdown_test() {
+        let log = Log::default();
+        let mut container = container(&log);
+        container
+            .add_scoped_singleton_deps_with_name::<Repository>("scoped")
+            .unwrap();
+        container.get_singleton::<Repository>().unwrap();
+
+        let scope = container.create_scope();
+        container.shutdown();
+        assert!(scope.is_shutdown());
+
+        let error = container.try_get_singleton::<Repository>().err().unwrap();
+        assert_eq!(ResolveErrorKind::Shutdown, error.kind());
+
+        // The scoped singletons are not created again
+        let error = scope
+            .try_get_scoped_singleton_with_name::<Repository>("scoped")
+            .err()
+            .unwrap();
+        assert_eq!(ResolveErrorKind::Shutdown, error.kind());
+        assert!(!scope.has_scoped_instance(&InjectionKey::with_name::<Repository>("scoped")));
+    }
+
+    #[test]
+    fn shutdown_async_test() {
+        let log = Log::default();
+        let container = container(&log);
+        container.get_singleton::<Api>().unwrap();
+
+        block_on(container.shutdown_async());
+        assert_eq!(*log.lock().unwrap(), vec!["api", "repository", "database"]);
+    }
+
+    #[test]
+    fn shutdown_ignores_uninitialized_test() {
+        let log = Log::default();
+        let container = container(&log);
+
+        block_on(container.shutdown_async());
+        assert_eq!(*log.lock().unwrap(), vec!["database"]);
+    }
+
+    #[test]
+    fn shutdown_scope_test() {
+        let log = Log::default();
+        let mut container = Container::new();
+        container.add_singleton(log.clone()).unwrap();
+        container.add_singleton(Database(log.clone())).unwrap();
+        container.add_scoped_singleton_deps::<Repository>().unwrap();
+        container.add_dispose::<Database>();
+        container.add_dispose::<Repository>();
+
+        let scope = container.create_scope();
+        scope.get_scoped_singleton::<Repository>().unwrap();
+
+        // Only the values of the scope are disposed
+        scope.shutdown();
+        assert_eq!(*log.lock().unwrap(), vec!["repository"]);
+        assert!(!scope.has_scoped_instance(&InjectionKey::of::<Repository>()));
+
+        container.shutdown();
+        assert_eq!(*log.lock().unwrap(), vec!["repository", "database"]);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::{InjectionKey, ProviderKind};
+use std::error;
+use std::fmt::{Debug, Display, Formatter};
+
+/// The reason why a dependency cannot be resolved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ResolveErrorKind {
+    /// There is no provider for the requested type and name.
+    MissingProvider,
+    /// A provider exists but is of other kind than the requested.
+    ScopeMismatch {
+        /// The kind of the provider requested.
+        expected: ProviderKind,
+        /// The kind of the provider registered.
+        found: ProviderKind,
+    },
+    /// A provider exists but the value it returns is not of the requested type.
+    TypeMismatch,
+    /// The provider constructs the value asynchronously and must be resolved with `get_async`.
+    AsyncProvider,
+    /// The dependency requires itself to be constructed.
+    CircularDependency,
+    /// The `Container` or one of its parents was shutdown.
+    Shutdown,
+    /// A custom error.
+    Other,
+}
+
+/// Represents an error occurring when resolving a dependency from the `Container`.
+pub struct ResolveError {
+    kind: ResolveErrorKind,
+    type_name: Option<&'static str>,
+    name: Option<String>,
+    chain: Vec<InjectionKey<'static>>,
+    cycle: Vec<InjectionKey<'static>>,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+    // The error is of the value requested and not of one of its dependencies
+    direct: bool,
+}
+
+impl ResolveError {
+    /// Constructs a new `ResolveError` from an error.
+    pub fn new<E>(error: E) -> Self
+    where
+        E: Into<Box<dyn error::Error + Send + Sync + 'static>>,
+    {
+        ResolveError {
+            kind: ResolveErrorKind::Other,
+            type_name: None,
+            name: None,
+            chain: crate::resolution::current_chain(),
+            cycle: vec![],
+            source: Some(error.into()),
+            direct: false,
+        }
+    }
+
+    /// Constructs a new `ResolveError` for a dependency missing.
+    pub fn missing_dependency<T>() -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        let mut error = Self::with_kind::<T>(ResolveErrorKind::MissingProvider, None);
+        error.direct = true;
+        error
+    }
+
+    /// Constructs a new `ResolveError` for a dependency with a name missing.
+    pub fn missing_dependency_with_name<T, S>(name: S) -> Self
+    where
+        T: ?Sized + 'static,
+        S: Into<String>,
+    {
+        let mut error = Self::with_kind::<T>(ResolveErrorKind::MissingProvider, Some(name.into()));
+        error.direct = true;
+        error
+    }
+
+    pub(crate) fn scope_mismatch<T>(
+        name: Option<&str>,
+        expected: ProviderKind,
+        found: ProviderKind,
+    ) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        let kind = ResolveErrorKind::ScopeMismatch { expected, found };
+        Self::with_kind::<T>(kind, name.map(String::from))
+    }
+
+    pub(crate) fn type_mismatch<T>(name: Option<&str>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        Self::with_kind::<T>(ResolveErrorKind::TypeMismatch, name.map(String::from))
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn async_provider<T>(name: Option<&str>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        Self::with_kind::<T>(ResolveErrorKind::AsyncProvider, name.map(String::from))
+    }
+
+    pub(crate) fn circular_dependency<T>(
+        name: Option<&str>,
+        cycle: Vec<InjectionKey<'static>>,
+    ) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        let mut error =
+            Self::with_kind::<T>(ResolveErrorKind::CircularDependency, name.map(String::from));
+        error.cycle = cycle;
+        error
+    }
+
+    pub(crate) fn shutdown<T>(name: Option<&str>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        Self::with_kind::<T>(ResolveErrorKind::Shutdown, name.map(String::from))
+    }
+
+    fn with_kind<T>(kind: ResolveErrorKind, name: Option<String>) -> Self
+    where
+        T: ?Sized + 'static,
+    {
+        ResolveError {
+            kind,
+            type_name: Some(std::any::type_name::<T>()),
+            name,
+            chain: crate::resolution::current_chain(),
+            cycle: vec![],
+            source: None,
+            direct: false,
+        }
+    }
+
+    // The errors returned by a provider are of a dependency of the value being resolved
+    pub(crate) fn into_indirect(mut self) -> Self {
+        self.direct = false;
+        self
+    }
+
+    /// Returns the reason of this error.
+    #[inline]
+    pub fn kind(&self) -> ResolveErrorKind {
+        self.kind
+    }
+
+    /// Returns the name of the type that failed to resolve, or `None` for custom errors.
+    #[inline]
+    pub fn type_name(&self) -> Option<&'static str> {
+        self.type_name
+    }
+
+    /// Returns the name of the dependency that failed to resolve, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the kind of provider that was requested, if the error is a scope mismatch.
+    pub fn expected_kind(&self) -> Option<ProviderKind> {
+        match self.kind {
+            ResolveErrorKind::ScopeMismatch { expected, .. } => Some(expected),
+            _ => None,
+        }
+    }
+
+    /// Returns the kind of provider that was found, if the error is a scope mismatch.
+    pub fn found_kind(&self) -> Option<ProviderKind> {
+        match self.kind {
+            ResolveErrorKind::ScopeMismatch { found, .. } => Some(found),
+            _ => None,
+        }
+    }
+
+    /// Returns the keys that were being resolved when this error occurred,
+    /// from the outermost to the one that required the failing dependency.
+    #[inline]
+    pub fn chain(&self) -> &[InjectionKey<'static>] {
+        &self.chain
+    }
+
+    /// Returns the keys that form a circular dependency, starting and ending
+    /// with the same key, for example: `A -> B -> C -> A`.
+    ///
+    /// Returns an empty slice if the error is not a circular dependency.
+    #[inline]
+    pub fn cycle(&self) -> &[InjectionKey<'static>] {
+        &self.cycle
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let type_name = self.type_name.unwrap_or("?");
+
+        match self.kind {
+            ResolveErrorKind::MissingProvider => write!(f, "missing dependency `{}`", type_name)?,
+            ResolveErrorKind::ScopeMismatch { expected, found } => write!(
+                f,
+                "expected {} dependency `{}` but found {}",
+                expected, type_name, found
+            )?,
+            ResolveErrorKind::TypeMismatch => write!(
+                f,
+                "the provider of `{}` returned a value of other type",
+                type_name
+            )?,
+            ResolveErrorKind::AsyncProvider => write!(
+                f,
+                "the provider of `{}` is async and must be resolved with `get_async`",
+                type_name
+            )?,
+            ResolveErrorKind::Shutdown => write!(
+                f,
+                "cannot resolve `{}` after the container was shutdown",
+                type_name
+            )?,
+            ResolveErrorKind::CircularDependency => {
+                write!(f, "circular dependency detected: ")?;
+                write_keys(f, &self.cycle)?;
+                return Ok(());
+            }
+            ResolveErrorKind::Other => match &self.source {
+                Some(source) => write!(f, "{}", source)?,
+                None => write!(f, "unable to resolve dependency")?,
+            },
+        }
+
+        if let Some(name) = &self.name {
+            write!(f, " (name: \"{}\")", name)?;
+        }
+
+        if !self.chain.is_empty() {
+            write!(f, ", required by: ")?;
+            write_keys(f, &self.chain)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl error::Error for ResolveError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
+
+/// Extension methods for the result of resolving a value from the `Container`.
+pub trait ResolveResultExt<T> {
+    /// Returns `Ok(None)` if there is no provider for the requested value,
+    /// the errors resolving the dependencies of the value are still returned.
+    ///
+    /// # Example
+    /// ```
+    /// use dilib::{Container, ResolveResultExt};
+    ///
+    /// let container = Container::new();
+    /// let value = container.try_get_scoped::<String>().optional().unwrap();
+    /// assert!(value.is_none());
+    /// ```
+    fn optional(self) -> Result<Option<T>, ResolveError>;
+}
+
+impl<T> ResolveResultExt<T> for Result<T, ResolveError> {
+    fn optional(self) -> Result<Option<T>, ResolveError> {
+        match self {
+            Ok(value) => Ok(Some(value)),
+            // A missing dependency of the value is returned by its provider
+            Err(err) if err.kind == ResolveErrorKind::MissingProvider && err.direct => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+fn write_keys(f: &mut Formatter<'_>, keys: &[InjectionKey]) -> std::fmt::Result {
+    for (index, key) in keys.iter().enumerate() {
+        if index > 0 {
+            write!(f, " -> ")?;
+        }
+
+        write!(f, "{}", key)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn missing_dependency_test() {
+        let error = ResolveError::missing_dependency::<String>();
+
+        assert_eq!(error.kind(), ResolveErrorKind::MissingProvider);
+        assert_eq!(error.type_name(), Some(std::any::type_name::<String>()));
+        assert_eq!(error.name(), None);
+        assert!(error.chain().is_empty());
+        assert!(error.to_string().contains("String"));
+    }
+
+    #[test]
+    fn missing_dependency_with_name_test() {
+        let error = ResolveError::missing_dependency_with_name::<i32, _>("number");
+
+        assert_eq!(error.kind(), ResolveErrorKind::MissingProvider);
+        assert_eq!(error.name(), Some("number"));
+        assert_eq!(
+            error.to_string(),
+            "missing dependency `i32` (name: \"number\")"
+        );
+    }
+
+    #[test]
+    fn custom_error_test() {
+        let error = ResolveError::new("invalid configuration");
+
+        assert_eq!(error.kind(), ResolveErrorKind::Other);
+        assert_eq!(error.type_name(), None);
+        assert_eq!(error.to_string(), "invalid configuration");
+        assert!(error::Error::source(&error).is_some());
+    }
+
+    #[test]
+    fn optional_test() {
+        use crate::{Container, ResolveResultExt, TryInject};
+
+        struct Service;
+        impl TryInject for Service {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                container.try_get_scoped::<String>()?;
+                Ok(Service)
+            }
+        }
+
+        let mut container = Container::new();
+        assert!(container
+            .try_get_scoped::<Service>()
+            .optional()
+            .unwrap()
+            .is_none());
+
+        // The missing dependency of the value is an error
+        container.add_try_deps::<Service>().unwrap();
+        let error = container
+            .try_get_scoped::<Service>()
+            .optional()
+            .err()
+            .unwrap();
+        assert_eq!(error.kind(), ResolveErrorKind::MissingProvider);
+        assert_eq!(error.type_name(), Some(std::any::type_name::<String>()));
+
+        container.add_scoped(String::new).unwrap();
+        assert!(container
+            .try_get_scoped::<Service>()
+            .optional()
+            .unwrap()
+            .is_some());
+    }
+
+    #[test]
+    fn optional_outside_resolution_test() {
+        use crate::{Container, ResolveResultExt, TryInject};
+        use std::sync::Mutex;
+
+        type Slot = Mutex<Option<Result<String, ResolveError>>>;
+
+        struct Service;
+        impl TryInject for Service {
+            fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+                // The result is stored while `Service` is being resolved
+                let slot = container.try_get_singleton::<Slot>()?;
+                *slot.lock().unwrap() = Some(container.try_get_scoped::<String>());
+                Ok(Service)
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_singleton(Slot::default()).unwrap();
+        container.add_try_deps::<Service>().unwrap();
+        container.try_get_scoped::<Service>().unwrap();
+
+        let slot = container.get_singleton::<Slot>().unwrap();
+        let result = slot.lock().unwrap().take().unwrap();
+
+        // The missing value is still the one requested, in other thread
+        let value = std::thread::spawn(move || result.optional())
+            .join()
+            .unwrap();
+        assert!(value.unwrap().is_none());
+    }
+}
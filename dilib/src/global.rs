@@ -4,7 +4,7 @@ use std::sync::atomic::{AtomicPtr, AtomicU8, Ordering};
 
 #[cfg(feature = "unstable_provide")]
 use {
-    crate::{InjectionKey, Provider},
+    crate::{DuplicatePolicy, InjectionKey, Provider},
     once_cell::sync::Lazy,
     std::sync::Mutex,
 };
@@ -111,6 +111,10 @@ pub struct InjectProvider {
     pub provider: Provider<'static>,
     // The key used to inject the provider
     pub key: InjectionKey<'static>,
+    // Whether the provider is added to the multi-bindings of the key
+    pub multi: bool,
+    // The order of the provider in `Container::get_all`
+    pub priority: i32,
 }
 
 // List of providers to be added to the global container
@@ -119,7 +123,33 @@ pub struct InjectProvider {
 pub static PROVIDERS: Lazy<Mutex<Option<Vec<InjectProvider>>>> =
     Lazy::new(|| Mutex::new(Some(vec![])));
 
+// Used by #[provide] on an `async fn` to fail to compile if the `async` feature is not enabled
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unstable_provide", feature = "async"))]
+macro_rules! __require_async {
+    ($fn_name:literal) => {};
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(all(feature = "unstable_provide", not(feature = "async")))]
+macro_rules! __require_async {
+    ($fn_name:literal) => {
+        compile_error!(concat!(
+            "#[provide] on `async fn ",
+            $fn_name,
+            "` requires the `async` feature of dilib"
+        ));
+    };
+}
+
 /// Initializes the global [`Container`].
+///
+/// The providers registered with `#[provide]` are added after calling `f`, following the
+/// [`DuplicatePolicy`] of the container, with the default policy if a provider already exists this panics.
+///
+/// [`DuplicatePolicy`]: crate::DuplicatePolicy
 #[cold]
 pub fn init_container<F>(f: F) -> Result<(), InitContainerError>
 where
@@ -133,16 +163,31 @@ where
                 let providers = lock.take().unwrap();
                 let container = CONTAINER.get_mut().unwrap();
 
-                for InjectProvider { key, provider, .. } in providers {
+                for InjectProvider {
+                    key,
+                    provider,
+                    multi,
+                    priority,
+                } in providers
+                {
+                    if multi {
+                        container.add_multi_provider_internal(key, provider, priority);
+                        continue;
+                    }
+
+                    // The priority of a discarded provider must not change the existing one
+                    let is_discarded = container.duplicate_policy() == DuplicatePolicy::KeepFirst
+                        && container.contains(key.clone());
+
+                    if is_discarded {
+                        continue;
+                    }
+
                     container
                         .add_provider_internal(key.clone(), provider)
-                        .unwrap_or_else(|_| {
-                            match key.name() {
-                                // TODO: Track actual type name for debugging
-                                Some(s) => panic!("Provider for '{}' already exists", s),
-                                None => panic!("Provider for '{:?}' already exists", key.type_id()),
-                            }
-                        });
+                        .unwrap_or_else(|_| panic!("Provider for '{}' already exists", key));
+
+                    container.set_priority(key, priority);
                 }
             }
 
@@ -153,11 +198,46 @@ where
 }
 
 /// Returns a reference to the global [`Container`] or `None` if is not initialized.
+///
+/// With the `testing` feature, returns the last override layer of the current thread if any,
+/// see [`crate::testing::override_container`].
 #[inline]
 pub fn get_container() -> Option<&'static Container<'static>> {
+    #[cfg(feature = "testing")]
+    if let Some(container) = crate::testing::current_override() {
+        return Some(container);
+    }
+
+    get_global_container()
+}
+
+// Returns the global container ignoring the override layers
+#[inline]
+pub(crate) fn get_global_container() -> Option<&'static Container<'static>> {
     CONTAINER.get()
 }
 
+/// Disposes the singletons of the global [`Container`], see [`Container::shutdown`].
+///
+/// The global container is never dropped, so this should be called before the program exits
+/// to close resources like database pools or file handles. Does nothing if the container is not initialized.
+///
+/// # Panics
+/// If a singleton has an [`AsyncDispose`](crate::AsyncDispose) hook, use [`shutdown_container_async`] instead.
+pub fn shutdown_container() {
+    if let Some(container) = get_global_container() {
+        container.shutdown();
+    }
+}
+
+/// Disposes the singletons of the global [`Container`] awaiting the async hooks,
+/// see [`Container::shutdown_async`].
+pub async fn shutdown_container_async() {
+    if let Some(container) = get_global_container() {
+        container.shutdown_async().await;
+    }
+}
+
 /// Returns a scoped value from the global [`Container`] or `None` if is not in the container.
 #[macro_export]
 macro_rules! get_scoped {
@@ -0,0 +1,458 @@
+use crate::{Container, InjectionKey, Provider, ProviderKind};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+#[cfg(feature = "serde")]
+use serde_crate::Serialize;
+
+/// A graph of the providers of a [`Container`] and the dependencies between them.
+///
+/// The nodes are sorted by type name and name, and the edges refer to the nodes by its index.
+///
+/// With the `serde` feature enabled the graph implements `Serialize`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
+pub struct DependencyGraph {
+    nodes: Vec<GraphNode>,
+    edges: Vec<GraphEdge>,
+}
+
+/// A provider in a [`DependencyGraph`], or a dependency without provider.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
+pub struct GraphNode {
+    #[cfg_attr(feature = "serde", serde(skip))]
+    key: InjectionKey<'static>,
+    type_name: &'static str,
+    name: Option<String>,
+    kind: Option<ProviderKind>,
+    initialized: Option<bool>,
+}
+
+/// A dependency between two nodes of a [`DependencyGraph`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize), serde(crate = "serde_crate"))]
+pub struct GraphEdge {
+    from: usize,
+    to: usize,
+    kind: ProviderKind,
+    has_default: bool,
+}
+
+impl DependencyGraph {
+    /// Returns the nodes of the graph.
+    #[inline]
+    pub fn nodes(&self) -> &[GraphNode] {
+        &self.nodes
+    }
+
+    /// Returns the edges of the graph.
+    #[inline]
+    pub fn edges(&self) -> &[GraphEdge] {
+        &self.edges
+    }
+
+    /// Returns the node for the given key, if any.
+    pub fn node(&self, key: &InjectionKey) -> Option<&GraphNode> {
+        self.nodes.iter().find(|n| &n.key == key)
+    }
+
+    /// Renders the graph in the Graphviz DOT format.
+    ///
+    /// Dependencies without provider are drawn dashed and in red,
+    /// and the edges of dependencies with default values are dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph dependencies {\n    node [shape=box];\n");
+
+        // Writing to a `String` never fails
+        for (index, node) in self.nodes.iter().enumerate() {
+            let style = if node.is_missing() {
+                ", style=dashed, color=red"
+            } else {
+                ""
+            };
+
+            writeln!(
+                dot,
+                "    n{} [label=\"{}\"{}];",
+                index,
+                escape(&node.label()),
+                style
+            )
+            .unwrap();
+        }
+
+        for edge in &self.edges {
+            let style = if edge.has_default {
+                " [style=dashed]"
+            } else {
+                ""
+            };
+
+            writeln!(dot, "    n{} -> n{}{};", edge.from, edge.to, style).unwrap();
+        }
+
+        dot.push('}');
+        dot
+    }
+}
+
+impl GraphNode {
+    /// Returns the `InjectionKey` of this node.
+    #[inline]
+    pub fn key(&self) -> &InjectionKey<'static> {
+        &self.key
+    }
+
+    /// Returns the name of the type of this node.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the name of this node, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// Returns the kind of the provider, or `None` if the dependency have no provider.
+    #[inline]
+    pub fn kind(&self) -> Option<ProviderKind> {
+        self.kind
+    }
+
+    /// Returns `true` if there is no provider for this node.
+    #[inline]
+    pub fn is_missing(&self) -> bool {
+        self.kind.is_none()
+    }
+
+    /// Returns `Some(true)` if this is a lazy singleton that has been initialized,
+    /// `Some(false)` if is a lazy singleton not initialized yet,
+    /// or `None` if is not a lazy singleton.
+    ///
+    /// Scoped singletons are initialized if the scope of the graph have its instance.
+    #[inline]
+    pub fn initialized(&self) -> Option<bool> {
+        self.initialized
+    }
+
+    fn label(&self) -> String {
+        let mut label = self.type_name.to_owned();
+
+        if let Some(name) = &self.name {
+            write!(label, "\nname: \"{}\"", name).unwrap();
+        }
+
+        match (self.kind, self.initialized) {
+            (None, _) => label.push_str("\nmissing"),
+            (Some(kind), None) => write!(label, "\n{}", kind).unwrap(),
+            (Some(kind), Some(true)) => write!(label, "\n{} (lazy, initialized)", kind).unwrap(),
+            (Some(kind), Some(false)) => write!(label, "\n{} (lazy, uninitialized)", kind).unwrap(),
+        }
+
+        label
+    }
+}
+
+impl GraphEdge {
+    /// Returns the index of the node that requires the dependency.
+    #[inline]
+    pub fn from(&self) -> usize {
+        self.from
+    }
+
+    /// Returns the index of the node of the dependency.
+    #[inline]
+    pub fn to(&self) -> usize {
+        self.to
+    }
+
+    /// Returns the kind of provider required for the dependency.
+    #[inline]
+    pub fn kind(&self) -> ProviderKind {
+        self.kind
+    }
+
+    /// Returns `true` if the dependency have a default value.
+    #[inline]
+    pub fn has_default(&self) -> bool {
+        self.has_default
+    }
+}
+
+pub(crate) fn dependency_graph(container: &Container) -> DependencyGraph {
+    let mut nodes = container
+        .iter()
+        .map(|(key, provider)| provider_node(container, key, provider))
+        .collect::<Vec<_>>();
+
+    // Dependencies from a parent scope or without provider are also included in the graph,
+    // the dependencies of the providers found in a parent scope are added until there is no new key
+    let mut pending = container
+        .iter()
+        .map(|(_, provider)| provider)
+        .collect::<Vec<_>>();
+
+    while let Some(provider) = pending.pop() {
+        for dependency in provider.dependencies() {
+            let key = dependency.key();
+            if nodes.iter().any(|n| n.key == key) {
+                continue;
+            }
+
+            let node = match container.get_provider(&key) {
+                Some(found) => {
+                    pending.push(found);
+                    provider_node(container, &key, found)
+                }
+                None => GraphNode {
+                    key,
+                    type_name: dependency.type_name(),
+                    name: dependency.name().map(String::from),
+                    kind: None,
+                    initialized: None,
+                },
+            };
+
+            nodes.push(node);
+        }
+    }
+
+    // Sorted by type name to always render the graph in the same order
+    nodes.sort_by(|a, b| {
+        (a.type_name, a.name.as_deref())
+            .cmp(&(b.type_name, b.name.as_deref()))
+            .then_with(|| a.key.cmp(&b.key))
+    });
+
+    let indices = nodes
+        .iter()
+        .enumerate()
+        .map(|(index, node)| (node.key.clone(), index))
+        .collect::<HashMap<_, _>>();
+
+    let mut edges = Vec::new();
+
+    for (from, node) in nodes.iter().enumerate() {
+        if let Some(provider) = container.get_provider(&node.key) {
+            for dependency in provider.dependencies() {
+                edges.push(GraphEdge {
+                    from,
+                    to: indices[&dependency.key()],
+                    kind: dependency.kind(),
+                    has_default: dependency.has_default(),
+                });
+            }
+        }
+    }
+
+    DependencyGraph { nodes, edges }
+}
+
+fn provider_node(container: &Container, key: &InjectionKey, provider: &Provider) -> GraphNode {
+    GraphNode {
+        key: key.clone().into_owned(),
+        type_name: provider.type_name(),
+        name: key.name().map(String::from),
+        kind: Some(provider.kind()),
+        initialized: match provider {
+            Provider::Singleton(shared) => shared.is_initialized(),
+            Provider::ScopedSingleton(_) => Some(container.has_scoped_instance(key)),
+            Provider::Scoped(_) => None,
+        },
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DependencyInfo, Inject};
+
+    struct Database;
+
+    struct Repository;
+
+    impl Inject for Repository {
+        fn inject(_: &Container) -> Self {
+            Repository
+        }
+
+        fn dependencies() -> Vec<DependencyInfo> {
+            vec![
+                DependencyInfo::singleton::<Database>(),
+                DependencyInfo::scoped::<String>().with_name("table"),
+                DependencyInfo::scoped::<u32>().with_default(),
+            ]
+        }
+    }
+
+    fn container() -> Container<'static> {
+        let mut container = Container::new();
+        container.add_lazy_singleton(|_| Database).unwrap();
+        container.add_deps::<Repository>().unwrap();
+        container.add_scoped(|| 3_u32).unwrap();
+        container
+    }
+
+    #[test]
+    fn dependency_graph_test() {
+        let container = container();
+        let graph = container.dependency_graph();
+
+        let type_names = graph
+            .nodes()
+            .iter()
+            .map(|n| n.type_name())
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            type_names,
+            vec![
+                std::any::type_name::<String>(),
+                std::any::type_name::<Database>(),
+                std::any::type_name::<Repository>(),
+                "u32"
+            ]
+        );
+
+        let string = &graph.nodes()[0];
+        assert_eq!(string.name(), Some("table"));
+        assert!(string.is_missing());
+
+        let database = graph.node(&InjectionKey::of::<Database>()).unwrap();
+        assert_eq!(database.kind(), Some(ProviderKind::Singleton));
+        assert_eq!(database.initialized(), Some(false));
+
+        let repository = graph.node(&InjectionKey::of::<Repository>()).unwrap();
+        assert_eq!(repository.kind(), Some(ProviderKind::Scoped));
+        assert_eq!(repository.initialized(), None);
+
+        let edges = graph
+            .edges()
+            .iter()
+            .map(|e| (e.from(), e.to(), e.kind(), e.has_default()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            edges,
+            vec![
+                (2, 1, ProviderKind::Singleton, false),
+                (2, 0, ProviderKind::Scoped, false),
+                (2, 3, ProviderKind::Scoped, true),
+            ]
+        );
+
+        container.get_singleton::<Database>().unwrap();
+        let graph = container.dependency_graph();
+        let database = graph.node(&InjectionKey::of::<Database>()).unwrap();
+        assert_eq!(database.initialized(), Some(true));
+    }
+
+    #[test]
+    fn dependency_graph_to_dot_test() {
+        let mut container = Container::new();
+        container.add_singleton(Database).unwrap();
+        container.add_deps::<Repository>().unwrap();
+
+        let dot = container.dependency_graph().to_dot();
+        let expected = format!(
+            r#"digraph dependencies {{
+    node [shape=box];
+    n0 [label="{}\nname: \"table\"\nmissing", style=dashed, color=red];
+    n1 [label="{}\nsingleton"];
+    n2 [label="{}\nscoped"];
+    n3 [label="u32\nmissing", style=dashed, color=red];
+    n2 -> n1;
+    n2 -> n0;
+    n2 -> n3 [style=dashed];
+}}"#,
+            std::any::type_name::<String>(),
+            std::any::type_name::<Database>(),
+            std::any::type_name::<Repository>(),
+        );
+
+        assert_eq!(dot, expected);
+    }
+
+    #[test]
+    fn dependency_graph_scope_test() {
+        struct Service;
+
+        impl Inject for Service {
+            fn inject(_: &Container) -> Self {
+                Service
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<Repository>()]
+            }
+        }
+
+        let mut parent = Container::new();
+        parent.add_singleton(Database).unwrap();
+        parent.add_deps::<Repository>().unwrap();
+
+        let mut scope = parent.create_scope();
+        scope.add_deps::<Service>().unwrap();
+
+        let graph = scope.dependency_graph();
+        let type_names = graph
+            .nodes()
+            .iter()
+            .map(|n| n.type_name())
+            .collect::<Vec<_>>();
+
+        // The dependencies of `Repository` are added from the parent
+        assert_eq!(
+            type_names,
+            vec![
+                std::any::type_name::<String>(),
+                std::any::type_name::<Database>(),
+                std::any::type_name::<Repository>(),
+                std::any::type_name::<Service>(),
+                "u32"
+            ]
+        );
+
+        let service = graph.node(&InjectionKey::of::<Service>()).unwrap();
+        assert_eq!(service.kind(), Some(ProviderKind::Scoped));
+        assert!(graph.node(&InjectionKey::of::<u32>()).unwrap().is_missing());
+
+        let edges = graph
+            .edges()
+            .iter()
+            .map(|e| (e.from(), e.to()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(edges, vec![(2, 1), (2, 0), (2, 4), (3, 2)]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn dependency_graph_serialize_test() {
+        let mut container = Container::new();
+        container.add_singleton(Database).unwrap();
+
+        let json = serde_json::to_value(container.dependency_graph()).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "nodes": [{
+                    "type_name": std::any::type_name::<Database>(),
+                    "name": null,
+                    "kind": "singleton",
+                    "initialized": null
+                }],
+                "edges": []
+            })
+        );
+    }
+}
@@ -0,0 +1,345 @@
+use crate::{Container, ResolveError};
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+#[cfg(feature = "lazy")]
+use {crate::Resolved, once_cell::sync::OnceCell, std::ops::Deref};
+
+/// Creates a new value of a scoped provider each time is called.
+///
+/// A `Factory<T>` is obtained with [`Container::get_factory`] or injected in a field of type `Factory<T>`,
+/// and can be used after the `Container` that created it is dropped.
+///
+/// # Example
+/// ```
+/// use dilib::{Container, Factory};
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+///
+/// static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+///
+/// struct Request { id: usize }
+///
+/// let mut container = Container::new();
+/// container.add_scoped(|| Request { id: NEXT_ID.fetch_add(1, Ordering::SeqCst) }).unwrap();
+///
+/// let factory = container.get_factory::<Request>().unwrap();
+/// assert_eq!(factory.create().id, 0);
+/// assert_eq!(factory.create().id, 1);
+/// ```
+pub struct Factory<T> {
+    container: Arc<Container<'static>>,
+    name: Option<String>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T> Factory<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new(container: Arc<Container<'static>>, name: Option<&str>) -> Self {
+        Factory {
+            container,
+            name: name.map(String::from),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Creates a new value.
+    ///
+    /// # Panics
+    /// If the value cannot be created, see [`Factory::try_create`].
+    pub fn create(&self) -> T {
+        self.try_create().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Creates a new value or returns the error if the value cannot be created.
+    pub fn try_create(&self) -> Result<T, ResolveError> {
+        match self.name.as_deref() {
+            Some(name) => self.container.try_get_scoped_with_name::<T>(name),
+            None => self.container.try_get_scoped::<T>(),
+        }
+    }
+
+    /// Returns the name of the provider, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+impl<T> Clone for Factory<T> {
+    fn clone(&self) -> Self {
+        Factory {
+            container: self.container.clone(),
+            name: self.name.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Debug for Factory<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Factory<{}>", std::any::type_name::<T>())
+    }
+}
+
+/// A value resolved from a provider of any scope the first time is dereferenced.
+///
+/// A `Lazy<T>` is obtained with [`Container::get_lazy`] or injected in a field of type `Lazy<T>`,
+/// and can be used after the `Container` that created it is dropped.
+///
+/// # Example
+/// ```
+/// use dilib::{Container, Lazy};
+///
+/// struct Report { rows: Vec<u32> }
+///
+/// let mut container = Container::new();
+/// container.add_scoped(|| Report { rows: vec![1, 2, 3] }).unwrap();
+///
+/// let report = container.get_lazy::<Report>().unwrap();
+/// assert!(!report.is_resolved());
+/// assert_eq!(report.rows.len(), 3);
+/// assert!(report.is_resolved());
+/// ```
+#[cfg(feature = "lazy")]
+pub struct Lazy<T> {
+    // Boxed so a type can hold a `Lazy` of a type that depends on it
+    cell: OnceCell<Box<Resolved<T>>>,
+    container: Arc<Container<'static>>,
+    name: Option<String>,
+}
+
+#[cfg(feature = "lazy")]
+impl<T> Lazy<T>
+where
+    T: Send + Sync + 'static,
+{
+    pub(crate) fn new(container: Arc<Container<'static>>, name: Option<&str>) -> Self {
+        Lazy {
+            cell: OnceCell::new(),
+            container,
+            name: name.map(String::from),
+        }
+    }
+
+    /// Returns the value, resolving it if was not resolved yet.
+    ///
+    /// # Panics
+    /// If the value cannot be resolved, see [`Lazy::try_get`].
+    pub fn get(&self) -> &T {
+        self.try_get().unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Returns the value, resolving it if was not resolved yet,
+    /// or returns the error if the value cannot be resolved.
+    pub fn try_get(&self) -> Result<&T, ResolveError> {
+        let resolved = self.cell.get_or_try_init(|| {
+            let resolved = match self.name.as_deref() {
+                Some(name) => self.container.try_get_with_name::<T>(name)?,
+                None => self.container.try_get::<T>()?,
+            };
+
+            Ok::<_, ResolveError>(Box::new(resolved))
+        })?;
+
+        Ok(resolved.as_ref().deref())
+    }
+
+    /// Returns `true` if the value has been resolved.
+    #[inline]
+    pub fn is_resolved(&self) -> bool {
+        self.cell.get().is_some()
+    }
+
+    /// Returns the name of the provider, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+}
+
+#[cfg(feature = "lazy")]
+impl<T> Deref for Lazy<T>
+where
+    T: Send + Sync + 'static,
+{
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+#[cfg(feature = "lazy")]
+impl<T: Debug> Debug for Lazy<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.cell.get() {
+            Some(value) => f
+                .debug_tuple("Lazy")
+                .field(value.as_ref().as_ref())
+                .finish(),
+            None => write!(f, "Lazy(<unresolved>)"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Container, DependencyInfo, Inject, Lazy};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Mutex;
+
+    #[test]
+    fn factory_test() {
+        let mut container = Container::new();
+        container.add_singleton(AtomicUsize::new(0)).unwrap();
+        container
+            .add_deps_fn(|c: &Container| {
+                let counter = c.get_singleton::<AtomicUsize>().unwrap();
+                counter.fetch_add(1, Ordering::SeqCst)
+            })
+            .unwrap();
+
+        let factory = container.get_factory::<usize>().unwrap();
+        assert_eq!(factory.create(), 0);
+        assert_eq!(factory.clone().create(), 1);
+    }
+
+    #[test]
+    fn factory_outlives_container_test() {
+        let mut container = Container::new();
+        container.add_singleton(Mutex::new(vec![1, 2])).unwrap();
+        container.add_scoped_with_name("total", || 3_usize).unwrap();
+
+        let factory = container.get_factory_with_name::<usize>("total").unwrap();
+        let values = container.get_singleton::<Mutex<Vec<i32>>>().unwrap();
+        drop(container);
+
+        assert_eq!(factory.create(), 3);
+        assert_eq!(values.lock().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn factory_requires_scoped_provider_test() {
+        let mut container = Container::new();
+        container.add_singleton(String::from("hello")).unwrap();
+
+        assert!(container.try_get_factory::<String>().is_err());
+        assert!(container.try_get_factory::<u32>().is_err());
+    }
+
+    #[test]
+    fn lazy_test() {
+        let mut container = Container::new();
+        container.add_singleton(AtomicUsize::new(0)).unwrap();
+        container
+            .add_deps_fn(|c: &Container| {
+                let counter = c.get_singleton::<AtomicUsize>().unwrap();
+                counter.fetch_add(1, Ordering::SeqCst);
+                String::from("value")
+            })
+            .unwrap();
+
+        let counter = container.get_singleton::<AtomicUsize>().unwrap();
+        let lazy = container.get_lazy::<String>().unwrap();
+        assert!(!lazy.is_resolved());
+        assert_eq!(counter.load(Ordering::SeqCst), 0);
+
+        assert_eq!(lazy.as_str(), "value");
+        assert_eq!(lazy.as_str(), "value");
+        assert_eq!(counter.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn lazy_shares_singletons_test() {
+        let mut container = Container::new();
+        container
+            .add_lazy_singleton(|_| Mutex::new(String::from("hello")))
+            .unwrap();
+
+        let lazy = container.get_lazy::<Mutex<String>>().unwrap();
+        lazy.lock().unwrap().push_str(" world");
+
+        let value = container.get_singleton::<Mutex<String>>().unwrap();
+        assert_eq!(*value.lock().unwrap(), "hello world");
+    }
+
+    #[test]
+    fn lazy_scoped_singleton_test() {
+        let mut container = Container::new();
+        container.add_scoped_singleton(|_| Mutex::new(0)).unwrap();
+
+        let scope = container.create_scope();
+        let lazy = scope.get_lazy::<Mutex<i32>>().unwrap();
+        *lazy.lock().unwrap() += 5;
+
+        // The value is shared with the scope that created the handle
+        assert_eq!(
+            *scope
+                .get_scoped_singleton::<Mutex<i32>>()
+                .unwrap()
+                .lock()
+                .unwrap(),
+            5
+        );
+        assert_eq!(
+            *container
+                .get_scoped_singleton::<Mutex<i32>>()
+                .unwrap()
+                .lock()
+                .unwrap(),
+            0
+        );
+    }
+
+    #[test]
+    fn lazy_missing_provider_test() {
+        let container = Container::new();
+        assert!(container.get_lazy::<String>().is_none());
+    }
+
+    #[test]
+    fn lazy_breaks_circular_dependency_test() {
+        struct Parent {
+            child: Child,
+        }
+
+        struct Child {
+            parent: Lazy<Parent>,
+        }
+
+        impl Inject for Parent {
+            fn inject(container: &Container) -> Self {
+                let child = container.get_scoped::<Child>().unwrap();
+                Parent { child }
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<Child>()]
+            }
+        }
+
+        impl Inject for Child {
+            fn inject(container: &Container) -> Self {
+                let parent = container.get_lazy::<Parent>().unwrap();
+                Child { parent }
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<Parent>().with_deferred()]
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_deps::<Parent>().unwrap();
+        container.add_deps::<Child>().unwrap();
+        container.validate().unwrap();
+
+        let parent = container.get_scoped::<Parent>().unwrap();
+        assert!(!parent.child.parent.is_resolved());
+        assert!(!parent.child.parent.child.parent.is_resolved());
+        assert!(parent.child.parent.is_resolved());
+    }
+}
@@ -1,4 +1,4 @@
-use crate::Container;
+use crate::{Container, DependencyInfo, ResolveError};
 
 /// A trait for constructing a type getting the dependencies from a `Container`.
 ///
@@ -30,6 +30,16 @@ use crate::Container;
 pub trait Inject {
     /// Constructs this type using the `Container`.
     fn inject(container: &Container) -> Self;
+
+    /// Returns the dependencies this type requires from the `Container`,
+    /// used by [`Container::validate`].
+    ///
+    /// The dependencies with a default value are not required to be in the `Container`.
+    ///
+    /// The default implementation returns no dependencies.
+    fn dependencies() -> Vec<DependencyInfo> {
+        Vec::new()
+    }
 }
 
 impl<T: Default> Inject for T {
@@ -39,29 +49,113 @@ impl<T: Default> Inject for T {
     }
 }
 
-/*
-Is not decided yet if is necessary to have an `TryInject` trait.
+/// Describes the dependencies of a type without constructing it.
+///
+/// This is implemented by `#[derive(Inject)]` and `#[derive(TryInject)]`
+/// with an entry for each field, including the fields with a default value.
+///
+/// # Example
+/// ```
+/// use dilib::{DependencyInfo, InjectMetadata, ProviderKind};
+///
+/// struct Cache;
+///
+/// impl InjectMetadata for Cache {
+///     fn dependencies() -> Vec<DependencyInfo> {
+///         vec![
+///             DependencyInfo::singleton::<String>().with_name("redis_url"),
+///             DependencyInfo::scoped::<u64>().with_default(),
+///         ]
+///     }
+/// }
+///
+/// let deps = <Cache as InjectMetadata>::dependencies();
+/// assert_eq!(deps[0].kind(), ProviderKind::Singleton);
+/// assert_eq!(deps[0].name(), Some("redis_url"));
+/// assert!(deps[1].has_default());
+/// ```
+pub trait InjectMetadata {
+    /// Returns the dependencies of this type.
+    fn dependencies() -> Vec<DependencyInfo>;
+}
 
-When creating an instance from `Inject` there is only 2 options:
-or create the instance or panic, is expected to be deterministic,
-if we allow the operation to fail with an `Err` this open some questions:
+/// A trait for attempt to construct a type getting the dependencies from a `Container`.
+///
+/// Unlike [`Inject`], a missing dependency is returned as a [`ResolveError`]
+/// instead of a panic, so the caller can decide how to recover.
+///
+/// # Example
+/// ```
+/// use dilib::{Container, ResolveError, Singleton, TryInject};
+///
+/// struct Notifier {
+///     smtp_host: Singleton<String>,
+/// }
+///
+/// impl TryInject for Notifier {
+///     fn try_inject(container: &Container) -> Result<Self, ResolveError> {
+///         let smtp_host = container.try_get_singleton_with_name::<String>("smtp_host")?;
+///         Ok(Notifier { smtp_host })
+///     }
+/// }
+///
+/// let mut container = Container::new();
+/// container.add_try_deps::<Notifier>().unwrap();
+///
+/// // `smtp_host` is not registered
+/// assert!(container.try_get_scoped::<Notifier>().is_err());
+/// ```
+pub trait TryInject: Sized {
+    /// Attempts to constructs this type using the `Container`.
+    fn try_inject(container: &Container) -> Result<Self, ResolveError>;
 
-- Why want an instance that may not be created?
-- In which cases no creating an instance is something that should not panic?
-- Why not just implement Inject to a `struct Fallible<T>(Result<T, Error>)`?
+    /// Returns the dependencies this type requires from the `Container`,
+    /// used by [`Container::validate`].
+    ///
+    /// The dependencies with a default value are not required to be in the `Container`.
+    ///
+    /// The default implementation returns no dependencies.
+    fn dependencies() -> Vec<DependencyInfo> {
+        Vec::new()
+    }
+}
 
-Checkout the related: `z_error.rs` and `z_typing.rs`
-*/
+/// The future returned by [`AsyncInject::inject_async`].
+#[cfg(feature = "async")]
+pub type InjectFuture<'a, T> = std::pin::Pin<Box<dyn std::future::Future<Output = T> + Send + 'a>>;
 
-// /// A trait for attempt to construct a type getting the dependencies from a `Container`.
-// pub trait TryInject: Sized {
-//     /// Attempts to constructs this type using the `Container`.
-//     fn try_inject(container: &Container) -> Result<Self, crate::error::ResolveError>;
-// }
+/// A trait for constructing a type asynchronously getting the dependencies from a `Container`.
+///
+/// The types are registered with [`Container::add_async_deps`] or [`Container::add_async_singleton_deps`]
+/// and resolved with [`Container::get_async`].
+///
+/// # Example
+/// ```
+/// use dilib::{AsyncInject, Container, InjectFuture, Singleton};
+///
+/// struct Client {
+///     base_url: Singleton<String>,
+/// }
+///
+/// impl AsyncInject for Client {
+///     fn inject_async<'c>(container: &'c Container<'c>) -> InjectFuture<'c, Self> {
+///         Box::pin(async move {
+///             let base_url = container.try_get_singleton_async::<String>().await.unwrap();
+///             Client { base_url }
+///         })
+///     }
+/// }
+/// ```
+#[cfg(feature = "async")]
+pub trait AsyncInject: Sized {
+    /// Constructs this type using the `Container`.
+    fn inject_async<'c>(container: &'c Container<'c>) -> InjectFuture<'c, Self>;
 
-// impl<T: TryInject> Inject for T {
-//     #[inline]
-//     fn inject(container: &Container) -> Self {
-//         Self::try_inject(container).unwrap()
-//     }
-// }
+    /// Returns the dependencies this type requires from the `Container`,
+    /// used by [`Container::validate`].
+    ///
+    /// The default implementation returns no dependencies.
+    fn dependencies() -> Vec<DependencyInfo> {
+        Vec::new()
+    }
+}
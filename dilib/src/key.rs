@@ -1,16 +1,29 @@
 use std::any::TypeId;
 use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+
+// Used when the key is constructed only from a `TypeId`
+const UNKNOWN_TYPE_NAME: &str = "<unknown>";
 
 /// Represents an unique key for identify a provider.
-#[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+///
+/// The key also holds the name of the type for diagnostics,
+/// but only the `TypeId` and the name are used for comparing and hashing.
+#[derive(Debug, Clone)]
 pub struct InjectionKey<'a> {
     type_id: TypeId,
+    type_name: &'static str,
     name: Option<Cow<'a, str>>,
 }
 
 impl<'a> InjectionKey<'a> {
     /// Constructs a new `InjectionKey`.
     ///
+    /// The name of the type is unknown for keys created with this method,
+    /// prefer [`InjectionKey::of`] or [`InjectionKey::with_name`].
+    ///
     /// # Params
     /// - `type_id`: The type of the value returned by the provider of this key.
     /// - `name`: The name of the key.
@@ -19,17 +32,45 @@ impl<'a> InjectionKey<'a> {
         S: Into<Cow<'a, str>>,
     {
         let name = name.map(|s| s.into());
-        InjectionKey { type_id, name }
+        InjectionKey {
+            type_id,
+            type_name: UNKNOWN_TYPE_NAME,
+            name,
+        }
     }
 
     /// Constructs a new `InjectionKey` from the specified type `T`.
     pub fn of<T: ?Sized + 'static>() -> Self {
-        Self::new::<String>(TypeId::of::<T>(), None)
+        Self::typed::<T, String>(None)
     }
 
     /// Constructs a new `InjectionKey` from the specified type `T` and name.
     pub fn with_name<T: ?Sized + 'static>(name: &str) -> Self {
-        Self::new(TypeId::of::<T>(), Some(name.to_string()))
+        Self::typed::<T, _>(Some(name.to_string()))
+    }
+
+    pub(crate) fn typed<T, S>(name: Option<S>) -> Self
+    where
+        T: ?Sized + 'static,
+        S: Into<Cow<'a, str>>,
+    {
+        InjectionKey {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            name: name.map(|s| s.into()),
+        }
+    }
+
+    pub(crate) fn from_parts(
+        type_id: TypeId,
+        type_name: &'static str,
+        name: Option<&'a str>,
+    ) -> Self {
+        InjectionKey {
+            type_id,
+            type_name,
+            name: name.map(Cow::Borrowed),
+        }
     }
 
     /// Returns the `TypeId` of the value this key provider returns.
@@ -37,10 +78,66 @@ impl<'a> InjectionKey<'a> {
         self.type_id
     }
 
+    /// Returns the name of the type of the value this key provider returns,
+    /// or `"<unknown>"` if the key was constructed from a `TypeId`.
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
     /// Returns the name of this key or `None`.
     pub fn name(&self) -> Option<&str> {
         self.name.as_deref()
     }
+
+    /// Converts this key into an `InjectionKey` that owns its name.
+    pub fn into_owned(self) -> InjectionKey<'static> {
+        InjectionKey {
+            type_id: self.type_id,
+            type_name: self.type_name,
+            name: self.name.map(|s| Cow::Owned(s.into_owned())),
+        }
+    }
+}
+
+impl PartialEq for InjectionKey<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id && self.name == other.name
+    }
+}
+
+impl Eq for InjectionKey<'_> {}
+
+impl Hash for InjectionKey<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.type_id.hash(state);
+        self.name.hash(state);
+    }
+}
+
+impl PartialOrd for InjectionKey<'_> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for InjectionKey<'_> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.type_id
+            .cmp(&other.type_id)
+            .then_with(|| self.name.cmp(&other.name))
+    }
+}
+
+impl Display for InjectionKey<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.type_name)?;
+
+        if let Some(name) = self.name() {
+            write!(f, " (name: \"{}\")", name)?;
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -50,9 +147,9 @@ mod tests {
     #[test]
     fn new_test() {
         let type_id = TypeId::of::<i32>();
-        let key = InjectionKey::new(type_id.clone(), Some("value"));
+        let key = InjectionKey::new(type_id, Some("value"));
 
-        assert_eq!(key.type_id(), type_id.clone());
+        assert_eq!(key.type_id(), type_id);
         assert_eq!(key.name(), Some("value"));
     }
 
@@ -69,4 +166,29 @@ mod tests {
         assert_eq!(key.type_id(), TypeId::of::<bool>());
         assert_eq!(key.name(), Some("value2"));
     }
+
+    #[test]
+    fn into_owned_test() {
+        let name = String::from("value3");
+        let key = InjectionKey::new(TypeId::of::<u8>(), Some(name.as_str())).into_owned();
+        drop(name);
+
+        assert_eq!(key.type_id(), TypeId::of::<u8>());
+        assert_eq!(key.name(), Some("value3"));
+    }
+
+    #[test]
+    fn type_name_test() {
+        let key = InjectionKey::with_name::<String>("text");
+        assert_eq!(key.type_name(), std::any::type_name::<String>());
+        assert_eq!(
+            key.to_string(),
+            format!("{} (name: \"text\")", std::any::type_name::<String>())
+        );
+
+        // The type name is not used for equality
+        let untyped = InjectionKey::new(TypeId::of::<String>(), Some("text"));
+        assert_eq!(untyped.type_name(), "<unknown>");
+        assert_eq!(key, untyped);
+    }
 }
@@ -2,7 +2,6 @@
 #![doc = include_str!(concat!(env!("OUT_DIR"), "/README-lib.md"))]
 
 mod lib_macros;
-pub use lib_macros::*;
 
 mod injectable;
 pub use injectable::*;
@@ -25,10 +24,33 @@ pub use shared::*;
 mod resolved;
 pub use resolved::*;
 
+mod error;
+pub use error::*;
+
+mod metadata;
+pub use metadata::*;
+
+mod validation;
+pub use validation::*;
+
+mod graph;
+pub use graph::*;
+
+mod dispose;
+pub use dispose::*;
+
+mod handle;
+pub use handle::*;
+
+mod resolution;
+
 #[cfg(feature = "derive")]
 mod derive {
     extern crate dilib_derive;
     pub use dilib_derive::*;
+
+    // Only emits an `Inject` impl, so doesn't need `unstable_provide`
+    pub use dilib_macros::injectable;
 }
 
 #[cfg(feature = "derive")]
@@ -38,6 +60,10 @@ pub use derive::*;
 #[cfg(feature = "global")]
 pub mod global;
 
+/// Utilities for testing, to override the global `Container` and record the resolved values.
+#[cfg(feature = "testing")]
+pub mod testing;
+
 /// procedural macros of `dilib`.
 #[cfg(feature = "unstable_provide")]
 pub mod macros {
@@ -49,4 +75,4 @@ pub use macros::*;
 
 #[doc(hidden)]
 #[cfg(feature = "unstable_provide")]
-pub use ctor::ctor;
\ No newline at end of file
+pub use ctor::ctor;
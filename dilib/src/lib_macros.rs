@@ -294,12 +294,12 @@ macro_rules! add_singleton_trait {
 #[macro_export]
 macro_rules! get_singleton_trait {
     ($container:ident, $trait_type:ident $(<$($generic:ident),+>)?) => {{
-        let ret = $container.get_singleton::<std::boxed::Box<(dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static)>>();
+        let ret = $container.get_singleton::<std::boxed::Box<dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static>>();
         ret
     }};
 
     ($container:ident, $trait_type:ident $(<$($generic:ident),+>)?, $name:literal) => {{
-        let ret = $container.get_singleton_with_name::<std::boxed::Box<(dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static)>>($name);
+        let ret = $container.get_singleton_with_name::<std::boxed::Box<dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static>>($name);
         ret
     }};
 }
@@ -308,12 +308,12 @@ macro_rules! get_singleton_trait {
 #[macro_export]
 macro_rules! get_resolved_trait {
     ($container:ident, $trait_type:ident $(<$($generic:ident),+>)?) => {{
-        let ret = $container.get::<std::boxed::Box<(dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static)>>();
+        let ret = $container.get::<std::boxed::Box<dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static>>();
         ret
     }};
 
     ($container:ident, $trait_type:ident $(<$($generic:ident),+>)?, $name:literal) => {{
-        let ret = $container.get_with_name::<std::boxed::Box<(dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static)>>($name);
+        let ret = $container.get_with_name::<std::boxed::Box<dyn $trait_type $(<$($generic),+>)? + Send + Sync + 'static>>($name);
         ret
     }};
 }
@@ -357,14 +357,17 @@ mod tests {
         let _r2 = get_resolved_trait!(container, Gen2<i32, bool>).unwrap();
     }
 
+    #[allow(dead_code)]
     trait Gen1<T1> {
         fn get(&self) -> &T1;
     }
 
+    #[allow(dead_code)]
     trait Gen2<T1, T2> {
         fn get(&self) -> (&T1, &T2);
     }
 
+    #[allow(dead_code)]
     trait Gen3<T1, T2, T3> {
         fn get(&self) -> (&T1, &T2, &T3);
     }
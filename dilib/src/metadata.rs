@@ -0,0 +1,176 @@
+use crate::{InjectionKey, ProviderKind};
+use std::any::TypeId;
+
+/// Describes the value returned by a provider and the dependencies it requires.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderInfo {
+    type_name: &'static str,
+    dependencies: Vec<DependencyInfo>,
+}
+
+impl ProviderInfo {
+    /// Constructs a `ProviderInfo` for a provider that returns a value of type `T`.
+    pub fn of<T: ?Sized + 'static>() -> Self {
+        ProviderInfo {
+            type_name: std::any::type_name::<T>(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Returns the name of the type of the value returned by the provider.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the dependencies required by the provider.
+    ///
+    /// Providers created from a function only have dependencies if they were declared.
+    #[inline]
+    pub fn dependencies(&self) -> &[DependencyInfo] {
+        &self.dependencies
+    }
+
+    pub(crate) fn set_dependencies(&mut self, dependencies: Vec<DependencyInfo>) {
+        self.dependencies = dependencies;
+    }
+}
+
+/// Describes a dependency required by a provider.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DependencyInfo {
+    type_id: TypeId,
+    type_name: &'static str,
+    name: Option<&'static str>,
+    kind: ProviderKind,
+    has_default: bool,
+    deferred: bool,
+    any_kind: bool,
+}
+
+impl DependencyInfo {
+    /// Constructs a `DependencyInfo` for a scoped value of type `T`.
+    pub fn scoped<T: ?Sized + 'static>() -> Self {
+        Self::new::<T>(ProviderKind::Scoped)
+    }
+
+    /// Constructs a `DependencyInfo` for a singleton of type `T` for each scope.
+    pub fn scoped_singleton<T: ?Sized + 'static>() -> Self {
+        Self::new::<T>(ProviderKind::ScopedSingleton)
+    }
+
+    /// Constructs a `DependencyInfo` for a singleton of type `T`.
+    pub fn singleton<T: ?Sized + 'static>() -> Self {
+        Self::new::<T>(ProviderKind::Singleton)
+    }
+
+    fn new<T: ?Sized + 'static>(kind: ProviderKind) -> Self {
+        DependencyInfo {
+            type_id: TypeId::of::<T>(),
+            type_name: std::any::type_name::<T>(),
+            name: None,
+            kind,
+            has_default: false,
+            deferred: false,
+            any_kind: false,
+        }
+    }
+
+    /// Sets the name of the dependency.
+    pub fn with_name(mut self, name: &'static str) -> Self {
+        self.name = Some(name);
+        self
+    }
+
+    /// Marks the dependency as having a default value,
+    /// so is not required to be registered in the `Container`.
+    pub fn with_default(mut self) -> Self {
+        self.has_default = true;
+        self
+    }
+
+    /// Marks the dependency as resolved after the value is constructed,
+    /// like a [`Lazy`] or [`Factory`] handle, so it cannot cause a circular dependency.
+    ///
+    /// [`Lazy`]: crate::Lazy
+    /// [`Factory`]: crate::Factory
+    pub fn with_deferred(mut self) -> Self {
+        self.deferred = true;
+        self
+    }
+
+    /// Marks the dependency as resolved from a provider of any kind,
+    /// like a [`Lazy`] handle, so its kind is only a hint.
+    ///
+    /// [`Lazy`]: crate::Lazy
+    pub fn with_any_kind(mut self) -> Self {
+        self.any_kind = true;
+        self
+    }
+
+    /// Returns the `TypeId` of the dependency.
+    #[inline]
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the name of the type of the dependency.
+    #[inline]
+    pub fn type_name(&self) -> &'static str {
+        self.type_name
+    }
+
+    /// Returns the name of the dependency, if any.
+    #[inline]
+    pub fn name(&self) -> Option<&'static str> {
+        self.name
+    }
+
+    /// Returns the kind of provider required for this dependency.
+    #[inline]
+    pub fn kind(&self) -> ProviderKind {
+        self.kind
+    }
+
+    /// Returns `true` if the dependency have a default value
+    /// and is not resolved from the `Container`.
+    #[inline]
+    pub fn has_default(&self) -> bool {
+        self.has_default
+    }
+
+    /// Returns `true` if the dependency is resolved after the value is constructed.
+    #[inline]
+    pub fn is_deferred(&self) -> bool {
+        self.deferred
+    }
+
+    /// Returns `true` if the dependency can be resolved from a provider of any kind.
+    #[inline]
+    pub fn is_any_kind(&self) -> bool {
+        self.any_kind
+    }
+
+    /// Returns the `InjectionKey` of the provider for this dependency.
+    pub fn key(&self) -> InjectionKey<'static> {
+        InjectionKey::from_parts(self.type_id, self.type_name, self.name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dependency_info_test() {
+        let dep = DependencyInfo::singleton::<String>().with_name("text");
+
+        assert_eq!(dep.type_id(), TypeId::of::<String>());
+        assert_eq!(dep.type_name(), std::any::type_name::<String>());
+        assert_eq!(dep.name(), Some("text"));
+        assert_eq!(dep.kind(), ProviderKind::Singleton);
+        assert!(!dep.has_default());
+        assert_eq!(dep.key(), InjectionKey::with_name::<String>("text"));
+        assert_eq!(dep.key().type_name(), std::any::type_name::<String>());
+    }
+}
@@ -1,21 +1,41 @@
 use crate::scoped::Scoped;
-use crate::{Container, Inject, Shared, Singleton};
-use std::fmt::{Debug, Formatter};
+use crate::{Container, DependencyInfo, Inject, ProviderInfo, Shared, Singleton};
+use std::fmt::{Debug, Display, Formatter};
 
 /// Represents the type of the provider.
 #[derive(Debug, Clone, Copy, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde_crate::Serialize),
+    serde(crate = "serde_crate", rename_all = "snake_case")
+)]
 pub enum ProviderKind {
     /// Is a scoped provider.
     Scoped,
+    /// Is a singleton provider for each scope created with [`Container::create_scope`].
+    ScopedSingleton,
     /// Is a singleton provider.
     Singleton,
 }
 
+impl Display for ProviderKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProviderKind::Scoped => write!(f, "scoped"),
+            ProviderKind::ScopedSingleton => write!(f, "scoped singleton"),
+            ProviderKind::Singleton => write!(f, "singleton"),
+        }
+    }
+}
+
 /// Provides a [`Container`] value.
 #[derive(Clone)]
 pub enum Provider<'a> {
     /// A provider that returns a new value each time is requested.
     Scoped(Scoped),
+    /// A provider that returns the same value each time is requested from the same scope,
+    /// each scope created with [`Container::create_scope`] have its own value.
+    ScopedSingleton(Scoped),
     /// A provider that returns the same value each time is required.
     Singleton(Shared<'a>),
 }
@@ -26,19 +46,52 @@ impl<'a> Provider<'a> {
         matches!(self, Provider::Scoped(_))
     }
 
+    /// Returns `true` if the provider is a singleton for each scope.
+    pub fn is_scoped_singleton(&self) -> bool {
+        matches!(self, Provider::ScopedSingleton(_))
+    }
+
     /// Returns `true` if the provider is a singleton.
     pub fn is_singleton(&self) -> bool {
         matches!(self, Provider::Singleton(_))
     }
 
+    /// Returns `true` if the provider constructs the value asynchronously.
+    pub fn is_async(&self) -> bool {
+        match self {
+            Provider::Scoped(scoped) | Provider::ScopedSingleton(scoped) => scoped.is_async(),
+            Provider::Singleton(shared) => shared.is_async(),
+        }
+    }
+
     /// Returns the type of this provider.
     pub fn kind(&self) -> ProviderKind {
         match self {
             Provider::Scoped(_) => ProviderKind::Scoped,
+            Provider::ScopedSingleton(_) => ProviderKind::ScopedSingleton,
             Provider::Singleton(_) => ProviderKind::Singleton,
         }
     }
 
+    /// Returns the name of the type of the value this provider returns.
+    pub fn type_name(&self) -> &'static str {
+        self.info().type_name()
+    }
+
+    /// Returns the dependencies this provider requires.
+    pub fn dependencies(&self) -> &[DependencyInfo] {
+        self.info().dependencies()
+    }
+
+    /// Returns the information of this provider.
+    pub fn info(&self) -> &ProviderInfo {
+        match self {
+            Provider::Scoped(scoped) => scoped.info(),
+            Provider::ScopedSingleton(scoped) => scoped.info(),
+            Provider::Singleton(shared) => shared.info(),
+        }
+    }
+
     /// Gets a value of the specified type `T` or `None` if `T` is `Inject`.
     #[inline]
     pub fn get_scoped<T>(&self) -> Option<T>
@@ -46,7 +99,7 @@ impl<'a> Provider<'a> {
         T: Send + Sync + 'static,
     {
         match self {
-            Provider::Scoped(scoped @ Scoped::Factory(_)) => scoped.call_factory(),
+            Provider::Scoped(scoped @ Scoped::Factory(..)) => scoped.call_factory(),
             _ => None,
         }
     }
@@ -59,8 +112,11 @@ impl<'a> Provider<'a> {
     {
         match self {
             Provider::Scoped(scoped) => match scoped {
-                Scoped::Factory(_) => None,
-                Scoped::Construct(_) => scoped.call_construct(container),
+                Scoped::Factory(..) => None,
+                Scoped::Construct(..) => scoped.call_construct(container),
+                Scoped::TryConstruct(..) => scoped.call_try_construct(container).ok().flatten(),
+                #[cfg(feature = "async")]
+                Scoped::AsyncConstruct(..) => None,
             },
             _ => None,
         }
@@ -90,13 +146,25 @@ impl<'a> Provider<'a> {
             _ => None,
         }
     }
+
+    // Returns this provider without the lifetime of the container
+    pub(crate) fn into_static(self) -> Provider<'static> {
+        match self {
+            Provider::Scoped(x) => Provider::Scoped(x),
+            Provider::ScopedSingleton(x) => Provider::ScopedSingleton(x),
+            Provider::Singleton(x) => Provider::Singleton(x.into_static()),
+        }
+    }
 }
 
 impl Debug for Provider<'_> {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             Provider::Scoped(scoped) => write!(f, "Provider::Scoped({:?})", scoped),
-            Provider::Singleton(_) => write!(f, "Provider::Singleton(..))"),
+            Provider::ScopedSingleton(scoped) => {
+                write!(f, "Provider::ScopedSingleton({:?})", scoped)
+            }
+            Provider::Singleton(shared) => write!(f, "Provider::Singleton({:?})", shared),
         }
     }
 }
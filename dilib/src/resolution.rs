@@ -0,0 +1,155 @@
+use crate::InjectionKey;
+use std::cell::RefCell;
+
+#[cfg(feature = "async")]
+use {
+    std::future::Future,
+    std::pin::Pin,
+    std::task::{Context, Poll},
+};
+
+thread_local! {
+    // Keys being resolved in the current thread, from the outermost to the innermost.
+    static RESOLVING: RefCell<Vec<InjectionKey<'static>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Marks a key as being resolved in the current thread until is dropped.
+pub(crate) struct ResolvingGuard(());
+
+impl ResolvingGuard {
+    /// Marks the key as being resolved, or returns the cycle of keys
+    /// if the key is already being resolved in the current thread.
+    pub(crate) fn enter(key: &InjectionKey) -> Result<Self, Vec<InjectionKey<'static>>> {
+        RESOLVING.with(|keys| {
+            let mut keys = keys.borrow_mut();
+            check_cycle(&keys, key)?;
+            keys.push(key.clone().into_owned());
+            Ok(ResolvingGuard(()))
+        })
+    }
+}
+
+impl Drop for ResolvingGuard {
+    fn drop(&mut self) {
+        // The thread local may be already destroyed if we are dropped during thread teardown
+        let _ = RESOLVING.try_with(|keys| keys.borrow_mut().pop());
+    }
+}
+
+/// Returns the keys being resolved in the current thread.
+pub(crate) fn current_chain() -> Vec<InjectionKey<'static>> {
+    RESOLVING
+        .try_with(|keys| keys.borrow().clone())
+        .unwrap_or_default()
+}
+
+// Returns the cycle if the key is already being resolved
+fn check_cycle(
+    keys: &[InjectionKey],
+    key: &InjectionKey,
+) -> Result<(), Vec<InjectionKey<'static>>> {
+    match keys.iter().position(|k| k == key) {
+        Some(pos) => {
+            let mut cycle = keys[pos..]
+                .iter()
+                .map(|k| k.clone().into_owned())
+                .collect::<Vec<_>>();
+            cycle.push(key.clone().into_owned());
+            Err(cycle)
+        }
+        None => Ok(()),
+    }
+}
+
+/// A future that resolves a key, the keys being resolved are restored each time is polled
+/// so they are tracked across `.await` points, even if the task moves to other thread.
+#[cfg(feature = "async")]
+pub(crate) struct ResolvingFuture<F> {
+    keys: Vec<InjectionKey<'static>>,
+    future: Pin<Box<F>>,
+}
+
+#[cfg(feature = "async")]
+impl<F: Future> ResolvingFuture<F> {
+    /// Marks the key as being resolved while the future runs, or returns the cycle of keys
+    /// if the key is already being resolved by the current task.
+    pub(crate) fn enter(key: &InjectionKey, future: F) -> Result<Self, Vec<InjectionKey<'static>>> {
+        let mut keys = current_chain();
+        check_cycle(&keys, key)?;
+        keys.push(key.clone().into_owned());
+
+        Ok(ResolvingFuture {
+            keys,
+            future: Box::pin(future),
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl<F: Future> Future for ResolvingFuture<F> {
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // Restores the keys of the caller even if the future panics
+        struct Restore<'a>(&'a mut Vec<InjectionKey<'static>>);
+
+        impl Drop for Restore<'_> {
+            fn drop(&mut self) {
+                let _ = RESOLVING.try_with(|keys| std::mem::swap(&mut *keys.borrow_mut(), self.0));
+            }
+        }
+
+        let this = self.get_mut();
+        RESOLVING.with(|keys| std::mem::swap(&mut *keys.borrow_mut(), &mut this.keys));
+        let _restore = Restore(&mut this.keys);
+        this.future.as_mut().poll(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolving_guard_test() {
+        assert!(current_chain().is_empty());
+
+        {
+            let _g1 = ResolvingGuard::enter(&InjectionKey::of::<i32>()).unwrap();
+            let _g2 = ResolvingGuard::enter(&InjectionKey::with_name::<String>("text")).unwrap();
+
+            let chain = current_chain();
+            assert_eq!(
+                chain,
+                vec![
+                    InjectionKey::of::<i32>(),
+                    InjectionKey::with_name::<String>("text")
+                ]
+            );
+        }
+
+        assert!(current_chain().is_empty());
+    }
+
+    #[test]
+    fn resolving_guard_cycle_test() {
+        let _g1 = ResolvingGuard::enter(&InjectionKey::of::<i32>()).unwrap();
+        let _g2 = ResolvingGuard::enter(&InjectionKey::of::<u32>()).unwrap();
+        let _g3 = ResolvingGuard::enter(&InjectionKey::of::<bool>()).unwrap();
+
+        let cycle = ResolvingGuard::enter(&InjectionKey::of::<u32>())
+            .err()
+            .unwrap();
+        assert_eq!(
+            cycle,
+            vec![
+                InjectionKey::of::<u32>(),
+                InjectionKey::of::<bool>(),
+                InjectionKey::of::<u32>()
+            ]
+        );
+
+        // The stack is not modified
+        assert_eq!(current_chain().len(), 3);
+    }
+}
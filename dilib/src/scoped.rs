@@ -1,13 +1,30 @@
-use crate::Container;
+use crate::{Container, DependencyInfo, ProviderInfo, ResolveError};
 use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
+type BoxedFactoryFn = Arc<dyn Fn() -> Box<dyn Any + Send + Sync> + Send + Sync>;
+type BoxedConstructFn = Arc<dyn Fn(&Container) -> Box<dyn Any + Send + Sync> + Send + Sync>;
+type BoxedTryConstructFn =
+    Arc<dyn Fn(&Container) -> Result<Box<dyn Any + Send + Sync>, ResolveError> + Send + Sync>;
+
+#[cfg(feature = "async")]
+type BoxedAsyncConstructFn = Arc<
+    dyn for<'c> Fn(&'c Container<'c>) -> crate::InjectFuture<'c, Box<dyn Any + Send + Sync>>
+        + Send
+        + Sync,
+>;
+
 /// Represents an `Scoped` provider which provide a new instance each time.
+///
+/// Each variant holds the function that creates the value and the information of the provider.
 #[derive(Clone)]
 pub enum Scoped {
-    Factory(Arc<dyn Fn() -> Box<dyn Any + Send + Sync> + Send + Sync>),
-    Construct(Arc<dyn Fn(&Container) -> Box<dyn Any + Send + Sync> + Send + Sync>),
+    Factory(BoxedFactoryFn, ProviderInfo),
+    Construct(BoxedConstructFn, ProviderInfo),
+    TryConstruct(BoxedTryConstructFn, ProviderInfo),
+    #[cfg(feature = "async")]
+    AsyncConstruct(BoxedAsyncConstructFn, ProviderInfo),
 }
 
 impl Scoped {
@@ -19,7 +36,7 @@ impl Scoped {
     {
         let factory = Arc::new(move || -> Box<dyn Any + Send + Sync> { Box::new(f()) });
 
-        Scoped::Factory(factory)
+        Scoped::Factory(factory, ProviderInfo::of::<T>())
     }
 
     /// Creates a new `Scoped` provider from an inject function.
@@ -31,7 +48,99 @@ impl Scoped {
         let factory =
             Arc::new(move |c: &Container| -> Box<dyn Any + Send + Sync> { Box::new(f(c)) });
 
-        Scoped::Construct(factory)
+        Scoped::Construct(factory, ProviderInfo::of::<T>())
+    }
+
+    /// Creates a new `Scoped` provider from a fallible inject function.
+    pub fn from_try_construct<T, F>(f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&Container) -> Result<T, ResolveError> + Send + Sync + 'static,
+    {
+        let factory = Arc::new(
+            move |c: &Container| -> Result<Box<dyn Any + Send + Sync>, ResolveError> {
+                let value = f(c)?;
+                Ok(Box::new(value))
+            },
+        );
+
+        Scoped::TryConstruct(factory, ProviderInfo::of::<T>())
+    }
+
+    /// Creates a new `Scoped` provider from a factory function that returns a future.
+    #[cfg(feature = "async")]
+    pub fn from_async_factory<T, F, Fut>(f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = T> + Send + 'static,
+    {
+        let factory = boxed_async_construct(move |_| {
+            let future = f();
+            Box::pin(async move { Box::new(future.await) as Box<dyn Any + Send + Sync> })
+        });
+
+        Scoped::AsyncConstruct(factory, ProviderInfo::of::<T>())
+    }
+
+    /// Creates a new `Scoped` provider from an async inject function.
+    #[cfg(feature = "async")]
+    pub fn from_async_construct<T, F>(f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: for<'c> Fn(&'c Container<'c>) -> crate::InjectFuture<'c, T> + Send + Sync + 'static,
+    {
+        let factory = boxed_async_construct(move |c| {
+            let future = f(c);
+            Box::pin(async move { Box::new(future.await) as Box<dyn Any + Send + Sync> })
+        });
+
+        Scoped::AsyncConstruct(factory, ProviderInfo::of::<T>())
+    }
+
+    /// Returns `true` if the value is constructed asynchronously.
+    pub fn is_async(&self) -> bool {
+        match self {
+            #[cfg(feature = "async")]
+            Scoped::AsyncConstruct(..) => true,
+            _ => false,
+        }
+    }
+
+    /// Sets the dependencies this provider requires to construct the value.
+    pub fn with_dependencies<I>(mut self, dependencies: I) -> Self
+    where
+        I: IntoIterator<Item = DependencyInfo>,
+    {
+        self.info_mut()
+            .set_dependencies(dependencies.into_iter().collect());
+        self
+    }
+
+    /// Returns the information of this provider.
+    pub fn info(&self) -> &ProviderInfo {
+        match self {
+            Scoped::Factory(_, info) => info,
+            Scoped::Construct(_, info) => info,
+            Scoped::TryConstruct(_, info) => info,
+            #[cfg(feature = "async")]
+            Scoped::AsyncConstruct(_, info) => info,
+        }
+    }
+
+    fn info_mut(&mut self) -> &mut ProviderInfo {
+        match self {
+            Scoped::Factory(_, info) => info,
+            Scoped::Construct(_, info) => info,
+            Scoped::TryConstruct(_, info) => info,
+            #[cfg(feature = "async")]
+            Scoped::AsyncConstruct(_, info) => info,
+        }
+    }
+
+    /// Returns the name of the type this provider returns.
+    pub fn type_name(&self) -> &'static str {
+        self.info().type_name()
     }
 
     pub(crate) fn call_factory<T>(&self) -> Option<T>
@@ -39,7 +148,7 @@ impl Scoped {
         T: Send + Sync + 'static,
     {
         match self {
-            Scoped::Factory(f) => {
+            Scoped::Factory(f, _) => {
                 let x: Box<dyn Any + Send + Sync> = f.as_ref()();
                 x.downcast().map(|x| *x).ok()
             }
@@ -52,20 +161,86 @@ impl Scoped {
         T: 'static,
     {
         match self {
-            Scoped::Construct(f) => {
+            Scoped::Construct(f, _) => {
                 let x: Box<dyn Any + Send + Sync> = f.as_ref()(container);
                 x.downcast().map(|x| *x).ok()
             }
             _ => None,
         }
     }
+
+    // Calls the function of any of the variants
+    pub(crate) fn call<T>(&self, container: &Container) -> Result<Option<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        match self {
+            Scoped::Factory(..) => Ok(self.call_factory()),
+            Scoped::Construct(..) => Ok(self.call_construct(container)),
+            Scoped::TryConstruct(..) => self.call_try_construct(container),
+            #[cfg(feature = "async")]
+            Scoped::AsyncConstruct(..) => Err(ResolveError::async_provider::<T>(None)),
+        }
+    }
+
+    // Calls the function of any of the variants, awaiting the async constructors
+    #[cfg(feature = "async")]
+    pub(crate) async fn call_async<T>(
+        &self,
+        container: &Container<'_>,
+    ) -> Result<Option<T>, ResolveError>
+    where
+        T: Send + Sync + 'static,
+    {
+        match self {
+            Scoped::AsyncConstruct(f, _) => {
+                let x: Box<dyn Any + Send + Sync> = f.as_ref()(container).await;
+                Ok(x.downcast().map(|x| *x).ok())
+            }
+            _ => self.call(container),
+        }
+    }
+
+    // Returns `Err` if the construction failed, or `Ok(None)` if the value is of other type
+    pub(crate) fn call_try_construct<T>(
+        &self,
+        container: &Container,
+    ) -> Result<Option<T>, ResolveError>
+    where
+        T: 'static,
+    {
+        match self {
+            Scoped::TryConstruct(f, _) => {
+                let x: Box<dyn Any + Send + Sync> = f.as_ref()(container)?;
+                Ok(x.downcast().map(|x| *x).ok())
+            }
+            _ => Ok(None),
+        }
+    }
+}
+
+// Constrains the closure to a function that returns a future borrowing the container
+#[cfg(feature = "async")]
+fn boxed_async_construct<F>(f: F) -> BoxedAsyncConstructFn
+where
+    F: for<'c> Fn(&'c Container<'c>) -> crate::InjectFuture<'c, Box<dyn Any + Send + Sync>>
+        + Send
+        + Sync
+        + 'static,
+{
+    Arc::new(f)
 }
 
 impl Debug for Scoped {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Scoped::Factory(_) => write!(f, "Scoped::Factory(..)"),
-            Scoped::Construct(_) => write!(f, "Scoped::Inject(..)"),
+            Scoped::Factory(_, info) => write!(f, "Scoped::Factory({})", info.type_name()),
+            Scoped::Construct(_, info) => write!(f, "Scoped::Inject({})", info.type_name()),
+            Scoped::TryConstruct(_, info) => write!(f, "Scoped::TryInject({})", info.type_name()),
+            #[cfg(feature = "async")]
+            Scoped::AsyncConstruct(_, info) => {
+                write!(f, "Scoped::AsyncInject({})", info.type_name())
+            }
         }
     }
 }
@@ -89,4 +264,23 @@ mod tests {
         let inject = Scoped::from_construct(|c| c.get::<i32>().unwrap().cloned() + 1);
         assert_eq!(inject.call_construct(&container), Some(6));
     }
+
+    #[test]
+    fn call_try_construct_test() {
+        let mut container = Container::new();
+        container.add_singleton(5_i32).unwrap();
+
+        let inject = Scoped::from_try_construct(|c| c.try_get_singleton::<i32>().map(|x| *x + 1));
+        assert_eq!(inject.call_try_construct(&container).unwrap(), Some(6));
+
+        let inject = Scoped::from_try_construct(|c| c.try_get_singleton::<u8>().map(|x| *x + 1));
+        assert!(inject.call_try_construct::<u8>(&container).is_err());
+    }
+
+    #[test]
+    fn type_name_test() {
+        let factory = Scoped::from_factory(|| 10_u64);
+        assert_eq!(factory.type_name(), "u64");
+        assert_eq!(format!("{:?}", factory), "Scoped::Factory(u64)");
+    }
 }
@@ -1,4 +1,6 @@
+use crate::{DependencyInfo, ProviderInfo};
 use std::any::Any;
+use std::fmt::{Debug, Formatter};
 use std::sync::Arc;
 
 #[cfg(feature = "lazy")]
@@ -7,18 +9,29 @@ type BoxedOnceFn = Box<dyn FnOnce(&crate::Container) -> Arc<dyn Any + Send + Syn
 #[cfg(feature = "lazy")]
 use {crate::Container, late_init::LateInit};
 
+#[cfg(feature = "async")]
+use async_late_init::AsyncLateInit;
+
 /// Provides a singleton value.
+///
+/// Each variant holds the value or the function that creates it, and the information of the provider.
 #[derive(Clone)]
 pub enum Shared<'a> {
     /// A single instance of the value.
-    Instance(Arc<dyn Any + Send + Sync>),
+    Instance(Arc<dyn Any + Send + Sync>, ProviderInfo),
 
     #[cfg(feature = "lazy")]
     /// A factory function to create the single value.
-    Lazy(Arc<LateInit<Arc<dyn Any + Send + Sync>, &'a Container<'a>, BoxedOnceFn>>),
+    Lazy(
+        Arc<LateInit<Arc<dyn Any + Send + Sync>, (), BoxedOnceFn>>,
+        ProviderInfo,
+    ),
+
+    #[cfg(feature = "async")]
+    /// A factory function to create the single value asynchronously.
+    AsyncLazy(Arc<AsyncLateInit>, ProviderInfo),
 
     #[doc(hidden)]
-    #[cfg(not(feature = "lazy"))]
     __NonExhaustive(&'a std::marker::PhantomData<()>),
 }
 
@@ -28,7 +41,7 @@ impl<'a> Shared<'a> {
     where
         T: Send + Sync + 'static,
     {
-        Shared::Instance(Arc::new(value))
+        Shared::Instance(Arc::new(value), ProviderInfo::of::<T>())
     }
 
     /// Provides a new instance of the singleton from a factory.
@@ -45,39 +58,186 @@ impl<'a> Shared<'a> {
 
         let boxed: BoxedOnceFn = Box::new(factory);
         let lazy = LateInit::new(boxed);
-        Shared::Lazy(Arc::new(lazy))
+        Shared::Lazy(Arc::new(lazy), ProviderInfo::of::<T>())
     }
 
-    pub(crate) fn get<T>(&self) -> Option<Arc<T>>
+    /// Provides a new instance of the singleton from a factory that returns a future.
+    #[cfg(feature = "async")]
+    pub fn new_async<T, F, Fut>(f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&crate::Container) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = T> + Send + 'static,
+    {
+        let lazy = AsyncLateInit::new(move |container| {
+            let future = f(container);
+            Box::pin(async move { Arc::new(future.await) as Arc<dyn Any + Send + Sync> })
+        });
+
+        Shared::AsyncLazy(Arc::new(lazy), ProviderInfo::of::<T>())
+    }
+
+    /// Provides a new instance of the singleton from an async inject function.
+    #[cfg(feature = "async")]
+    pub fn from_async_construct<T, F>(f: F) -> Self
     where
         T: Send + Sync + 'static,
+        F: for<'c> Fn(&'c crate::Container<'c>) -> crate::InjectFuture<'c, T>
+            + Send
+            + Sync
+            + 'static,
     {
+        let lazy = AsyncLateInit::new(move |container| {
+            let future = f(container);
+            Box::pin(async move { Arc::new(future.await) as Arc<dyn Any + Send + Sync> })
+        });
+
+        Shared::AsyncLazy(Arc::new(lazy), ProviderInfo::of::<T>())
+    }
+
+    /// Sets the dependencies this provider requires to construct the singleton.
+    ///
+    /// This only have effect on lazy singletons.
+    #[cfg_attr(not(any(feature = "lazy", feature = "async")), allow(unused_variables))]
+    pub fn with_dependencies<I>(mut self, dependencies: I) -> Self
+    where
+        I: IntoIterator<Item = DependencyInfo>,
+    {
+        match &mut self {
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(_, info) => info.set_dependencies(dependencies.into_iter().collect()),
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(_, info) => info.set_dependencies(dependencies.into_iter().collect()),
+            _ => {}
+        }
+
+        self
+    }
+
+    /// Returns the information of this provider.
+    pub fn info(&self) -> &ProviderInfo {
         match self {
-            Shared::Instance(x) => x.clone().downcast().ok(),
+            Shared::Instance(_, info) => info,
             #[cfg(feature = "lazy")]
-            Shared::Lazy(_) => None,
-            #[cfg(not(feature = "lazy"))]
+            Shared::Lazy(_, info) => info,
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(_, info) => info,
+            Shared::__NonExhaustive(_) => unreachable!(),
+        }
+    }
+
+    /// Returns the name of the type of the singleton.
+    pub fn type_name(&self) -> &'static str {
+        self.info().type_name()
+    }
+
+    /// Returns `Some(true)` if this is a lazy singleton that has been initialized,
+    /// `Some(false)` if is a lazy singleton not initialized yet,
+    /// or `None` if is not a lazy singleton.
+    pub fn is_initialized(&self) -> Option<bool> {
+        match self {
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(lazy, _) => Some(lazy.is_init()),
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(lazy, _) => Some(lazy.is_init()),
             _ => None,
         }
     }
 
+    /// Returns `true` if the singleton is constructed asynchronously.
+    pub fn is_async(&self) -> bool {
+        match self {
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(..) => true,
+            _ => false,
+        }
+    }
+
+    // Returns the value of the singleton if it has been created
+    pub(crate) fn instance(&self) -> Option<Arc<dyn Any + Send + Sync>> {
+        match self {
+            Shared::Instance(x, _) => Some(x.clone()),
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(lazy, _) => lazy.get().cloned(),
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(lazy, _) => lazy.get().cloned(),
+            Shared::__NonExhaustive(_) => None,
+        }
+    }
+
+    // Returns this provider without the lifetime of the container, sharing the same value
+    pub(crate) fn into_static(self) -> Shared<'static> {
+        match self {
+            Shared::Instance(x, info) => Shared::Instance(x, info),
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(lazy, info) => Shared::Lazy(lazy, info),
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(lazy, info) => Shared::AsyncLazy(lazy, info),
+            Shared::__NonExhaustive(_) => Shared::__NonExhaustive(&std::marker::PhantomData),
+        }
+    }
+
+    pub(crate) fn get<T>(&self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        match self {
+            Shared::Instance(x, _) => x.clone().downcast().ok(),
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(..) => None,
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(lazy, _) => lazy.get().and_then(|x| x.clone().downcast().ok()),
+            Shared::__NonExhaustive(_) => None,
+        }
+    }
+
     #[cfg(feature = "lazy")]
-    pub(crate) fn get_with<T>(&self, container: &'a Container<'a>) -> Option<Arc<T>>
+    pub(crate) fn get_with<T>(&self, container: &Container) -> Option<Arc<T>>
     where
         T: Send + Sync + 'static,
     {
         match self {
-            Shared::Instance(_) => None,
-            Shared::Lazy(lazy) => {
+            Shared::Instance(..) => None,
+            Shared::Lazy(lazy, _) => {
                 if lazy.is_init() {
                     lazy.get().and_then(|x| x.clone().downcast().ok())
                 } else {
-                    lazy.get_or_init(container).clone().downcast().ok()
+                    lazy.get_or_init_with(|init| init(container))
+                        .clone()
+                        .downcast()
+                        .ok()
                 }
             }
 
-            #[cfg(not(feature = "lazy"))]
-            _ => None,
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(..) => None,
+
+            Shared::__NonExhaustive(_) => None,
+        }
+    }
+
+    // Initializes the async singleton once, other callers wait until the value is ready
+    #[cfg(feature = "async")]
+    pub(crate) async fn get_async<T>(&self, container: &crate::Container<'_>) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        match self {
+            Shared::AsyncLazy(lazy, _) => lazy.get_or_init(container).await.clone().downcast().ok(),
+            _ => self.get(),
+        }
+    }
+}
+
+impl Debug for Shared<'_> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Shared::Instance(_, info) => write!(f, "Shared::Instance({})", info.type_name()),
+            #[cfg(feature = "lazy")]
+            Shared::Lazy(_, info) => write!(f, "Shared::Lazy({})", info.type_name()),
+            #[cfg(feature = "async")]
+            Shared::AsyncLazy(_, info) => write!(f, "Shared::AsyncLazy({})", info.type_name()),
+            Shared::__NonExhaustive(_) => write!(f, "Shared(..)"),
         }
     }
 }
@@ -128,10 +288,75 @@ pub mod late_init {
     {
         /// Initializes the instance if it has not been initialized and returns a reference to the value.
         pub fn get_or_init(&self, arg: Arg) -> &T {
+            self.get_or_init_with(|init| init(arg))
+        }
+    }
+
+    impl<T, Arg, F> LateInit<T, Arg, F> {
+        /// Initializes the instance calling the init function with `call` if it has not been initialized,
+        /// and returns a reference to the value.
+        ///
+        /// This allows to pass arguments to the init function that cannot be expressed by `Arg`,
+        /// like references with any lifetime.
+        pub fn get_or_init_with<C>(&self, call: C) -> &T
+        where
+            C: FnOnce(F) -> T,
+        {
             self.cell.get_or_init(|| match self.init.take() {
-                Some(init) => init(arg),
+                Some(init) => call(init),
                 None => panic!("LateInit instance has previously been poisoned"),
             })
         }
     }
 }
+
+/// A cell evaluated asynchronously.
+#[cfg(feature = "async")]
+pub mod async_late_init {
+    use crate::{Container, InjectFuture};
+    use std::any::Any;
+    use std::sync::Arc;
+    use tokio::sync::OnceCell;
+
+    type BoxedAny = Arc<dyn Any + Send + Sync>;
+    type BoxedAsyncFn =
+        Box<dyn for<'c> Fn(&'c Container<'c>) -> InjectFuture<'c, BoxedAny> + Send + Sync>;
+
+    /// A value initialized once from a future that takes the `Container`.
+    ///
+    /// If the future is cancelled other caller initializes the value.
+    pub struct AsyncLateInit {
+        cell: OnceCell<BoxedAny>,
+        init: BoxedAsyncFn,
+    }
+
+    impl AsyncLateInit {
+        /// Constructs a new `AsyncLateInit` with the given function.
+        pub fn new<F>(init: F) -> Self
+        where
+            F: for<'c> Fn(&'c Container<'c>) -> InjectFuture<'c, BoxedAny> + Send + Sync + 'static,
+        {
+            AsyncLateInit {
+                cell: OnceCell::new(),
+                init: Box::new(init),
+            }
+        }
+
+        /// Returns the value if it has been initialized, `None` otherwise.
+        #[inline]
+        pub fn get(&self) -> Option<&BoxedAny> {
+            self.cell.get()
+        }
+
+        /// Returns `true` if the value has been initialized.
+        #[inline]
+        pub fn is_init(&self) -> bool {
+            self.cell.initialized()
+        }
+
+        /// Initializes the value if it has not been initialized and returns a reference to it.
+        pub async fn get_or_init(&self, container: &Container<'_>) -> &BoxedAny {
+            self.cell.get_or_init(|| (self.init)(container)).await
+        }
+    }
+}
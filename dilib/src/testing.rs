@@ -0,0 +1,582 @@
+use crate::global::get_global_container;
+use crate::{Container, DuplicatePolicy, Inject, InjectionKey};
+use std::cell::RefCell;
+use std::fmt::{Debug, Formatter};
+use std::marker::PhantomData;
+use std::ops::{Deref, DerefMut};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+thread_local! {
+    // The override layers of the current thread, the last one is used by `get_container`
+    static OVERRIDES: RefCell<Vec<&'static Container<'static>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Pushes an override layer over the global [`Container`] for the current thread,
+/// the layer is removed when the returned [`OverrideGuard`] is dropped.
+///
+/// The layer is a scope of the global container or of the previous layer,
+/// see [`Container::create_scope`], so the providers added in `f` shadow the existing ones
+/// and the other providers are resolved from the global container.
+/// The scoped providers of the global container are resolved with the layer,
+/// so its dependencies are also overridden, but the singletons are constructed with the global container.
+///
+/// Each call leaks its layer, which is never dropped because the global container returns `'static` references
+/// that may outlive the guard, so this should only be used in tests.
+/// The guards must be dropped in the reverse order they were created.
+///
+/// # Example
+/// ```
+/// use dilib::global::{get_container, init_container};
+/// use dilib::testing::override_container;
+///
+/// init_container(|container| {
+///     container.add_scoped(|| String::from("postgres")).unwrap();
+/// })
+/// .unwrap();
+///
+/// {
+///     let _guard = override_container(|container| {
+///         container.add_scoped(|| String::from("in-memory")).unwrap();
+///     });
+///
+///     let db = get_container().unwrap().get_scoped::<String>().unwrap();
+///     assert_eq!(db, "in-memory");
+/// }
+///
+/// let db = get_container().unwrap().get_scoped::<String>().unwrap();
+/// assert_eq!(db, "postgres");
+/// ```
+pub fn override_container<F>(f: F) -> OverrideGuard
+where
+    F: FnOnce(&mut Container<'static>),
+{
+    let parent = current_override().or_else(get_global_container);
+    let mut layer = match parent {
+        Some(parent) => parent.create_scope(),
+        None => Container::new(),
+    };
+
+    f(&mut layer);
+
+    let layer: &'static Container<'static> = Box::leak(Box::new(layer));
+    let depth = OVERRIDES.with(|overrides| {
+        let mut overrides = overrides.borrow_mut();
+        overrides.push(layer);
+        overrides.len() - 1
+    });
+
+    OverrideGuard {
+        depth,
+        _marker: PhantomData,
+    }
+}
+
+/// Calls `body` with an override layer over the global [`Container`] for the current thread,
+/// the layer is removed after `body` returns, see [`override_container`].
+pub fn with_container_override<F, R>(f: F, body: impl FnOnce() -> R) -> R
+where
+    F: FnOnce(&mut Container<'static>),
+{
+    let _guard = override_container(f);
+    body()
+}
+
+/// Removes an override layer of the global [`Container`] when dropped.
+///
+/// The guards must be dropped in the reverse order they were created, dropping a guard
+/// also removes the layers pushed after it, which panics in debug builds.
+///
+/// The guard is not `Send` because the layer only applies to the thread that created it.
+#[must_use = "the override is removed when the guard is dropped"]
+pub struct OverrideGuard {
+    depth: usize,
+    _marker: PhantomData<*const ()>,
+}
+
+impl Drop for OverrideGuard {
+    fn drop(&mut self) {
+        // The thread local may be destroyed if the guard is dropped while the thread exits
+        let _ = OVERRIDES.try_with(|overrides| {
+            let mut overrides = overrides.borrow_mut();
+            let len = overrides.len();
+            overrides.truncate(self.depth);
+
+            // The inner layers would be removed while its guards are alive
+            if !std::thread::panicking() {
+                debug_assert!(
+                    len == self.depth + 1,
+                    "the override guards must be dropped in the reverse order they were created"
+                );
+            }
+        });
+    }
+}
+
+// Returns the last override layer of the current thread
+pub(crate) fn current_override() -> Option<&'static Container<'static>> {
+    OVERRIDES
+        .try_with(|overrides| overrides.borrow().last().copied())
+        .ok()
+        .flatten()
+}
+
+/// A [`Container`] for unit tests that records the resolution requests.
+///
+/// A `TestContainer` is created with [`TestContainer::builder`] and can be used as a `Container`,
+/// the types that have no fake can be resolved with its `Default` value using [`TestContainerBuilder::fallback`],
+/// and in [`TestContainerBuilder::strict`] mode resolving a key that was not configured panics
+/// instead of returning an error, so is not ignored by `Option<T>` dependencies.
+///
+/// # Example
+/// ```
+/// use dilib::testing::TestContainer;
+///
+/// #[derive(Default)]
+/// struct Logger;
+///
+/// let container = TestContainer::builder()
+///     .add_scoped_with_name("url", || String::from("sqlite::memory:"))
+///     .fallback::<Logger>()
+///     .strict()
+///     .build();
+///
+/// let url = container.get_scoped_with_name::<String>("url").unwrap();
+/// let _logger = container.get_scoped::<Logger>().unwrap();
+///
+/// assert_eq!(url, "sqlite::memory:");
+/// container.assert_resolved::<Logger>();
+/// assert_eq!(container.resolution_count_with_name::<String>("url"), 1);
+/// ```
+pub struct TestContainer {
+    container: Container<'static>,
+    recorder: Arc<Recorder>,
+}
+
+impl TestContainer {
+    /// Returns a builder to configure a `TestContainer`.
+    pub fn builder() -> TestContainerBuilder {
+        TestContainerBuilder::new()
+    }
+
+    /// Returns the keys resolved by this container and its scopes, in the order they were requested.
+    pub fn resolutions(&self) -> Vec<InjectionKey<'static>> {
+        self.recorder.resolutions().clone()
+    }
+
+    /// Returns the number of times the value of type `T` was requested.
+    pub fn resolution_count<T: 'static>(&self) -> usize {
+        self.recorder.count(&InjectionKey::of::<T>())
+    }
+
+    /// Returns the number of times the value of type `T` and the given name was requested.
+    pub fn resolution_count_with_name<T: 'static>(&self, name: &str) -> usize {
+        self.recorder.count(&InjectionKey::with_name::<T>(name))
+    }
+
+    /// Asserts the value of type `T` was requested at least once.
+    ///
+    /// # Panics
+    /// If the value was never requested.
+    #[track_caller]
+    pub fn assert_resolved<T: 'static>(&self) {
+        self.assert_resolved_key(InjectionKey::of::<T>());
+    }
+
+    /// Asserts the value of type `T` and the given name was requested at least once.
+    ///
+    /// # Panics
+    /// If the value was never requested.
+    #[track_caller]
+    pub fn assert_resolved_with_name<T: 'static>(&self, name: &str) {
+        self.assert_resolved_key(InjectionKey::with_name::<T>(name));
+    }
+
+    /// Asserts the value of type `T` was never requested.
+    ///
+    /// # Panics
+    /// If the value was requested.
+    #[track_caller]
+    pub fn assert_not_resolved<T: 'static>(&self) {
+        let key = InjectionKey::of::<T>();
+        let count = self.recorder.count(&key);
+        assert!(
+            count == 0,
+            "expected `{}` to not be resolved, but was resolved {} times",
+            key,
+            count
+        );
+    }
+
+    /// Removes the recorded resolution requests.
+    pub fn clear_resolutions(&self) {
+        self.recorder.resolutions().clear();
+    }
+
+    /// Returns the inner `Container`.
+    #[inline]
+    pub fn container(&self) -> &Container<'static> {
+        &self.container
+    }
+
+    #[track_caller]
+    fn assert_resolved_key(&self, key: InjectionKey) {
+        if self.recorder.count(&key) == 0 {
+            let resolved = self
+                .recorder
+                .resolutions()
+                .iter()
+                .map(|k| format!("`{}`", k))
+                .collect::<Vec<_>>();
+
+            panic!(
+                "expected `{}` to be resolved, resolved keys: [{}]",
+                key,
+                resolved.join(", ")
+            );
+        }
+    }
+}
+
+impl Deref for TestContainer {
+    type Target = Container<'static>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.container
+    }
+}
+
+impl DerefMut for TestContainer {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.container
+    }
+}
+
+impl Debug for TestContainer {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TestContainer")
+            .field("providers", &self.container.len())
+            .field("resolutions", &self.recorder.resolutions().len())
+            .field("strict", &self.recorder.strict)
+            .finish()
+    }
+}
+
+/// A builder to configure a [`TestContainer`].
+///
+/// The providers added to the builder replace the existing providers of the same key,
+/// so the last fake added is the one used.
+pub struct TestContainerBuilder {
+    container: Container<'static>,
+    strict: bool,
+}
+
+impl TestContainerBuilder {
+    fn new() -> Self {
+        TestContainerBuilder {
+            container: Container::with_duplicate_policy(DuplicatePolicy::Replace),
+            strict: false,
+        }
+    }
+
+    /// Adds a scoped provider.
+    pub fn add_scoped<T, F>(mut self, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.container.replace_scoped(f);
+        self
+    }
+
+    /// Adds a scoped provider with the given name.
+    pub fn add_scoped_with_name<T, F>(mut self, name: &str, f: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn() -> T + Send + Sync + 'static,
+    {
+        self.container.replace_scoped_with_name(name, f);
+        self
+    }
+
+    /// Adds a singleton.
+    pub fn add_singleton<T>(mut self, value: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.container.replace_singleton(value);
+        self
+    }
+
+    /// Adds a singleton with the given name.
+    pub fn add_singleton_with_name<T>(mut self, name: &str, value: T) -> Self
+    where
+        T: Send + Sync + 'static,
+    {
+        self.container.replace_singleton_with_name(name, value);
+        self
+    }
+
+    /// Adds a scoped provider constructed with [`Inject`].
+    pub fn add_deps<T>(mut self) -> Self
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        self.container.replace_deps::<T>();
+        self
+    }
+
+    /// Adds a scoped provider constructed with [`Inject`] with the given name.
+    pub fn add_deps_with_name<T>(mut self, name: &str) -> Self
+    where
+        T: Inject + Send + Sync + 'static,
+    {
+        self.container.replace_deps_with_name::<T>(name);
+        self
+    }
+
+    /// Configures the container directly, the providers added replace the existing ones.
+    pub fn configure<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Container<'static>),
+    {
+        f(&mut self.container);
+        self
+    }
+
+    /// Resolves the type `T` with `T::default()` if no other provider is added for it.
+    pub fn fallback<T>(mut self) -> Self
+    where
+        T: Default + Send + Sync + 'static,
+    {
+        self.container.try_add_scoped(T::default);
+        self
+    }
+
+    /// Resolves the type `T` with the given name with `T::default()` if no other provider is added for it.
+    pub fn fallback_with_name<T>(mut self, name: &str) -> Self
+    where
+        T: Default + Send + Sync + 'static,
+    {
+        self.container.try_add_scoped_with_name(name, T::default);
+        self
+    }
+
+    /// Panics when a key that have no provider is resolved, instead of returning an error.
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    /// Builds the [`TestContainer`], the providers added after this use the [`DuplicatePolicy::Error`].
+    pub fn build(self) -> TestContainer {
+        let TestContainerBuilder {
+            mut container,
+            strict,
+        } = self;
+
+        let recorder = Arc::new(Recorder {
+            resolutions: Mutex::new(Vec::new()),
+            strict,
+        });
+
+        container.set_duplicate_policy(DuplicatePolicy::Error);
+        container.set_recorder(recorder.clone());
+        TestContainer {
+            container,
+            recorder,
+        }
+    }
+}
+
+// Records the keys requested to a `TestContainer` and its scopes
+pub(crate) struct Recorder {
+    resolutions: Mutex<Vec<InjectionKey<'static>>>,
+    strict: bool,
+}
+
+impl Recorder {
+    pub(crate) fn record(&self, key: &InjectionKey, found: bool) {
+        let key = key.clone().into_owned();
+
+        // The lock is released before panicking, so the recorder can be used after the panic is caught
+        self.resolutions().push(key.clone());
+
+        if self.strict && !found {
+            panic!(
+                "`{}` was resolved but is not configured in the `TestContainer`",
+                key
+            );
+        }
+    }
+
+    fn count(&self, key: &InjectionKey) -> usize {
+        self.resolutions().iter().filter(|k| *k == key).count()
+    }
+
+    fn resolutions(&self) -> MutexGuard<'_, Vec<InjectionKey<'static>>> {
+        self.resolutions
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn override_without_global_container_test() {
+        // The global container may be initialized by other test, so we use a type not registered there
+        struct Repository(&'static str);
+
+        let guard = override_container(|container| {
+            container.add_scoped(|| Repository("fake")).unwrap();
+        });
+
+        let container = crate::global::get_container().unwrap();
+        assert_eq!(container.get_scoped::<Repository>().unwrap().0, "fake");
+
+        drop(guard);
+        let repository = crate::global::get_container()
+            .and_then(|container| container.get_scoped::<Repository>());
+
+        assert!(repository.is_none());
+    }
+
+    #[test]
+    fn nested_override_test() {
+        struct Value(i32);
+        struct Name(&'static str);
+
+        let _outer = override_container(|container| {
+            container.add_scoped(|| Value(1)).unwrap();
+            container.add_scoped(|| Name("outer")).unwrap();
+        });
+
+        with_container_override(
+            |container| {
+                container.add_scoped(|| Value(2)).unwrap();
+            },
+            || {
+                let container = crate::global::get_container().unwrap();
+                assert_eq!(container.get_scoped::<Value>().unwrap().0, 2);
+                assert_eq!(container.get_scoped::<Name>().unwrap().0, "outer");
+            },
+        );
+
+        let container = crate::global::get_container().unwrap();
+        assert_eq!(container.get_scoped::<Value>().unwrap().0, 1);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "the override guards must be dropped in the reverse order")]
+    fn override_guards_dropped_out_of_order_test() {
+        let outer = override_container(|_| {});
+        let _inner = override_container(|_| {});
+        drop(outer);
+    }
+
+    #[test]
+    fn override_is_thread_local_test() {
+        struct Flag;
+
+        let _guard = override_container(|container| {
+            container.add_scoped(|| Flag).unwrap();
+        });
+
+        let found = std::thread::spawn(|| {
+            crate::global::get_container()
+                .and_then(|container| container.get_scoped::<Flag>())
+                .is_some()
+        })
+        .join()
+        .unwrap();
+
+        assert!(!found);
+        assert!(crate::global::get_container()
+            .unwrap()
+            .get_scoped::<Flag>()
+            .is_some());
+    }
+
+    #[test]
+    fn test_container_fallback_test() {
+        #[derive(Default)]
+        struct Clock(u64);
+
+        let container = TestContainer::builder()
+            .fallback::<Clock>()
+            .fallback_with_name::<String>("greeting")
+            .add_scoped_with_name("greeting", || String::from("hello"))
+            .add_scoped(|| Clock(10))
+            .fallback::<Clock>()
+            .fallback::<u32>()
+            .build();
+
+        assert_eq!(container.get_scoped::<Clock>().unwrap().0, 10);
+        assert_eq!(container.get_scoped::<u32>().unwrap(), 0);
+        assert_eq!(
+            container
+                .get_scoped_with_name::<String>("greeting")
+                .unwrap(),
+            "hello"
+        );
+    }
+
+    #[test]
+    fn test_container_records_resolutions_test() {
+        let container = TestContainer::builder()
+            .add_scoped(|| 1_i32)
+            .add_singleton_with_name("name", String::from("dilib"))
+            .build();
+
+        container.assert_not_resolved::<i32>();
+        container.get_scoped::<i32>().unwrap();
+        container.get_scoped::<i32>().unwrap();
+        container.create_scope().get_scoped::<i32>().unwrap();
+        container.get_singleton_with_name::<String>("name").unwrap();
+        assert!(container.get_scoped::<bool>().is_none());
+
+        assert_eq!(container.resolution_count::<i32>(), 3);
+        assert_eq!(container.resolution_count_with_name::<String>("name"), 1);
+        assert_eq!(container.resolution_count::<String>(), 0);
+        container.assert_resolved::<bool>();
+        container.assert_resolved_with_name::<String>("name");
+        assert_eq!(
+            container.resolutions().last(),
+            Some(&InjectionKey::of::<bool>())
+        );
+
+        container.clear_resolutions();
+        assert!(container.resolutions().is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "expected `i32` to be resolved")]
+    fn test_container_assert_resolved_test() {
+        let container = TestContainer::builder().add_scoped(|| 1_i32).build();
+        container.assert_resolved::<i32>();
+    }
+
+    #[test]
+    #[should_panic(expected = "was resolved but is not configured in the `TestContainer`")]
+    fn test_container_strict_test() {
+        let container = TestContainer::builder()
+            .add_scoped(|| 1_i32)
+            .strict()
+            .build();
+
+        assert_eq!(container.get_scoped::<i32>().unwrap(), 1);
+        let _ = container.get_scoped::<String>();
+    }
+
+    #[test]
+    fn test_container_duplicate_after_build_test() {
+        let mut container = TestContainer::builder().add_scoped(|| 1_i32).build();
+
+        assert!(container.add_scoped(|| 2_i32).is_err());
+        assert!(container.add_scoped(|| String::from("added")).is_ok());
+        assert_eq!(container.get_scoped::<String>().unwrap(), "added");
+    }
+}
@@ -0,0 +1,426 @@
+use crate::{Container, DependencyInfo, InjectionKey, Provider, ProviderKind};
+use std::collections::HashMap;
+use std::error;
+use std::fmt::{Display, Formatter};
+
+/// A problem found when validating a [`Container`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ValidationIssue {
+    /// There is no provider for a dependency.
+    MissingDependency {
+        /// The key of the provider that requires the dependency.
+        provider: InjectionKey<'static>,
+        /// The dependency missing.
+        dependency: DependencyInfo,
+    },
+    /// The provider of a dependency is of other kind than the required.
+    ScopeMismatch {
+        /// The key of the provider that requires the dependency.
+        provider: InjectionKey<'static>,
+        /// The dependency required.
+        dependency: DependencyInfo,
+        /// The kind of the provider registered for the dependency.
+        found: ProviderKind,
+    },
+    /// A dependency without name have no provider, but there are providers
+    /// with a name for the same type, so is not clear which one should be used.
+    AmbiguousName {
+        /// The key of the provider that requires the dependency.
+        provider: InjectionKey<'static>,
+        /// The dependency required.
+        dependency: DependencyInfo,
+        /// The keys of the named providers of the same type.
+        candidates: Vec<InjectionKey<'static>>,
+    },
+    /// The dependencies form a cycle, starting and ending with the same key.
+    CircularDependency {
+        /// The keys of the cycle, for example: `A -> B -> A`.
+        cycle: Vec<InjectionKey<'static>>,
+    },
+}
+
+impl Display for ValidationIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationIssue::MissingDependency {
+                provider,
+                dependency,
+            } => write!(
+                f,
+                "`{}` requires missing dependency `{}`",
+                provider,
+                dependency.key()
+            ),
+            ValidationIssue::ScopeMismatch {
+                provider,
+                dependency,
+                found,
+            } => write!(
+                f,
+                "`{}` requires {} dependency `{}` but found {}",
+                provider,
+                dependency.kind(),
+                dependency.key(),
+                found
+            ),
+            ValidationIssue::AmbiguousName {
+                provider,
+                dependency,
+                candidates,
+            } => {
+                write!(
+                    f,
+                    "`{}` requires dependency `{}` without name, but only named providers exist: ",
+                    provider,
+                    dependency.key()
+                )?;
+
+                for (index, key) in candidates.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+
+                    write!(f, "`{}`", key)?;
+                }
+
+                Ok(())
+            }
+            ValidationIssue::CircularDependency { cycle } => {
+                write!(f, "circular dependency detected: ")?;
+
+                for (index, key) in cycle.iter().enumerate() {
+                    if index > 0 {
+                        write!(f, " -> ")?;
+                    }
+
+                    write!(f, "{}", key)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Error returned by [`Container::validate`] with all the problems found.
+#[derive(Debug, Clone)]
+pub struct ValidationError {
+    issues: Vec<ValidationIssue>,
+}
+
+impl ValidationError {
+    /// Returns the problems found in the `Container`.
+    #[inline]
+    pub fn issues(&self) -> &[ValidationIssue] {
+        &self.issues
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "container validation failed with {} issue(s):",
+            self.issues.len()
+        )?;
+
+        for issue in &self.issues {
+            write!(f, "\n  - {}", issue)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl error::Error for ValidationError {}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum VisitState {
+    Visiting,
+    Visited,
+}
+
+pub(crate) fn validate(container: &Container) -> Result<(), ValidationError> {
+    // Sorted by type name to report the issues always in the same order
+    let mut providers = container
+        .iter()
+        .map(|(key, provider)| (key.clone().into_owned(), provider))
+        .collect::<Vec<_>>();
+
+    providers.sort_by(|(a, _), (b, _)| {
+        (a.type_name(), a.name())
+            .cmp(&(b.type_name(), b.name()))
+            .then_with(|| a.cmp(b))
+    });
+
+    let mut issues = Vec::new();
+
+    for (key, provider) in &providers {
+        for dependency in provider.dependencies().iter().filter(|d| !d.has_default()) {
+            if let Some(issue) = check_dependency(container, key, dependency) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    let mut states = HashMap::new();
+    let mut stack = Vec::new();
+
+    for (key, provider) in &providers {
+        find_cycles(
+            container,
+            key,
+            provider,
+            &mut states,
+            &mut stack,
+            &mut issues,
+        );
+    }
+
+    if issues.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError { issues })
+    }
+}
+
+fn check_dependency(
+    container: &Container,
+    key: &InjectionKey<'static>,
+    dependency: &DependencyInfo,
+) -> Option<ValidationIssue> {
+    match container.get_provider(&dependency.key()) {
+        Some(found) if !dependency.is_any_kind() && found.kind() != dependency.kind() => {
+            Some(ValidationIssue::ScopeMismatch {
+                provider: key.clone(),
+                dependency: *dependency,
+                found: found.kind(),
+            })
+        }
+        Some(_) => None,
+        None => {
+            let mut candidates = container
+                .iter()
+                .map(|(k, _)| k)
+                .filter(|k| k.type_id() == dependency.type_id() && k.name().is_some())
+                .map(|k| k.clone().into_owned())
+                .collect::<Vec<_>>();
+
+            if dependency.name().is_none() && !candidates.is_empty() {
+                candidates.sort_by(|a, b| a.name().cmp(&b.name()));
+                Some(ValidationIssue::AmbiguousName {
+                    provider: key.clone(),
+                    dependency: *dependency,
+                    candidates,
+                })
+            } else {
+                Some(ValidationIssue::MissingDependency {
+                    provider: key.clone(),
+                    dependency: *dependency,
+                })
+            }
+        }
+    }
+}
+
+fn find_cycles(
+    container: &Container,
+    key: &InjectionKey<'static>,
+    provider: &Provider,
+    states: &mut HashMap<InjectionKey<'static>, VisitState>,
+    stack: &mut Vec<InjectionKey<'static>>,
+    issues: &mut Vec<ValidationIssue>,
+) {
+    match states.get(key) {
+        Some(VisitState::Visited) => return,
+        Some(VisitState::Visiting) => {
+            // SAFETY: a key being visited is always in the stack
+            let pos = stack.iter().position(|k| k == key).unwrap();
+            let mut cycle = stack[pos..].to_vec();
+            cycle.push(key.clone());
+            issues.push(ValidationIssue::CircularDependency { cycle });
+            return;
+        }
+        None => {}
+    }
+
+    states.insert(key.clone(), VisitState::Visiting);
+    stack.push(key.clone());
+
+    // Deferred dependencies are resolved after the value is constructed, so they don't form cycles
+    let dependencies = provider
+        .dependencies()
+        .iter()
+        .filter(|d| !d.has_default() && !d.is_deferred());
+
+    for dependency in dependencies {
+        let dependency_key = dependency.key();
+        if let Some(next) = container.get_provider(&dependency_key) {
+            find_cycles(container, &dependency_key, next, states, stack, issues);
+        }
+    }
+
+    stack.pop();
+    states.insert(key.clone(), VisitState::Visited);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Inject;
+
+    struct Database;
+
+    struct Repository;
+
+    impl Inject for Repository {
+        fn inject(_: &Container) -> Self {
+            Repository
+        }
+
+        fn dependencies() -> Vec<DependencyInfo> {
+            vec![DependencyInfo::singleton::<Database>()]
+        }
+    }
+
+    struct Service;
+
+    impl Inject for Service {
+        fn inject(_: &Container) -> Self {
+            Service
+        }
+
+        fn dependencies() -> Vec<DependencyInfo> {
+            vec![
+                DependencyInfo::scoped::<Repository>(),
+                DependencyInfo::scoped::<String>(),
+                DependencyInfo::scoped::<u32>().with_default(),
+            ]
+        }
+    }
+
+    #[test]
+    fn validate_ok_test() {
+        let mut container = Container::new();
+        container.add_singleton(Database).unwrap();
+        container.add_deps::<Repository>().unwrap();
+        container.add_deps::<Service>().unwrap();
+        container.add_scoped(String::new).unwrap();
+
+        assert!(container.validate().is_ok());
+    }
+
+    #[test]
+    fn validate_reports_all_issues_test() {
+        let mut container = Container::new();
+        container.add_scoped(|| Database).unwrap();
+        container.add_deps::<Repository>().unwrap();
+        container.add_deps::<Service>().unwrap();
+        container.add_scoped_with_name("a", String::new).unwrap();
+        container.add_scoped_with_name("b", String::new).unwrap();
+
+        let error = container.validate().unwrap_err();
+        assert_eq!(
+            error.issues(),
+            &[
+                ValidationIssue::ScopeMismatch {
+                    provider: InjectionKey::of::<Repository>(),
+                    dependency: DependencyInfo::singleton::<Database>(),
+                    found: ProviderKind::Scoped,
+                },
+                ValidationIssue::AmbiguousName {
+                    provider: InjectionKey::of::<Service>(),
+                    dependency: DependencyInfo::scoped::<String>(),
+                    candidates: vec![
+                        InjectionKey::with_name::<String>("a"),
+                        InjectionKey::with_name::<String>("b")
+                    ],
+                }
+            ]
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "lazy")]
+    fn validate_lazy_of_other_kind_test() {
+        struct Config(crate::Lazy<String>);
+
+        impl Inject for Config {
+            fn inject(container: &Container) -> Self {
+                Config(container.get_lazy::<String>().unwrap())
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<String>()
+                    .with_deferred()
+                    .with_any_kind()]
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_singleton(String::from("config")).unwrap();
+        container.add_deps::<Config>().unwrap();
+
+        // A `Lazy` resolves the singleton
+        assert!(container.validate().is_ok());
+        let config = container.get_scoped::<Config>().unwrap();
+        assert_eq!(config.0.get(), "config");
+    }
+
+    #[test]
+    fn validate_missing_dependency_test() {
+        let mut container = Container::new();
+        container.add_deps::<Repository>().unwrap();
+
+        let error = container.validate().unwrap_err();
+        assert_eq!(
+            error.issues(),
+            &[ValidationIssue::MissingDependency {
+                provider: InjectionKey::of::<Repository>(),
+                dependency: DependencyInfo::singleton::<Database>(),
+            }]
+        );
+        assert!(error.to_string().contains("requires missing dependency"));
+    }
+
+    #[test]
+    fn validate_circular_dependency_test() {
+        struct A;
+        struct B;
+
+        impl Inject for A {
+            fn inject(_: &Container) -> Self {
+                A
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<B>()]
+            }
+        }
+
+        impl Inject for B {
+            fn inject(_: &Container) -> Self {
+                B
+            }
+
+            fn dependencies() -> Vec<DependencyInfo> {
+                vec![DependencyInfo::scoped::<A>()]
+            }
+        }
+
+        let mut container = Container::new();
+        container.add_deps::<A>().unwrap();
+        container.add_deps::<B>().unwrap();
+
+        let error = container.validate().unwrap_err();
+        assert_eq!(error.issues().len(), 1);
+
+        match &error.issues()[0] {
+            ValidationIssue::CircularDependency { cycle } => {
+                assert_eq!(cycle.len(), 3);
+                assert_eq!(cycle.first(), cycle.last());
+            }
+            issue => panic!("unexpected issue: {}", issue),
+        }
+    }
+}
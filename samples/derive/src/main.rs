@@ -133,7 +133,7 @@ mod fruits {
         pub fn init(&self) -> Result<(), AlreadyInitialized> {
             let db = &self.0;
 
-            if db.read().unwrap().len() > 0 {
+            if !db.read().unwrap().is_empty() {
                 return Err(AlreadyInitialized);
             }
 
@@ -63,6 +63,7 @@ mod app {
     use std::sync::RwLock;
 
     pub trait Repository<T> {
+        #[allow(dead_code)]
         fn add(&self, item: T);
         fn get_all(&self) -> Vec<T>;
     }
@@ -78,7 +79,7 @@ mod app {
             T: Send + Sync + Clone + 'static,
         {
             let mut lock = self.0.write().unwrap();
-            let items = lock.entry(TypeId::of::<T>()).or_insert_with(Vec::new);
+            let items = lock.entry(TypeId::of::<T>()).or_default();
 
             for item in new_items {
                 items.push(Box::new(item));
@@ -94,8 +95,7 @@ mod app {
             if let Some(any_vec) = lock.get(&TypeId::of::<T>()) {
                 let vec = any_vec
                     .iter()
-                    .map(|any| any.downcast_ref::<T>().cloned())
-                    .flatten()
+                    .filter_map(|any| any.downcast_ref::<T>().cloned())
                     .collect::<Vec<T>>();
 
                 items.extend(vec);
@@ -141,7 +141,7 @@ mod app {
     pub struct DbSeeder(Singleton<Db>);
     impl DbSeeder {
         pub fn init_db(&self) -> Result<(), AlreadyInitialized> {
-            if self.0 .0.read().unwrap().len() > 0 {
+            if !self.0 .0.read().unwrap().is_empty() {
                 return Err(AlreadyInitialized);
             }
 
@@ -9,7 +9,7 @@ use uuid::Uuid;
 pub async fn get_all() -> impl Responder {
     let repository = resolve!(trait Repository<AuditLog, Uuid>).unwrap();
     let mut result = repository.get_all().await;
-    result.sort_by_key(|x| std::cmp::Reverse(x.created_at().clone()));
+    result.sort_by_key(|x| std::cmp::Reverse(*x.created_at()));
     HttpResponse::Ok().json(result)
 }
 
@@ -12,7 +12,7 @@ use crate::services::AuditLogService;
 use actix_web::middleware;
 use actix_web::{web, App, HttpServer};
 use dilib::add_scoped_trait;
-use dilib::global::init_container;
+use dilib::global::{init_container, shutdown_container_async};
 use entities::{AuditLog, TodoTask};
 use uuid::Uuid;
 
@@ -26,7 +26,7 @@ async fn main() -> std::io::Result<()> {
         .and_then(|port| port.parse::<u16>().ok())
         .unwrap_or(8080);
 
-    HttpServer::new(|| {
+    let result = HttpServer::new(|| {
         App::new()
             .wrap(middleware::NormalizePath::trim())
             .wrap(middleware::Logger::default())
@@ -49,12 +49,18 @@ async fn main() -> std::io::Result<()> {
     })
     .bind(("127.0.0.1", port))?
     .run()
-    .await
+    .await;
+
+    // Dispose the singletons before exit
+    shutdown_container_async().await;
+    result
 }
 
 #[allow(dead_code)]
 async fn init_dependency_injection() {
-    use crate::repositories::{InMemoryRepository, Repository, StorageRepository};
+    use crate::repositories::{
+        add_physical_storage, InMemoryRepository, Repository, StorageRepository,
+    };
 
     // Used for the sake of the example
     enum RepositoryType {
@@ -74,6 +80,7 @@ async fn init_dependency_injection() {
                     .unwrap();
             }
             RepositoryType::Storage => {
+                add_physical_storage(container);
                 add_scoped_trait!(container, Repository<TodoTask, Uuid> => StorageRepository::new("todo_tasks"))
                     .unwrap();
                 add_scoped_trait!(container, Repository<AuditLog, Uuid> => StorageRepository::new("audit_logs"))
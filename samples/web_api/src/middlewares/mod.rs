@@ -6,7 +6,7 @@ use actix_web::{
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     Error,
 };
-use dilib::resolve;
+use dilib::global::get_container;
 use futures_util::future::LocalBoxFuture;
 use std::any::TypeId;
 use std::future::{ready, Ready};
@@ -80,7 +80,9 @@ where
         let fut = self.service.call(req);
 
         Box::pin(async move {
-            let audit_log_service = resolve!(AuditLogService).unwrap();
+            // Each request have its own scope
+            let scope = get_container().unwrap().create_scope();
+            let audit_log_service = scope.get_scoped_singleton::<AuditLogService>().unwrap();
             let start_time = std::time::Instant::now();
             let res: Self::Response = fut.await?;
             let end_time = std::time::Instant::now();
@@ -117,7 +119,7 @@ fn generate_message<T: 'static>(_req: &ServiceRequest) -> Option<String> {
 
     let ty = Type::of::<T>();
     let message = format!("resource: {}", ty.name());
-    return Some(message);
+    Some(message)
 }
 
 fn can_log_method(method: &Method) -> bool {
@@ -4,7 +4,10 @@ use std::hash::Hash;
 mod memory;
 mod storage;
 
-pub use {memory::InMemoryRepository, storage::StorageRepository};
+pub use {
+    memory::InMemoryRepository,
+    storage::{add_physical_storage, StorageRepository},
+};
 
 pub trait Entity<Id> {
     fn id(&self) -> &Id;
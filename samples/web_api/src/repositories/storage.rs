@@ -1,26 +1,34 @@
 use crate::repositories::{Entity, Repository};
+use dilib::global::get_container;
+use dilib::{Container, Singleton};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::Value;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
 use std::hash::Hash;
-use std::io::ErrorKind;
 use std::marker::PhantomData;
 use std::path::PathBuf;
 use tokio::io::Error;
-use tokio::sync::{OnceCell, RwLock};
+use tokio::sync::RwLock;
 
 type DataMap = HashMap<String, HashMap<String, Value>>;
 
-static PHYSICAL_STORAGE: OnceCell<RwLock<PhysicalStorage>> = OnceCell::const_new();
-
-async fn get_physical_storage() -> &'static RwLock<PhysicalStorage> {
-    PHYSICAL_STORAGE
-        .get_or_init(|| async {
+/// Registers the storage used by the `StorageRepository`, which is loaded the first time is used.
+pub fn add_physical_storage(container: &mut Container) {
+    container
+        .add_async_singleton(|_| async {
             let physical_storage = PhysicalStorage::load("data/data.json").await.unwrap();
             RwLock::new(physical_storage)
         })
+        .unwrap();
+}
+
+async fn get_physical_storage() -> Singleton<RwLock<PhysicalStorage>> {
+    get_container()
+        .expect("container is not initialized")
+        .try_get_singleton_async::<RwLock<PhysicalStorage>>()
         .await
+        .unwrap()
 }
 
 struct PhysicalStorage {
@@ -47,7 +55,7 @@ impl PhysicalStorage {
         let json = tokio::fs::read_to_string(&path).await?;
         match serde_json::from_str::<DataMap>(&json) {
             Ok(data) => Ok(PhysicalStorage { inner: data, path }),
-            Err(e) => Err(Error::new(ErrorKind::Other, e)),
+            Err(e) => Err(Error::other(e)),
         }
     }
 
@@ -69,7 +77,7 @@ impl PhysicalStorage {
         if let Some(map) = self.inner.get(key) {
             let mut result = HashMap::new();
             for (map_id, map_key) in map {
-                let key = serde_json::from_str::<K>(&map_id).expect("Failed to parse key");
+                let key = serde_json::from_str::<K>(map_id).expect("Failed to parse key");
                 let value =
                     serde_json::from_value::<V>(map_key.clone()).expect("Failed to parse value");
                 result.insert(key, value);
@@ -134,13 +142,15 @@ where
     Id: Hash + Eq + Sync + Send + Clone + DeserializeOwned + Serialize + 'static,
 {
     async fn get_all(&self) -> Vec<T> {
-        let storage = get_physical_storage().await.read().await;
+        let storage = get_physical_storage().await;
+        let storage = storage.read().await;
         let map = storage.get_all::<Id, T>(&self.key).await.unwrap();
         map.values().cloned().collect()
     }
 
     async fn get(&self, id: Id) -> Option<T> {
-        let storage = get_physical_storage().await.read().await;
+        let storage = get_physical_storage().await;
+        let storage = storage.read().await;
         storage
             .with(&self.key, move |map: HashMap<Id, T>| map.get(&id).cloned())
             .await
@@ -148,7 +158,8 @@ where
     }
 
     async fn add(&mut self, entity: T) -> T {
-        let mut storage = get_physical_storage().await.write().await;
+        let storage = get_physical_storage().await;
+        let mut storage = storage.write().await;
         storage
             .with_save(&self.key, move |map: &mut HashMap<Id, T>| {
                 let id = entity.id().clone();
@@ -162,7 +173,8 @@ where
     }
 
     async fn update(&mut self, entity: T) -> Option<T> {
-        let mut storage = get_physical_storage().await.write().await;
+        let storage = get_physical_storage().await;
+        let mut storage = storage.write().await;
         storage
             .with_save(&self.key, move |map: &mut HashMap<Id, T>| {
                 let id = entity.id().clone();
@@ -180,7 +192,8 @@ where
     }
 
     async fn delete(&mut self, id: Id) -> Option<T> {
-        let mut storage = get_physical_storage().await.write().await;
+        let storage = get_physical_storage().await;
+        let mut storage = storage.write().await;
         storage
             .with_save(&self.key, move |map: &mut HashMap<Id, T>| map.remove(&id))
             .await
@@ -4,7 +4,8 @@ use dilib::{get_scoped_trait, provide, Container, Inject};
 use futures_util::lock::Mutex;
 use uuid::Uuid;
 
-#[provide]
+// One for each request scope
+#[provide(scope = "scoped_singleton")]
 pub struct AuditLogService {
     repository: Mutex<Box<dyn Repository<AuditLog, Uuid> + Send + Sync>>,
 }
@@ -48,7 +48,7 @@ impl PartialEq<Self> for Type {
 
 impl PartialOrd<Self> for Type {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.id.partial_cmp(&other.id)
+        Some(self.cmp(other))
     }
 }
 
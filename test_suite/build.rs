@@ -2,6 +2,8 @@ use std::env::var_os;
 use std::process::Command;
 
 fn main() {
+    println!("cargo:rustc-check-cfg=cfg(nightly)");
+
     // Checks whether we are on nightly and emit it to the compiler
     if is_nightly() {
         println!("cargo:rustc-cfg=nightly")
@@ -0,0 +1,50 @@
+use dilib::{AsyncInject, Singleton};
+use std::sync::Mutex;
+struct UserRepository {
+    pool: Singleton<Mutex<Vec<String>>>,
+    #[inject(name = "table")]
+    table: String,
+    #[inject(scope = "scoped_singleton")]
+    request_id: Singleton<usize>,
+    #[inject(default = 3_usize)]
+    retries: usize,
+}
+impl dilib::AsyncInject for UserRepository {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject_async<'c>(container: &'c dilib::Container<'c>) -> dilib::InjectFuture<'c, Self> {
+        std::boxed::Box::pin(async move {
+            let pool: dilib::Singleton<Mutex<Vec<String>>> = container
+                .try_get_singleton_async()
+                .await
+                .expect("cannot get singleton value of type `Mutex<Vec<String>>`");
+            let table: String = container
+                .try_get_scoped_async_with_name("table")
+                .await
+                .expect("cannot get scoped value of name \"table\"");
+            let request_id: dilib::Singleton<usize> = container
+                .try_get_scoped_singleton()
+                .expect("cannot get scoped singleton value of type `usize`");
+            let retries: usize = 3_usize.into();
+            UserRepository {
+                pool,
+                table,
+                request_id,
+                retries,
+            }
+        })
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for UserRepository {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<Mutex<Vec<String>>>(),
+            dilib::DependencyInfo::scoped::<String>().with_name("table"),
+            dilib::DependencyInfo::scoped_singleton::<usize>(),
+            dilib::DependencyInfo::scoped::<usize>().with_default(),
+        ])
+    }
+}
@@ -0,0 +1,16 @@
+use dilib::{AsyncInject, Singleton};
+use std::sync::Mutex;
+
+#[derive(AsyncInject)]
+struct UserRepository {
+    pool: Singleton<Mutex<Vec<String>>>,
+
+    #[inject(name="table")]
+    table: String,
+
+    #[inject(scope="scoped_singleton")]
+    request_id: Singleton<usize>,
+
+    #[inject(default=3_usize)]
+    retries: usize,
+}
\ No newline at end of file
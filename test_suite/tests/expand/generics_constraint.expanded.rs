@@ -9,14 +9,26 @@ impl<A: Clone, B: Default, C: Copy> dilib::Inject for StructWithConstraints<A, B
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: A = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `A`");
         let b: dilib::Singleton<B> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `B`");
         let c: Box<C> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<C>`");
         StructWithConstraints { a, b, c }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl<A: Clone, B: Default, C: Copy> dilib::InjectMetadata for StructWithConstraints<A, B, C> {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<A>(),
+            dilib::DependencyInfo::singleton::<B>(),
+            dilib::DependencyInfo::scoped::<Box<C>>(),
+        ])
+    }
 }
@@ -0,0 +1,34 @@
+use dilib::{Inject, Singleton};
+trait Handler {
+    fn handle(&self);
+}
+struct Pipeline {
+    #[inject(all)]
+    handlers: Vec<Box<dyn Handler + Send + Sync>>,
+    #[inject(all)]
+    hooks: Vec<Singleton<String>>,
+}
+impl dilib::Inject for Pipeline {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let handlers: std::vec::Vec<Box<dyn Handler + Send + Sync>> = container
+            .try_get_all_scoped()
+            .expect("cannot get all the scoped values of type `Box<dynHandler+Send+Sync>`");
+        let hooks: std::vec::Vec<dilib::Singleton<String>> = container
+            .try_get_all_singletons()
+            .expect("cannot get all the singleton values of type `String`");
+        Pipeline { handlers, hooks }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Pipeline {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<Box<dyn Handler + Send + Sync>>().with_default(),
+            dilib::DependencyInfo::singleton::<String>().with_default(),
+        ])
+    }
+}
@@ -0,0 +1,13 @@
+use dilib::{Inject, Singleton};
+
+trait Handler {
+    fn handle(&self);
+}
+
+#[derive(Inject)]
+struct Pipeline {
+    #[inject(all)]
+    handlers: Vec<Box<dyn Handler + Send + Sync>>,
+    #[inject(all)]
+    hooks: Vec<Singleton<String>>,
+}
\ No newline at end of file
@@ -10,16 +10,28 @@ impl dilib::Inject for StructInjectConstructor {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: String = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `String`");
         let b: dilib::Singleton<usize> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `usize`");
         let c: Box<bool> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<bool>`");
         StructInjectConstructor::new(a, b, c)
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructInjectConstructor {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>(),
+            dilib::DependencyInfo::singleton::<usize>(),
+            dilib::DependencyInfo::scoped::<Box<bool>>(),
+        ])
+    }
 }
 impl StructInjectConstructor {
     pub fn new(x: String, y: Singleton<usize>, z: Box<bool>) -> Self {
@@ -0,0 +1,56 @@
+use std::sync::Arc;
+use dilib::{Singleton, Inject};
+struct Repository;
+#[inject(
+    constructor = "new(label, repo: Arc<Repository> = name(\"main\"), cache: Singleton<String> = scope(\"scoped_singleton\"), retries = 3, verbose = true)"
+)]
+struct Service {
+    label: String,
+    ignored: Box<bool>,
+    retries: u32,
+    verbose: bool,
+}
+impl dilib::Inject for Service {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let label: String = container
+            .try_get_scoped()
+            .expect("cannot get scoped value of type `String`");
+        let repo: dilib::Singleton<Repository> = container
+            .try_get_singleton_with_name("main")
+            .expect("cannot get singleton value of name \"main\"");
+        let cache: dilib::Singleton<String> = container
+            .try_get_scoped_singleton()
+            .expect("cannot get scoped singleton value of type `String`");
+        Service::new(label, repo, cache, 3, true)
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Service {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>(),
+            dilib::DependencyInfo::singleton::<Repository>().with_name("main"),
+            dilib::DependencyInfo::scoped_singleton::<String>(),
+        ])
+    }
+}
+impl Service {
+    pub fn new(
+        label: String,
+        repo: Arc<Repository>,
+        cache: Singleton<String>,
+        retries: u32,
+        verbose: bool,
+    ) -> Self {
+        Service {
+            label,
+            ignored: Box::new(false),
+            retries,
+            verbose,
+        }
+    }
+}
@@ -0,0 +1,19 @@
+use std::sync::Arc;
+use dilib::{Singleton, Inject};
+
+struct Repository;
+
+#[derive(Inject)]
+#[inject(constructor="new(label, repo: Arc<Repository> = name(\"main\"), cache: Singleton<String> = scope(\"scoped_singleton\"), retries = 3, verbose = true)")]
+struct Service {
+    label: String,
+    ignored: Box<bool>,
+    retries: u32,
+    verbose: bool
+}
+
+impl Service {
+    pub fn new(label: String, repo: Arc<Repository>, cache: Singleton<String>, retries: u32, verbose: bool) -> Self {
+        Service { label, ignored: Box::new(false), retries, verbose }
+    }
+}
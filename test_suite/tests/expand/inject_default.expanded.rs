@@ -16,4 +16,16 @@ impl dilib::Inject for StructInjectDefault {
         let c: bool = std::default::Default::default();
         StructInjectDefault { a, b, c }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructInjectDefault {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>().with_default(),
+            dilib::DependencyInfo::scoped::<usize>().with_default(),
+            dilib::DependencyInfo::scoped::<bool>().with_default(),
+        ])
+    }
 }
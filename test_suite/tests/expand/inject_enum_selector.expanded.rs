@@ -0,0 +1,58 @@
+use dilib::{Singleton, Inject};
+#[inject(selector = "cache")]
+enum Cache {
+    Memory,
+    Redis(#[inject(name = "REDIS_URL")] String),
+    File {
+        path: String,
+        capacity: Singleton<usize>,
+    },
+}
+impl dilib::Inject for Cache {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let selector: std::string::String = container
+            .try_get_scoped_with_name("cache")
+            .expect("cannot get scoped value of name \"cache\"");
+        match selector.as_str() {
+            "Memory" => Cache::Memory,
+            "Redis" => {
+                let _0: String = container
+                    .try_get_scoped_with_name("REDIS_URL")
+                    .expect("cannot get scoped value of name \"REDIS_URL\"");
+                Cache::Redis(_0)
+            }
+            "File" => {
+                let path: String = container
+                    .try_get_scoped()
+                    .expect("cannot get scoped value of type `String`");
+                let capacity: dilib::Singleton<usize> = container
+                    .try_get_singleton()
+                    .expect("cannot get singleton value of type `usize`");
+                Cache::File { path, capacity }
+            }
+            other => {
+                ::core::panicking::panic_fmt(format_args!(
+                    "unknown variant `{0}` of `Cache` selected by \"cache\"",
+                    other
+                ));
+            }
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Cache {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<std::string::String>().with_name("cache"),
+            dilib::DependencyInfo::scoped::<String>()
+                .with_name("REDIS_URL")
+                .with_default(),
+            dilib::DependencyInfo::scoped::<String>().with_default(),
+            dilib::DependencyInfo::singleton::<usize>().with_default(),
+        ])
+    }
+}
@@ -0,0 +1,12 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+#[inject(selector="cache")]
+enum Cache {
+    Memory,
+    Redis(#[inject(name="REDIS_URL")] String),
+    File {
+        path: String,
+        capacity: Singleton<usize>
+    }
+}
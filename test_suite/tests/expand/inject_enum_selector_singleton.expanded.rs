@@ -0,0 +1,58 @@
+use dilib::{Singleton, Inject};
+#[inject(selector = "storage", scope = "singleton")]
+enum Storage {
+    Disk {
+        container: Singleton<String>,
+    },
+    Remote {
+        container1: String,
+        region: Singleton<usize>,
+    },
+    Nothing,
+}
+impl dilib::Inject for Storage {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container2: &dilib::Container) -> Self {
+        let selector: dilib::Singleton<std::string::String> = container2
+            .try_get_singleton_with_name("storage")
+            .expect("cannot get singleton value of name \"storage\"");
+        match selector.as_str() {
+            "Disk" => {
+                let container: dilib::Singleton<String> = container2
+                    .try_get_singleton()
+                    .expect("cannot get singleton value of type `String`");
+                Storage::Disk { container }
+            }
+            "Remote" => {
+                let container1: String = container2
+                    .try_get_scoped()
+                    .expect("cannot get scoped value of type `String`");
+                let region: dilib::Singleton<usize> = container2
+                    .try_get_singleton()
+                    .expect("cannot get singleton value of type `usize`");
+                Storage::Remote { container1, region }
+            }
+            "Nothing" => Storage::Nothing,
+            other => {
+                ::core::panicking::panic_fmt(format_args!(
+                    "unknown variant `{0}` of `Storage` selected by \"storage\"",
+                    other
+                ));
+            }
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Storage {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<std::string::String>().with_name("storage"),
+            dilib::DependencyInfo::singleton::<String>().with_default(),
+            dilib::DependencyInfo::scoped::<String>().with_default(),
+            dilib::DependencyInfo::singleton::<usize>().with_default(),
+        ])
+    }
+}
@@ -0,0 +1,14 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+#[inject(selector="storage", scope="singleton")]
+enum Storage {
+    Disk {
+        container: Singleton<String>
+    },
+    Remote {
+        container1: String,
+        region: Singleton<usize>
+    },
+    Nothing
+}
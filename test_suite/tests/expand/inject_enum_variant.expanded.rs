@@ -0,0 +1,34 @@
+use dilib::{Singleton, Inject};
+#[inject(variant = "Postgres")]
+enum Database {
+    Memory,
+    Postgres {
+        #[inject(name = "DATABASE_URL")]
+        url: String,
+        pool_size: Singleton<usize>,
+    },
+}
+impl dilib::Inject for Database {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let url: String = container
+            .try_get_scoped_with_name("DATABASE_URL")
+            .expect("cannot get scoped value of name \"DATABASE_URL\"");
+        let pool_size: dilib::Singleton<usize> = container
+            .try_get_singleton()
+            .expect("cannot get singleton value of type `usize`");
+        Database::Postgres { url, pool_size }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Database {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>().with_name("DATABASE_URL"),
+            dilib::DependencyInfo::singleton::<usize>(),
+        ])
+    }
+}
@@ -0,0 +1,12 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+#[inject(variant="Postgres")]
+enum Database {
+    Memory,
+    Postgres {
+        #[inject(name="DATABASE_URL")]
+        url: String,
+        pool_size: Singleton<usize>
+    }
+}
@@ -7,8 +7,16 @@ impl dilib::Inject for MyStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let scoped: std::vec::Vec<String> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `std::vec::Vec<String>`");
         MyStruct { scoped }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::scoped::<std::vec::Vec<String>>()])
+    }
 }
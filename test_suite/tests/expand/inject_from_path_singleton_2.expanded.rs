@@ -7,8 +7,18 @@ impl dilib::Inject for MyStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let singleton: dilib::Singleton<std::option::Option<String>> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `std::option::Option<String>`");
         MyStruct { singleton }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::singleton::<
+            std::option::Option<String>,
+        >()])
+    }
 }
@@ -0,0 +1,48 @@
+use dilib::{Factory, Inject, Lazy};
+struct Report;
+struct Connection;
+struct ReportService {
+    report: Lazy<Report>,
+    #[inject(name = "main")]
+    connections: Factory<Connection>,
+    #[inject(scope = "singleton")]
+    config: dilib::Lazy<String>,
+}
+impl dilib::Inject for ReportService {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let report: dilib::Lazy<Report> = container
+            .try_get_lazy()
+            .expect("cannot get lazy value of type `Report`");
+        let connections: dilib::Factory<Connection> = container
+            .try_get_factory_with_name("main")
+            .expect("cannot get factory value of name \"main\"");
+        let config: dilib::Lazy<String> = container
+            .try_get_lazy()
+            .expect("cannot get lazy value of type `String`");
+        ReportService {
+            report,
+            connections,
+            config,
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for ReportService {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<Report>()
+                .with_deferred()
+                .with_any_kind(),
+            dilib::DependencyInfo::scoped::<Connection>()
+                .with_name("main")
+                .with_deferred(),
+            dilib::DependencyInfo::singleton::<String>()
+                .with_deferred()
+                .with_any_kind(),
+        ])
+    }
+}
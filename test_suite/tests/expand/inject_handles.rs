@@ -0,0 +1,13 @@
+use dilib::{Factory, Inject, Lazy};
+
+struct Report;
+struct Connection;
+
+#[derive(Inject)]
+struct ReportService {
+    report: Lazy<Report>,
+    #[inject(name="main")]
+    connections: Factory<Connection>,
+    #[inject(scope="singleton")]
+    config: dilib::Lazy<String>,
+}
\ No newline at end of file
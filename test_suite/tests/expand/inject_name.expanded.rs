@@ -12,14 +12,26 @@ impl dilib::Inject for StructInjectName {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: String = container
-            .get_scoped_with_name("API_KEY")
+            .try_get_scoped_with_name("API_KEY")
             .expect("cannot get scoped value of name \"API_KEY\"");
         let b: dilib::Singleton<usize> = container
-            .get_singleton_with_name("count")
+            .try_get_singleton_with_name("count")
             .expect("cannot get singleton value of name \"count\"");
         let c: Box<bool> = container
-            .get_scoped_with_name("named")
+            .try_get_scoped_with_name("named")
             .expect("cannot get scoped value of name \"named\"");
         StructInjectName { a, b, c }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructInjectName {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>().with_name("API_KEY"),
+            dilib::DependencyInfo::singleton::<usize>().with_name("count"),
+            dilib::DependencyInfo::scoped::<Box<bool>>().with_name("named"),
+        ])
+    }
 }
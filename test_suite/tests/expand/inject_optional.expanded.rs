@@ -0,0 +1,44 @@
+use dilib::{Inject, Singleton};
+struct Cache;
+struct Metrics;
+struct Service {
+    cache: Option<Cache>,
+    #[inject(name = "metrics")]
+    metrics: Option<Singleton<Metrics>>,
+    #[inject(optional = false)]
+    retries: Option<u32>,
+}
+impl dilib::Inject for Service {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let cache: std::option::Option<Cache> =
+            dilib::ResolveResultExt::optional(container.try_get_scoped())
+                .expect("cannot get scoped value of type `Cache`");
+        let metrics: std::option::Option<dilib::Singleton<Metrics>> =
+            dilib::ResolveResultExt::optional(container.try_get_singleton_with_name("metrics"))
+                .expect("cannot get singleton value of name \"metrics\"");
+        let retries: Option<u32> = container
+            .try_get_scoped()
+            .expect("cannot get scoped value of type `Option<u32>`");
+        Service {
+            cache,
+            metrics,
+            retries,
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Service {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<Cache>().with_default(),
+            dilib::DependencyInfo::singleton::<Metrics>()
+                .with_name("metrics")
+                .with_default(),
+            dilib::DependencyInfo::scoped::<Option<u32>>(),
+        ])
+    }
+}
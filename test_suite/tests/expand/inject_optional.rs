@@ -0,0 +1,13 @@
+use dilib::{Inject, Singleton};
+
+struct Cache;
+struct Metrics;
+
+#[derive(Inject)]
+struct Service {
+    cache: Option<Cache>,
+    #[inject(name="metrics")]
+    metrics: Option<Singleton<Metrics>>,
+    #[inject(optional=false)]
+    retries: Option<u32>,
+}
\ No newline at end of file
@@ -0,0 +1,137 @@
+use dilib::{Singleton, Inject, Container};
+fn register(worker: &mut Worker, _: &Container) {
+    worker.registered = true;
+}
+#[inject(post_construct = "start")]
+struct Server {
+    port: Singleton<u16>,
+    #[inject(default)]
+    started: bool,
+}
+impl dilib::Inject for Server {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let port: dilib::Singleton<u16> = container
+            .try_get_singleton()
+            .expect("cannot get singleton value of type `u16`");
+        let started: bool = std::default::Default::default();
+        {
+            let mut value = Server { port, started };
+            Self::start(&mut value, container);
+            value
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Server {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<u16>(),
+            dilib::DependencyInfo::scoped::<bool>().with_default(),
+        ])
+    }
+}
+impl Server {
+    fn start(&mut self, _: &Container) {
+        self.started = true;
+    }
+}
+#[inject(post_construct = "crate::register")]
+struct Worker {
+    #[inject(default)]
+    registered: bool,
+}
+impl dilib::Inject for Worker {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let registered: bool = std::default::Default::default();
+        {
+            let mut value = Worker { registered };
+            crate::register(&mut value, container);
+            value
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Worker {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::scoped::<bool>().with_default()])
+    }
+}
+#[inject(post_construct = "Self::start")]
+struct Empty;
+impl dilib::Inject for Empty {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        {
+            let mut value = Empty;
+            Self::start(&mut value, container);
+            value
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Empty {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::new()
+    }
+}
+impl Empty {
+    fn start(&mut self, _: &Container) {}
+}
+#[inject(selector = "mode", post_construct = "Mode::check")]
+enum Mode {
+    Debug,
+    Release(Singleton<u16>),
+}
+impl dilib::Inject for Mode {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let selector: std::string::String = container
+            .try_get_scoped_with_name("mode")
+            .expect("cannot get scoped value of name \"mode\"");
+        {
+            let mut value = match selector.as_str() {
+                "Debug" => Mode::Debug,
+                "Release" => {
+                    let _0: dilib::Singleton<u16> = container
+                        .try_get_singleton()
+                        .expect("cannot get singleton value of type `u16`");
+                    Mode::Release(_0)
+                }
+                other => {
+                    ::core::panicking::panic_fmt(format_args!(
+                        "unknown variant `{0}` of `Mode` selected by \"mode\"",
+                        other
+                    ));
+                }
+            };
+            Mode::check(&mut value, container);
+            value
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Mode {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<std::string::String>().with_name("mode"),
+            dilib::DependencyInfo::singleton::<u16>().with_default(),
+        ])
+    }
+}
+impl Mode {
+    fn check(&mut self, _: &Container) {}
+}
@@ -0,0 +1,46 @@
+use dilib::{Singleton, Inject, Container};
+
+fn register(worker: &mut Worker, _: &Container) {
+    worker.registered = true;
+}
+
+#[derive(Inject)]
+#[inject(post_construct="start")]
+struct Server {
+    port: Singleton<u16>,
+
+    #[inject(default)]
+    started: bool
+}
+
+impl Server {
+    fn start(&mut self, _: &Container) {
+        self.started = true;
+    }
+}
+
+#[derive(Inject)]
+#[inject(post_construct="crate::register")]
+struct Worker {
+    #[inject(default)]
+    registered: bool
+}
+
+#[derive(Inject)]
+#[inject(post_construct="Self::start")]
+struct Empty;
+
+impl Empty {
+    fn start(&mut self, _: &Container) {}
+}
+
+#[derive(Inject)]
+#[inject(selector="mode", post_construct="Mode::check")]
+enum Mode {
+    Debug,
+    Release(Singleton<u16>)
+}
+
+impl Mode {
+    fn check(&mut self, _: &Container) {}
+}
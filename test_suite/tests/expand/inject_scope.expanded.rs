@@ -12,11 +12,22 @@ impl dilib::Inject for StructInjectScope {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: dilib::Singleton<UsizeSingleton> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `UsizeSingleton`");
         let b: Box<i32> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<i32>`");
         StructInjectScope { a, b }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructInjectScope {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<UsizeSingleton>(),
+            dilib::DependencyInfo::scoped::<Box<i32>>(),
+        ])
+    }
 }
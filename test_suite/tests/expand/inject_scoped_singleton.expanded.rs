@@ -0,0 +1,32 @@
+use dilib::{Singleton, Inject};
+use std::sync::Mutex;
+struct RequestHandler {
+    #[inject(scope = "scoped_singleton")]
+    log: Singleton<Mutex<Vec<String>>>,
+    #[inject(scope = "scoped_singleton", name = "request_id")]
+    id: Singleton<usize>,
+}
+impl dilib::Inject for RequestHandler {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let log: dilib::Singleton<Mutex<Vec<String>>> = container
+            .try_get_scoped_singleton()
+            .expect("cannot get scoped singleton value of type `Mutex<Vec<String>>`");
+        let id: dilib::Singleton<usize> = container
+            .try_get_scoped_singleton_with_name("request_id")
+            .expect("cannot get scoped singleton value of name \"request_id\"");
+        RequestHandler { log, id }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for RequestHandler {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped_singleton::<Mutex<Vec<String>>>(),
+            dilib::DependencyInfo::scoped_singleton::<usize>().with_name("request_id"),
+        ])
+    }
+}
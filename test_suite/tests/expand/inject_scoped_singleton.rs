@@ -0,0 +1,11 @@
+use dilib::{Singleton, Inject};
+use std::sync::Mutex;
+
+#[derive(Inject)]
+struct RequestHandler {
+    #[inject(scope="scoped_singleton")]
+    log: Singleton<Mutex<Vec<String>>>,
+
+    #[inject(scope="scoped_singleton", name="request_id")]
+    id: Singleton<usize>,
+}
\ No newline at end of file
@@ -10,8 +10,16 @@ impl dilib::Inject for MyStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let scoped: Box<dyn SomeTrait> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<dynSomeTrait>`");
         MyStruct { scoped }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::scoped::<Box<dyn SomeTrait>>()])
+    }
 }
@@ -0,0 +1,121 @@
+use dilib::{Singleton, Inject, TryInject, Container};
+struct Connection(String);
+fn connect(container: &Container) -> Connection {
+    Connection(container.get_scoped_with_name::<String>("url").unwrap())
+}
+fn parse_port(port: String) -> u16 {
+    port.parse().unwrap()
+}
+fn pool_size(size: Singleton<usize>) -> usize {
+    *size
+}
+struct Database {
+    #[inject(with = "connect")]
+    connection: Connection,
+    #[inject(map = "parse_port")]
+    port: u16,
+    #[inject(map = "parse_port", name = "backup_port")]
+    backup_port: u16,
+    #[inject(map = "pool_size", scope = "singleton")]
+    pool_size: usize,
+}
+impl dilib::Inject for Database {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(container: &dilib::Container) -> Self {
+        let connection: Connection = connect(container);
+        let port: u16 = parse_port(
+            container
+                .try_get_scoped()
+                .expect("cannot get scoped value for `parse_port`"),
+        );
+        let backup_port: u16 = parse_port(
+            container
+                .try_get_scoped_with_name("backup_port")
+                .expect("cannot get scoped value of name \"backup_port\""),
+        );
+        let pool_size: usize = pool_size(
+            container
+                .try_get_singleton()
+                .expect("cannot get singleton value for `pool_size`"),
+        );
+        Database {
+            connection,
+            port,
+            backup_port,
+            pool_size,
+        }
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Database {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            {
+                fn info<T: 'static, R>(_: fn(T) -> R) -> dilib::DependencyInfo {
+                    dilib::DependencyInfo::scoped::<T>()
+                }
+                info(parse_port)
+            },
+            {
+                fn info<T: 'static, R>(_: fn(T) -> R) -> dilib::DependencyInfo {
+                    dilib::DependencyInfo::scoped::<T>()
+                }
+                info(parse_port)
+            }
+            .with_name("backup_port"),
+            {
+                fn info<T: 'static, R>(_: fn(dilib::Singleton<T>) -> R) -> dilib::DependencyInfo {
+                    dilib::DependencyInfo::singleton::<T>()
+                }
+                info(pool_size)
+            },
+        ])
+    }
+}
+struct TryDatabase {
+    #[inject(with = "connect")]
+    connection: Connection,
+    #[inject(map = "parse_port", name = "port")]
+    port: u16,
+    #[inject(map = "pool_size", scope = "singleton")]
+    pool_size: usize,
+}
+impl dilib::TryInject for TryDatabase {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn try_inject(container: &dilib::Container) -> std::result::Result<Self, dilib::ResolveError> {
+        let connection: Connection = connect(container);
+        let port: u16 = parse_port(container.try_get_scoped_with_name("port")?);
+        let pool_size: usize = pool_size(container.try_get_singleton()?);
+        std::result::Result::Ok(TryDatabase {
+            connection,
+            port,
+            pool_size,
+        })
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for TryDatabase {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            {
+                fn info<T: 'static, R>(_: fn(T) -> R) -> dilib::DependencyInfo {
+                    dilib::DependencyInfo::scoped::<T>()
+                }
+                info(parse_port)
+            }
+            .with_name("port"),
+            {
+                fn info<T: 'static, R>(_: fn(dilib::Singleton<T>) -> R) -> dilib::DependencyInfo {
+                    dilib::DependencyInfo::singleton::<T>()
+                }
+                info(pool_size)
+            },
+        ])
+    }
+}
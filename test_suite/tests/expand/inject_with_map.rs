@@ -0,0 +1,42 @@
+use dilib::{Singleton, Inject, TryInject, Container};
+
+struct Connection(String);
+
+fn connect(container: &Container) -> Connection {
+    Connection(container.get_scoped_with_name::<String>("url").unwrap())
+}
+
+fn parse_port(port: String) -> u16 {
+    port.parse().unwrap()
+}
+
+fn pool_size(size: Singleton<usize>) -> usize {
+    *size
+}
+
+#[derive(Inject)]
+struct Database {
+    #[inject(with="connect")]
+    connection: Connection,
+
+    #[inject(map="parse_port")]
+    port: u16,
+
+    #[inject(map="parse_port", name="backup_port")]
+    backup_port: u16,
+
+    #[inject(map="pool_size", scope="singleton")]
+    pool_size: usize,
+}
+
+#[derive(TryInject)]
+struct TryDatabase {
+    #[inject(with="connect")]
+    connection: Connection,
+
+    #[inject(map="parse_port", name="port")]
+    port: u16,
+
+    #[inject(map="pool_size", scope="singleton")]
+    pool_size: usize,
+}
@@ -9,14 +9,26 @@ impl<'a, 'b> dilib::Inject for StructWithLifeTimes<'a, 'b> {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: &'a String = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `&String`");
         let b: Singleton<&'a usize> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Singleton<&usize>`");
         let c: &'static str = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `&str`");
         StructWithLifeTimes { a, b, c }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl<'a, 'b> dilib::InjectMetadata for StructWithLifeTimes<'a, 'b> {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<&'a String>(),
+            dilib::DependencyInfo::scoped::<Singleton<&'a usize>>(),
+            dilib::DependencyInfo::scoped::<&'static str>(),
+        ])
+    }
 }
@@ -9,14 +9,26 @@ impl dilib::Inject for InjectStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: dilib::Singleton<usize> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `usize`");
         let b: String = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `String`");
         let c: Box<bool> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<bool>`");
         InjectStruct { a, b, c }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for InjectStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<usize>(),
+            dilib::DependencyInfo::scoped::<String>(),
+            dilib::DependencyInfo::scoped::<Box<bool>>(),
+        ])
+    }
 }
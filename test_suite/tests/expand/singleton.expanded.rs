@@ -9,11 +9,22 @@ impl dilib::Inject for StructSingleton {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let a: dilib::Singleton<usize> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `usize`");
         let b: dilib::Singleton<Mutex<usize>> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `Mutex<usize>`");
         StructSingleton { a, b }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructSingleton {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<usize>(),
+            dilib::DependencyInfo::singleton::<Mutex<usize>>(),
+        ])
+    }
 }
@@ -0,0 +1,30 @@
+use dilib::{Singleton, TryInject};
+struct StructTryInject {
+    #[inject(name = "API_KEY")]
+    a: String,
+    b: Singleton<usize>,
+    #[inject(default)]
+    c: bool,
+}
+impl dilib::TryInject for StructTryInject {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn try_inject(container: &dilib::Container) -> std::result::Result<Self, dilib::ResolveError> {
+        let a: String = container.try_get_scoped_with_name("API_KEY")?;
+        let b: dilib::Singleton<usize> = container.try_get_singleton()?;
+        let c: bool = std::default::Default::default();
+        std::result::Result::Ok(StructTryInject { a, b, c })
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for StructTryInject {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<String>().with_name("API_KEY"),
+            dilib::DependencyInfo::singleton::<usize>(),
+            dilib::DependencyInfo::scoped::<bool>().with_default(),
+        ])
+    }
+}
@@ -0,0 +1,12 @@
+use dilib::{Singleton, TryInject};
+
+#[derive(TryInject)]
+struct StructTryInject {
+    #[inject(name="API_KEY")]
+    a: String,
+
+    b: Singleton<usize>,
+
+    #[inject(default)]
+    c: bool
+}
\ No newline at end of file
@@ -0,0 +1,50 @@
+use dilib::{Singleton, TryInject};
+#[inject(selector = "queue")]
+enum Queue {
+    Local(Singleton<usize>),
+    Remote {
+        #[inject(name = "QUEUE_URL")]
+        url: String,
+    },
+}
+impl dilib::TryInject for Queue {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn try_inject(container: &dilib::Container) -> std::result::Result<Self, dilib::ResolveError> {
+        let selector: std::string::String = container.try_get_scoped_with_name("queue")?;
+        std::result::Result::Ok(match selector.as_str() {
+            "Local" => {
+                let _0: dilib::Singleton<usize> = container.try_get_singleton()?;
+                Queue::Local(_0)
+            }
+            "Remote" => {
+                let url: String = container.try_get_scoped_with_name("QUEUE_URL")?;
+                Queue::Remote { url }
+            }
+            other => {
+                return std::result::Result::Err(dilib::ResolveError::new(
+                    ::alloc::__export::must_use({
+                        ::alloc::fmt::format(format_args!(
+                            "unknown variant `{0}` of `Queue` selected by \"queue\"",
+                            other
+                        ))
+                    }),
+                ))
+            }
+        })
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Queue {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<std::string::String>().with_name("queue"),
+            dilib::DependencyInfo::singleton::<usize>().with_default(),
+            dilib::DependencyInfo::scoped::<String>()
+                .with_name("QUEUE_URL")
+                .with_default(),
+        ])
+    }
+}
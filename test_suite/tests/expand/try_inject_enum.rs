@@ -0,0 +1,11 @@
+use dilib::{Singleton, TryInject};
+
+#[derive(TryInject)]
+#[inject(selector="queue")]
+enum Queue {
+    Local(Singleton<usize>),
+    Remote {
+        #[inject(name="QUEUE_URL")]
+        url: String
+    }
+}
@@ -5,14 +5,26 @@ impl dilib::Inject for TupleStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let _0: dilib::Singleton<usize> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `usize`");
         let _1: String = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `String`");
         let _2: bool = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `bool`");
         TupleStruct(_0, _1, _2)
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for TupleStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<usize>(),
+            dilib::DependencyInfo::scoped::<String>(),
+            dilib::DependencyInfo::scoped::<bool>(),
+        ])
+    }
 }
@@ -6,4 +6,12 @@ impl dilib::Inject for UnitStruct {
     fn inject(_: &dilib::Container) -> Self {
         UnitStruct
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for UnitStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::new()
+    }
 }
@@ -6,4 +6,12 @@ impl dilib::Inject for UnitStructBrackets {
     fn inject(container: &dilib::Container) -> Self {
         UnitStructBrackets {}
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for UnitStructBrackets {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::new()
+    }
 }
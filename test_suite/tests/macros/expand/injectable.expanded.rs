@@ -0,0 +1,54 @@
+use dilib::{injectable, Singleton};
+use std::sync::Arc;
+struct Config;
+struct Cache;
+struct Repository {
+    config: Arc<Config>,
+    cache: Singleton<Cache>,
+    label: String,
+    retries: usize,
+}
+impl Repository {
+    fn new(config: Arc<Config>, cache: Singleton<Cache>, label: String, retries: usize) -> Self {
+        Repository {
+            config,
+            cache,
+            label,
+            retries,
+        }
+    }
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
+impl dilib::Inject for Repository {
+    #[allow(unused)]
+    fn inject(container: &dilib::Container) -> Self {
+        let config = container
+            .try_get_singleton::<Config>()
+            .expect("failed to resolve: Config");
+        let cache = container
+            .try_get_scoped_singleton::<Cache>()
+            .expect("failed to resolve: Cache");
+        let label = container
+            .try_get_scoped_with_name::<String>("label")
+            .expect("failed to resolve: String");
+        let retries = container
+            .try_get_scoped::<usize>()
+            .expect("failed to resolve: usize");
+        Self::new(config, cache, label, retries)
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Repository {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<Config>(),
+            dilib::DependencyInfo::scoped_singleton::<Cache>(),
+            dilib::DependencyInfo::scoped::<String>().with_name("label"),
+            dilib::DependencyInfo::scoped::<usize>(),
+        ])
+    }
+}
@@ -0,0 +1,27 @@
+use dilib::{injectable, Singleton};
+use std::sync::Arc;
+
+struct Config;
+
+struct Cache;
+
+struct Repository {
+    config: Arc<Config>,
+    cache: Singleton<Cache>,
+    label: String,
+    retries: usize,
+}
+
+#[injectable]
+impl Repository {
+    #[inject]
+    #[inject(label, name="label")]
+    #[inject(cache, scope="scoped_singleton")]
+    fn new(config: Arc<Config>, cache: Singleton<Cache>, label: String, retries: usize) -> Self {
+        Repository { config, cache, label, retries }
+    }
+
+    fn label(&self) -> &str {
+        &self.label
+    }
+}
@@ -0,0 +1,43 @@
+use dilib::injectable;
+use std::sync::Arc;
+struct Config {
+    url: String,
+}
+struct Pool {
+    url: String,
+}
+impl Pool {
+    async fn connect(config: Arc<Config>, retries: u32) -> Self {
+        let _ = retries;
+        Pool {
+            url: config.url.clone(),
+        }
+    }
+}
+impl dilib::AsyncInject for Pool {
+    #[allow(unused)]
+    fn inject_async<'c>(container: &'c dilib::Container<'c>) -> dilib::InjectFuture<'c, Self> {
+        std::boxed::Box::pin(async move {
+            let config = container
+                .try_get_singleton_async::<Config>()
+                .await
+                .expect("failed to resolve: Config");
+            let retries = container
+                .try_get_scoped_async_with_name::<u32>("retries")
+                .await
+                .expect("failed to resolve: u32");
+            Self::connect(config, retries).await
+        })
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Pool {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<Config>(),
+            dilib::DependencyInfo::scoped::<u32>().with_name("retries"),
+        ])
+    }
+}
@@ -0,0 +1,20 @@
+use dilib::injectable;
+use std::sync::Arc;
+
+struct Config {
+    url: String,
+}
+
+struct Pool {
+    url: String,
+}
+
+#[injectable]
+impl Pool {
+    #[inject]
+    #[inject(retries, name="retries")]
+    async fn connect(config: Arc<Config>, retries: u32) -> Self {
+        let _ = retries;
+        Pool { url: config.url.clone() }
+    }
+}
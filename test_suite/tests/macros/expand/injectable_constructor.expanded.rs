@@ -0,0 +1,35 @@
+use dilib::injectable;
+use std::sync::Arc;
+struct Repository;
+struct Service {
+    repo: Arc<Repository>,
+    retries: usize,
+}
+impl Service {
+    fn new(repo: Arc<Repository>, retries: usize) -> Self {
+        Service { repo, retries }
+    }
+}
+impl dilib::Inject for Service {
+    #[allow(unused)]
+    fn inject(container: &dilib::Container) -> Self {
+        let repo = container
+            .try_get_singleton::<Repository>()
+            .expect("failed to resolve: Repository");
+        let retries = container
+            .try_get_scoped_with_name::<usize>("retries")
+            .expect("failed to resolve: usize");
+        Self::new(repo, retries)
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Service {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::singleton::<Repository>(),
+            dilib::DependencyInfo::scoped::<usize>().with_name("retries"),
+        ])
+    }
+}
@@ -0,0 +1,18 @@
+use dilib::injectable;
+use std::sync::Arc;
+
+struct Repository;
+
+struct Service {
+    repo: Arc<Repository>,
+    retries: usize,
+}
+
+#[injectable]
+impl Service {
+    #[inject(constructor)]
+    #[inject(retries, name="retries")]
+    fn new(repo: Arc<Repository>, retries: usize) -> Self {
+        Service { repo, retries }
+    }
+}
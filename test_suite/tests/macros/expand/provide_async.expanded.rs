@@ -0,0 +1,61 @@
+use dilib::provide;
+use std::sync::Arc;
+struct Config {
+    url: String,
+}
+struct Client {
+    url: String,
+}
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_connect_Client() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<Client>(),
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::from_async_construct::<Client, _>(|container| {
+                    std::boxed::Box::pin(async move {
+                        let config = container
+                            .try_get_singleton_async::<Config>()
+                            .await
+                            .expect("failed to resolve: Config");
+                        let retries = container
+                            .try_get_scoped_async::<u32>()
+                            .await
+                            .expect("failed to resolve: u32");
+                        let value: Client = connect(config, retries).await;
+                        value
+                    })
+                })
+                .with_dependencies([
+                    dilib::DependencyInfo::singleton::<Config>(),
+                    dilib::DependencyInfo::scoped::<u32>(),
+                ]),
+            ),
+            multi: false,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_connect_Client___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_connect_Client___rust_ctor___ctor() {
+            dilib_connect_Client()
+        };
+        dilib_connect_Client___rust_ctor___ctor
+    };
+};
+async fn connect(config: Arc<Config>, retries: u32) -> Client {
+    let _ = retries;
+    Client {
+        url: config.url.clone(),
+    }
+}
@@ -0,0 +1,16 @@
+use dilib::provide;
+use std::sync::Arc;
+
+struct Config {
+    url: String,
+}
+
+struct Client {
+    url: String,
+}
+
+#[provide(scope="singleton")]
+async fn connect(config: Arc<Config>, retries: u32) -> Client {
+    let _ = retries;
+    Client { url: config.url.clone() }
+}
\ No newline at end of file
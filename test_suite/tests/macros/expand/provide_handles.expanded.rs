@@ -0,0 +1,57 @@
+use dilib::{provide, Factory, Lazy};
+struct Connection;
+struct Config;
+struct Pool {
+    connections: Factory<Connection>,
+    config: Lazy<Config>,
+}
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_pool_Pol() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<Pool>(),
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> Pool {
+                    let connections = container
+                        .try_get_factory::<Connection>()
+                        .expect("failed to resolve: Connection");
+                    let config = container
+                        .try_get_lazy::<Config>()
+                        .expect("failed to resolve: Config");
+                    pool(connections, config)
+                })
+                .with_dependencies([
+                    dilib::DependencyInfo::scoped::<Connection>().with_deferred(),
+                    dilib::DependencyInfo::scoped::<Config>()
+                        .with_deferred()
+                        .with_any_kind(),
+                ]),
+            ),
+            multi: false,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_pool_Pol___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_pool_Pol___rust_ctor___ctor() {
+            dilib_pool_Pol()
+        };
+        dilib_pool_Pol___rust_ctor___ctor
+    };
+};
+fn pool(connections: Factory<Connection>, config: Lazy<Config>) -> Pool {
+    Pool {
+        connections,
+        config,
+    }
+}
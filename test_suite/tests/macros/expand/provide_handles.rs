@@ -0,0 +1,14 @@
+use dilib::{provide, Factory, Lazy};
+
+struct Connection;
+struct Config;
+
+struct Pool {
+    connections: Factory<Connection>,
+    config: Lazy<Config>,
+}
+
+#[provide(scope="singleton")]
+fn pool(connections: Factory<Connection>, config: Lazy<Config>) -> Pool {
+    Pool { connections, config }
+}
\ No newline at end of file
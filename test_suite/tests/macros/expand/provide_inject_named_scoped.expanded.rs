@@ -11,11 +11,14 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::with_name::<MyData>("point"),
-            provider: dilib::Provider::Scoped(dilib::Scoped::from_construct(
-                |container: &dilib::Container| -> MyData {
+            provider: dilib::Provider::Scoped(
+                dilib::Scoped::from_construct(|container: &dilib::Container| -> MyData {
                     <MyData as dilib::Inject>::inject(container)
-                },
-            )),
+                })
+                .with_dependencies(<MyData as dilib::Inject>::dependencies()),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -40,11 +43,22 @@ impl dilib::Inject for MyData {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let x: i32 = container
-            .get_scoped_with_name("x")
+            .try_get_scoped_with_name("x")
             .expect("cannot get scoped value of name \"x\"");
         let y: i32 = container
-            .get_scoped_with_name("y")
+            .try_get_scoped_with_name("y")
             .expect("cannot get scoped value of name \"y\"");
         MyData { x, y }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyData {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<i32>().with_name("x"),
+            dilib::DependencyInfo::scoped::<i32>().with_name("y"),
+        ])
+    }
 }
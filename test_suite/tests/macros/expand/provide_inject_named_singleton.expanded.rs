@@ -11,11 +11,14 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::with_name::<MyData>("point"),
-            provider: dilib::Provider::Singleton(dilib::Shared::new_lazy(
-                |container: &dilib::Container| -> MyData {
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> MyData {
                     <MyData as dilib::Inject>::inject(container)
-                },
-            )),
+                })
+                .with_dependencies(<MyData as dilib::Inject>::dependencies()),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -37,8 +40,16 @@ impl dilib::Inject for MyData {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let s: dilib::Singleton<String> = container
-            .get_singleton()
+            .try_get_singleton()
             .expect("cannot get singleton value of type `String`");
         MyData { s }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyData {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::singleton::<String>()])
+    }
 }
@@ -11,11 +11,14 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<MyStruct>(),
-            provider: dilib::Provider::Singleton(dilib::Shared::new_lazy(
-                |container: &dilib::Container| -> MyStruct {
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> MyStruct {
                     <MyStruct as dilib::Inject>::inject(container)
-                },
-            )),
+                })
+                .with_dependencies(<MyStruct as dilib::Inject>::dependencies()),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -38,11 +41,22 @@ impl dilib::Inject for MyStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let x: usize = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `usize`");
         let y: Box<String> = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `Box<String>`");
         MyStruct { x, y }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([
+            dilib::DependencyInfo::scoped::<usize>(),
+            dilib::DependencyInfo::scoped::<Box<String>>(),
+        ])
+    }
 }
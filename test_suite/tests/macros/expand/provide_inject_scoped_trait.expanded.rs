@@ -12,11 +12,14 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<std::boxed::Box<dyn MyScopedTrait + Send + Sync>>(),
-            provider: dilib::Provider::Scoped(dilib::Scoped::from_construct(
-                |container: &dilib::Container| -> std::boxed::Box<dyn MyScopedTrait + Send + Sync> {
+            provider: dilib::Provider::Scoped(
+                dilib::Scoped::from_construct(|container: &dilib::Container| -> std::boxed::Box<dyn MyScopedTrait + Send + Sync> {
                     std::boxed::Box::new(<MyStruct as dilib::Inject>::inject(container))
-                },
-            )),
+                })
+                .with_dependencies(<MyStruct as dilib::Inject>::dependencies()),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
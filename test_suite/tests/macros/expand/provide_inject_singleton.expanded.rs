@@ -11,11 +11,14 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<MyStruct>(),
-            provider: dilib::Provider::Singleton(dilib::Shared::new_lazy(
-                |container: &dilib::Container| -> MyStruct {
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> MyStruct {
                     <MyStruct as dilib::Inject>::inject(container)
-                },
-            )),
+                })
+                .with_dependencies(<MyStruct as dilib::Inject>::dependencies()),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -37,8 +40,16 @@ impl dilib::Inject for MyStruct {
     #[allow(dead_code)]
     fn inject(container: &dilib::Container) -> Self {
         let val: String = container
-            .get_scoped()
+            .try_get_scoped()
             .expect("cannot get scoped value of type `String`");
         MyStruct { val }
     }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for MyStruct {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::from([dilib::DependencyInfo::scoped::<String>()])
+    }
 }
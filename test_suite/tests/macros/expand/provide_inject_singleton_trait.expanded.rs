@@ -10,7 +10,7 @@ const _: () = {
             .lock()
             .expect("unable to get providers lock");
         let providers = lock.as_mut().expect("unable to get providers");
-        providers . push (dilib :: global :: InjectProvider { key : dilib :: InjectionKey :: of :: < std :: boxed :: Box < dyn MySingletonTrait + Send + Sync > > () , provider : dilib :: Provider :: Singleton (dilib :: Shared :: new_lazy (| container : & dilib :: Container | -> std :: boxed :: Box < dyn MySingletonTrait + Send + Sync > { std :: boxed :: Box :: new (< MyStruct as dilib :: Inject > :: inject (container)) })) , }) ;
+        providers . push (dilib :: global :: InjectProvider { key : dilib :: InjectionKey :: of :: < std :: boxed :: Box < dyn MySingletonTrait + Send + Sync > > () , provider : dilib :: Provider :: Singleton (dilib :: Shared :: new_lazy (| container : & dilib :: Container | -> std :: boxed :: Box < dyn MySingletonTrait + Send + Sync > { std :: boxed :: Box :: new (< MyStruct as dilib :: Inject > :: inject (container)) }) . with_dependencies (< MyStruct as dilib :: Inject > :: dependencies ())) , multi : false , priority : 0 , }) ;
     }
     #[used]
     #[allow(non_upper_case_globals)]
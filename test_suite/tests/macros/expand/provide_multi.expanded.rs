@@ -0,0 +1,91 @@
+use dilib::{provide, Inject};
+trait Plugin {
+    fn name(&self) -> &str;
+}
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_Logger_Loger() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<std::boxed::Box<dyn Plugin + Send + Sync>>(),
+            provider: dilib::Provider::Scoped(
+                dilib::Scoped::from_construct(
+                    |container: &dilib::Container| -> std::boxed::Box<dyn Plugin + Send + Sync> {
+                        std::boxed::Box::new(<Logger as dilib::Inject>::inject(container))
+                    },
+                )
+                .with_dependencies(<Logger as dilib::Inject>::dependencies()),
+            ),
+            multi: true,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_Logger_Loger___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_Logger_Loger___rust_ctor___ctor() {
+            dilib_Logger_Loger()
+        };
+        dilib_Logger_Loger___rust_ctor___ctor
+    };
+};
+struct Logger;
+impl dilib::Inject for Logger {
+    #[allow(unused)]
+    #[allow(dead_code)]
+    fn inject(_: &dilib::Container) -> Self {
+        Logger
+    }
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        <Self as dilib::InjectMetadata>::dependencies()
+    }
+}
+impl dilib::InjectMetadata for Logger {
+    fn dependencies() -> std::vec::Vec<dilib::DependencyInfo> {
+        std::vec::Vec::new()
+    }
+}
+impl Plugin for Logger {
+    fn name(&self) -> &str {
+        "logger"
+    }
+}
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_stage_String() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<String>(),
+            provider: dilib::Provider::Singleton(dilib::Shared::new(stage())),
+            multi: true,
+            priority: 10,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_stage_String___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_stage_String___rust_ctor___ctor() {
+            dilib_stage_String()
+        };
+        dilib_stage_String___rust_ctor___ctor
+    };
+};
+fn stage() -> String {
+    String::from("build")
+}
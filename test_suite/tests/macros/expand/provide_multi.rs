@@ -0,0 +1,20 @@
+use dilib::{provide, Inject};
+
+trait Plugin {
+    fn name(&self) -> &str;
+}
+
+#[derive(Inject)]
+#[provide(multi, bind="Plugin")]
+struct Logger;
+
+impl Plugin for Logger {
+    fn name(&self) -> &str {
+        "logger"
+    }
+}
+
+#[provide(multi, scope="singleton", priority=10)]
+fn stage() -> String {
+    String::from("build")
+}
\ No newline at end of file
@@ -13,6 +13,8 @@ const _: () = {
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::with_name::<Mutex<usize>>("id"),
             provider: dilib::Provider::Singleton(dilib::Shared::new(get_next_id())),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -0,0 +1,56 @@
+use dilib::{provide, Singleton};
+struct Cache;
+struct Config;
+struct Service {
+    cache: Option<Cache>,
+    config: Option<Singleton<Config>>,
+}
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_service_Service() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<Service>(),
+            provider: dilib::Provider::Scoped(
+                dilib::Scoped::from_construct(|container: &dilib::Container| -> Service {
+                    let cache =
+                        dilib::ResolveResultExt::optional(container.try_get_scoped::<Cache>())
+                            .expect("failed to resolve: Cache");
+                    let config = dilib::ResolveResultExt::optional(
+                        container.try_get_singleton_with_name::<Config>("main"),
+                    )
+                    .expect("failed to resolve: Config");
+                    service(cache, config)
+                })
+                .with_dependencies([
+                    dilib::DependencyInfo::scoped::<Cache>().with_default(),
+                    dilib::DependencyInfo::singleton::<Config>()
+                        .with_name("main")
+                        .with_default(),
+                ]),
+            ),
+            multi: false,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_service_Service___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_service_Service___rust_ctor___ctor() {
+            dilib_service_Service()
+        };
+        dilib_service_Service___rust_ctor___ctor
+    };
+};
+#[inject(config, name = "main")]
+fn service(cache: Option<Cache>, config: Option<Singleton<Config>>) -> Service {
+    Service { cache, config }
+}
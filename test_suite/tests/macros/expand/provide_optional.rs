@@ -0,0 +1,15 @@
+use dilib::{provide, Singleton};
+
+struct Cache;
+struct Config;
+
+struct Service {
+    cache: Option<Cache>,
+    config: Option<Singleton<Config>>,
+}
+
+#[provide]
+#[inject(config, name="main")]
+fn service(cache: Option<Cache>, config: Option<Singleton<Config>>) -> Service {
+    Service { cache, config }
+}
\ No newline at end of file
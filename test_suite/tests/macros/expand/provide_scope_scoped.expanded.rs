@@ -12,6 +12,8 @@ const _: () = {
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<String>(),
             provider: dilib::Provider::Scoped(dilib::Scoped::from_factory(get_greet)),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -0,0 +1,43 @@
+use dilib::{inject, provide};
+use std::sync::{Arc, Mutex};
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_request_log_len_usize() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<usize>(),
+            provider: dilib::Provider::ScopedSingleton(
+                dilib::Scoped::from_construct(|container: &dilib::Container| -> usize {
+                    let log = container
+                        .try_get_scoped_singleton::<Mutex<Vec<String>>>()
+                        .expect("failed to resolve: Mutex<Vec<String>>");
+                    request_log_len(log)
+                })
+                .with_dependencies([dilib::DependencyInfo::scoped_singleton::<
+                    Mutex<Vec<String>>,
+                >()]),
+            ),
+            multi: false,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_request_log_len_usize___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_request_log_len_usize___rust_ctor___ctor() {
+            dilib_request_log_len_usize()
+        };
+        dilib_request_log_len_usize___rust_ctor___ctor
+    };
+};
+fn request_log_len(log: Arc<Mutex<Vec<String>>>) -> usize {
+    log.lock().unwrap().len()
+}
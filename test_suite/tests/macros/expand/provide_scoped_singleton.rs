@@ -0,0 +1,8 @@
+use dilib::{inject, provide};
+use std::sync::{Arc, Mutex};
+
+#[provide(scope="scoped_singleton")]
+#[inject(log, scope="scoped_singleton")]
+fn request_log_len(log: Arc<Mutex<Vec<String>>>) -> usize {
+    log.lock().unwrap().len()
+}
\ No newline at end of file
@@ -17,6 +17,8 @@ const _: () = {
                     get_scoped_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -51,6 +53,8 @@ const _: () = {
                     get_multiple_scoped_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -82,6 +86,8 @@ const _: () = {
                     get_multiple_scoped_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
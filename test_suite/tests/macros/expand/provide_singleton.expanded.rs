@@ -13,6 +13,8 @@ const _: () = {
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<Mutex<u32>>(),
             provider: dilib::Provider::Singleton(dilib::Shared::new(get_counter())),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
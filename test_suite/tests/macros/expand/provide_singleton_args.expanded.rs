@@ -0,0 +1,41 @@
+use dilib::provide;
+use std::sync::Arc;
+const _: () = {
+    #[cold]
+    #[doc(hidden)]
+    #[allow(non_snake_case)]
+    #[allow(dead_code)]
+    extern "C" fn dilib_get_name_len_usize() {
+        let mut lock = dilib::global::PROVIDERS
+            .lock()
+            .expect("unable to get providers lock");
+        let providers = lock.as_mut().expect("unable to get providers");
+        providers.push(dilib::global::InjectProvider {
+            key: dilib::InjectionKey::of::<usize>(),
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> usize {
+                    let name = container
+                        .try_get_singleton::<String>()
+                        .expect("failed to resolve: String");
+                    get_name_len(name)
+                })
+                .with_dependencies([dilib::DependencyInfo::singleton::<String>()]),
+            ),
+            multi: false,
+            priority: 0,
+        });
+    }
+    #[used]
+    #[allow(non_upper_case_globals)]
+    #[doc(hidden)]
+    #[link_section = ".CRT$XCU"]
+    static dilib_get_name_len_usize___rust_ctor___ctor: unsafe extern "C" fn() = {
+        unsafe extern "C" fn dilib_get_name_len_usize___rust_ctor___ctor() {
+            dilib_get_name_len_usize()
+        };
+        dilib_get_name_len_usize___rust_ctor___ctor
+    };
+};
+fn get_name_len(name: Arc<String>) -> usize {
+    name.len()
+}
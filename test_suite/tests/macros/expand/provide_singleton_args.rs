@@ -0,0 +1,7 @@
+use dilib::provide;
+use std::sync::Arc;
+
+#[provide(scope="singleton")]
+fn get_name_len(name: Arc<String>) -> usize {
+    name.len()
+}
@@ -17,6 +17,8 @@ const _: () = {
                     get_singleton_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -50,6 +52,8 @@ const _: () = {
                     get_multiple_singleton_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -80,6 +84,8 @@ const _: () = {
                     get_multiple_singleton_trait(),
                 )
             })),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -12,17 +12,23 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::with_name::<String>("scoped_data"),
-            provider: dilib::Provider::Scoped(dilib::Scoped::from_construct(
-                |container: &dilib::Container| -> String {
+            provider: dilib::Provider::Scoped(
+                dilib::Scoped::from_construct(|container: &dilib::Container| -> String {
                     let x = container
-                        .get_scoped::<i32>()
+                        .try_get_scoped::<i32>()
                         .expect("failed to resolve: i32");
                     let y = container
-                        .get_singleton::<u32>()
+                        .try_get_singleton::<u32>()
                         .expect("failed to resolve: u32");
                     compute_named_scoped(x, y)
-                },
-            )),
+                })
+                .with_dependencies([
+                    dilib::DependencyInfo::scoped::<i32>(),
+                    dilib::DependencyInfo::singleton::<u32>(),
+                ]),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -12,17 +12,23 @@ const _: () = {
         let providers = lock.as_mut().expect("unable to get providers");
         providers.push(dilib::global::InjectProvider {
             key: dilib::InjectionKey::of::<Mutex<String>>(),
-            provider: dilib::Provider::Singleton(dilib::Shared::from_factory(
-                |container: &dilib::Container| -> Mutex<String> {
+            provider: dilib::Provider::Singleton(
+                dilib::Shared::new_lazy(|container: &dilib::Container| -> Mutex<String> {
                     let x = container
-                        .get_scoped::<i32>()
+                        .try_get_scoped::<i32>()
                         .expect("failed to resolve: i32");
                     let y = container
-                        .get_singleton::<u32>()
+                        .try_get_singleton::<u32>()
                         .expect("failed to resolve: u32");
                     compute_singleton(x, y)
-                },
-            )),
+                })
+                .with_dependencies([
+                    dilib::DependencyInfo::scoped::<i32>(),
+                    dilib::DependencyInfo::singleton::<u32>(),
+                ]),
+            ),
+            multi: false,
+            priority: 0,
         });
     }
     #[used]
@@ -0,0 +1,8 @@
+use dilib::provide;
+
+#[provide(scope="scoped_singleton")]
+async fn get_string() -> String {
+    "hello".to_string()
+}
+
+fn main(){}
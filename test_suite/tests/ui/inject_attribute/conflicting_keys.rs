@@ -0,0 +1,12 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+struct MyStruct {
+    #[inject(default, name="text")]
+    a: String,
+
+    #[inject(all, name="values")]
+    b: Vec<usize>,
+}
+
+fn main(){}
\ No newline at end of file
@@ -0,0 +1,12 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+struct MyStruct {
+    #[inject(all)]
+    a: usize,
+
+    #[inject(optional)]
+    b: String,
+}
+
+fn main(){}
\ No newline at end of file
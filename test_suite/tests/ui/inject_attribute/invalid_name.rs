@@ -0,0 +1,9 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+struct MyStruct {
+    #[inject(name=1)]
+    a: String,
+}
+
+fn main(){}
\ No newline at end of file
@@ -0,0 +1,21 @@
+use dilib::{Container, Inject};
+
+struct Connection;
+
+fn connect(_: &Container) -> Connection {
+    Connection
+}
+
+#[derive(Inject)]
+struct Service {
+    #[inject(with = "connect", name = "connection")]
+    a: Connection,
+
+    #[inject(map = "connect", default)]
+    b: Connection,
+
+    #[inject(with = "connect(1)")]
+    c: Connection,
+}
+
+fn main(){}
\ No newline at end of file
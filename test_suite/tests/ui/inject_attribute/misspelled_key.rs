@@ -0,0 +1,12 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+struct MyStruct {
+    #[inject(scpoe="singleton")]
+    a: Singleton<usize>,
+
+    #[inject(nmae="text")]
+    b: String,
+}
+
+fn main(){}
\ No newline at end of file
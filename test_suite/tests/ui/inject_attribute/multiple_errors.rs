@@ -0,0 +1,16 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+#[inject(name="value")]
+struct MyStruct {
+    #[inject(scope="unique", data="value")]
+    a: Singleton<usize>,
+
+    #[inject(name="text", name="other")]
+    b: String,
+
+    #[inject(optional=1)]
+    c: Option<String>,
+}
+
+fn main(){}
\ No newline at end of file
@@ -0,0 +1,9 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+union MyUnion {
+    a: u32,
+    b: f32,
+}
+
+fn main(){}
\ No newline at end of file
@@ -0,0 +1,10 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+#[inject(constructor="new(a b)")]
+struct MyStruct {
+    a: String,
+    b: usize
+}
+
+fn main(){}
\ No newline at end of file
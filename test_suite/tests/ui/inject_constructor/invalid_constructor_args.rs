@@ -0,0 +1,18 @@
+use dilib::Inject;
+use std::sync::Arc;
+
+struct Repo;
+
+#[derive(Inject)]
+#[inject(constructor = "new(repo = name(\"main\"), retries: u32 = nmae(\"retries\"), repo: Arc<Repo>)")]
+struct Service {
+    retries: u32,
+}
+
+impl Service {
+    pub fn new(_: Arc<Repo>, retries: u32, _: Arc<Repo>) -> Self {
+        Service { retries }
+    }
+}
+
+fn main(){}
\ No newline at end of file
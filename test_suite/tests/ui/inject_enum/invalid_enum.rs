@@ -0,0 +1,21 @@
+use dilib::Inject;
+
+#[derive(Inject)]
+#[inject(variant = "Memroy")]
+enum Cache {
+    Memory(usize),
+    Redis(String),
+}
+
+#[derive(Inject)]
+enum Storage {
+    Disk(String),
+}
+
+#[derive(Inject)]
+#[inject(variant = "Disk", selector = "storage", constructor = "new()")]
+enum Backend {
+    Disk(String),
+}
+
+fn main(){}
\ No newline at end of file
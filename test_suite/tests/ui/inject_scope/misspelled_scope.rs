@@ -0,0 +1,9 @@
+use dilib::{Singleton, Inject};
+
+#[derive(Inject)]
+struct MyStruct {
+    #[inject(scope="singelton")]
+    a: Singleton<usize>,
+}
+
+fn main(){}
\ No newline at end of file
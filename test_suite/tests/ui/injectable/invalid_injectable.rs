@@ -0,0 +1,35 @@
+use dilib::injectable;
+
+struct Repository;
+
+struct MissingConstructor;
+
+#[injectable]
+impl MissingConstructor {
+    fn new() -> Self {
+        MissingConstructor
+    }
+}
+
+struct InvalidConstructor(usize);
+
+#[injectable]
+impl InvalidConstructor {
+    #[inject]
+    #[inject(vlaue, name = "value")]
+    fn create(value: usize) -> usize {
+        value
+    }
+
+    #[inject(constructor)]
+    fn other(value: usize) -> Self {
+        InvalidConstructor(value)
+    }
+}
+
+trait Service {}
+
+#[injectable(name = "service")]
+impl Service for Repository {}
+
+fn main(){}
\ No newline at end of file
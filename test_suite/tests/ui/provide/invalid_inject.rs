@@ -0,0 +1,11 @@
+use dilib::provide;
+use std::sync::Arc;
+
+#[provide]
+#[inject(vlaue, name="value")]
+#[inject(text, scope="singleton", data="data")]
+fn get_data(value: Arc<usize>, text: String) -> String {
+    format!("{} {}", value, text)
+}
+
+fn main(){}
\ No newline at end of file
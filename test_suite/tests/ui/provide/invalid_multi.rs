@@ -0,0 +1,8 @@
+use dilib::provide;
+
+#[provide(multi, name="value", scope="scoped_singleton")]
+fn get_value() -> usize {
+    0
+}
+
+fn main(){}
\ No newline at end of file
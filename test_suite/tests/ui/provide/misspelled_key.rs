@@ -0,0 +1,13 @@
+use dilib::provide;
+
+#[provide(scpoe="singleton", nmae="value")]
+fn get_value() -> usize {
+    0
+}
+
+#[provide(bnid="std::fmt::Debug")]
+fn get_debug() -> String {
+    String::new()
+}
+
+fn main(){}
\ No newline at end of file
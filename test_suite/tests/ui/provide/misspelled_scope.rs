@@ -0,0 +1,8 @@
+use dilib::provide;
+
+#[provide(scope="singelton")]
+fn get_value() -> usize {
+    0
+}
+
+fn main(){}
\ No newline at end of file